@@ -0,0 +1,34 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use iex::{iex, Outcome};
+
+#[iex]
+fn fails(n: u32) -> Result<(), u32> {
+    Err(black_box(n))
+}
+
+#[iex]
+fn chained(n: u32) -> Result<(), u64> {
+    fails(n)
+        .map_err(|err| err + 1)
+        .map_err(|err| err * 2)
+        .map_err(u64::from)
+}
+
+#[iex]
+fn composed(n: u32) -> Result<(), u64> {
+    fails(n).map_err(|err| u64::from((err + 1) * 2))
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("map_err on error path");
+    group.bench_function("three map_err calls", |b| {
+        b.iter(|| chained(black_box(1)).into_result())
+    });
+    group.bench_function("one composed map_err", |b| {
+        b.iter(|| composed(black_box(1)).into_result())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,206 @@
+//! Representative patterns, each implemented with `#[iex]` and with plain `Result`.
+//!
+//! Every benchmark group has an `iex` and a `result` entry computing the same thing. Compare the
+//! two entries within a group rather than across groups: the absolute numbers depend on the
+//! machine, but the ratio shows whether `#[iex]` pays off for the pattern. The `ok` groups measure
+//! the happy path, which is what `#[iex]` optimizes; the `err` groups measure the cost of throwing,
+//! which is expected to be much higher than with `Result`. A real program benefits from `#[iex]`
+//! if errors are rare enough that the first effect outweighs the second.
+//!
+//! The `result` versions are written by hand rather than generated with `#[iex(also_result)]`.
+//! The generated twins call `#[iex]` callees through `.into_result()` instead of calling their
+//! twins, so they would measure the cost of catching at every layer rather than that of `Result`.
+//!
+//! Run with `cargo bench --bench patterns`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use iex::{iex, Outcome};
+
+// Deep `?` chains
+
+#[iex]
+fn deep_iex(n: u32) -> Result<u64, u32> {
+    if n == 0 {
+        Ok(black_box(1))
+    } else {
+        Ok(deep_iex(black_box(n - 1))? + 1)
+    }
+}
+
+fn deep_result(n: u32) -> Result<u64, u32> {
+    if n == 0 {
+        Ok(black_box(1))
+    } else {
+        Ok(deep_result(black_box(n - 1))? + 1)
+    }
+}
+
+// Error type conversions across layers. The payloads are only propagated, never inspected.
+
+#[allow(dead_code)]
+struct LowError(u32);
+
+#[allow(dead_code)]
+struct MidError(LowError);
+
+impl From<LowError> for MidError {
+    fn from(err: LowError) -> Self {
+        Self(err)
+    }
+}
+
+#[allow(dead_code)]
+enum HighError {
+    Mid(MidError),
+    Other(String),
+}
+
+impl From<MidError> for HighError {
+    fn from(err: MidError) -> Self {
+        Self::Mid(err)
+    }
+}
+
+#[iex]
+fn low_iex(x: u32) -> Result<u32, LowError> {
+    if x == 0 {
+        Err(LowError(x))
+    } else {
+        Ok(black_box(x))
+    }
+}
+
+#[iex]
+fn mid_iex(x: u32) -> Result<u32, MidError> {
+    Ok(low_iex(x)? + low_iex(x)?)
+}
+
+#[iex]
+fn high_iex(x: u32) -> Result<u32, HighError> {
+    Ok(mid_iex(x)? + mid_iex(x)?)
+}
+
+fn low_result(x: u32) -> Result<u32, LowError> {
+    if x == 0 {
+        Err(LowError(x))
+    } else {
+        Ok(black_box(x))
+    }
+}
+
+fn mid_result(x: u32) -> Result<u32, MidError> {
+    Ok(low_result(x)? + low_result(x)?)
+}
+
+fn high_result(x: u32) -> Result<u32, HighError> {
+    Ok(mid_result(x)? + mid_result(x)?)
+}
+
+// Loops where some iterations fail and the error is handled locally
+
+#[iex]
+fn check_iex(x: u32) -> Result<u32, u32> {
+    if x.is_multiple_of(100) {
+        Err(x)
+    } else {
+        Ok(black_box(x))
+    }
+}
+
+fn check_result(x: u32) -> Result<u32, u32> {
+    if x.is_multiple_of(100) {
+        Err(x)
+    } else {
+        Ok(black_box(x))
+    }
+}
+
+fn occasional_iex(n: u32) -> (u64, u32) {
+    let mut sum = 0;
+    let mut failures = 0;
+    for x in 1..=n {
+        match check_iex(x).into_result() {
+            Ok(value) => sum += u64::from(value),
+            Err(_) => failures += 1,
+        }
+    }
+    (sum, failures)
+}
+
+fn occasional_result(n: u32) -> (u64, u32) {
+    let mut sum = 0;
+    let mut failures = 0;
+    for x in 1..=n {
+        match check_result(x) {
+            Ok(value) => sum += u64::from(value),
+            Err(_) => failures += 1,
+        }
+    }
+    (sum, failures)
+}
+
+// The hot path: many fallible calls, none of which fail
+
+#[iex]
+fn step_iex(x: u32) -> Result<u32, u32> {
+    if x == u32::MAX {
+        Err(x)
+    } else {
+        Ok(black_box(x) ^ 1)
+    }
+}
+
+#[iex]
+fn hot_iex(n: u32) -> Result<u64, u32> {
+    let mut sum = 0;
+    for x in 0..n {
+        sum += u64::from(step_iex(x)?);
+    }
+    Ok(sum)
+}
+
+fn step_result(x: u32) -> Result<u32, u32> {
+    if x == u32::MAX {
+        Err(x)
+    } else {
+        Ok(black_box(x) ^ 1)
+    }
+}
+
+fn hot_result(n: u32) -> Result<u64, u32> {
+    let mut sum = 0;
+    for x in 0..n {
+        sum += u64::from(step_result(x)?);
+    }
+    Ok(sum)
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("deep chain ok");
+    group.bench_function("iex", |b| b.iter(|| deep_iex(black_box(100)).into_result()));
+    group.bench_function("result", |b| b.iter(|| deep_result(black_box(100))));
+    group.finish();
+
+    let mut group = c.benchmark_group("conversions ok");
+    group.bench_function("iex", |b| b.iter(|| high_iex(black_box(1)).into_result()));
+    group.bench_function("result", |b| b.iter(|| high_result(black_box(1))));
+    group.finish();
+
+    let mut group = c.benchmark_group("conversions err");
+    group.bench_function("iex", |b| b.iter(|| high_iex(black_box(0)).into_result()));
+    group.bench_function("result", |b| b.iter(|| high_result(black_box(0))));
+    group.finish();
+
+    let mut group = c.benchmark_group("loop with 1% errors");
+    group.bench_function("iex", |b| b.iter(|| occasional_iex(black_box(1000))));
+    group.bench_function("result", |b| b.iter(|| occasional_result(black_box(1000))));
+    group.finish();
+
+    let mut group = c.benchmark_group("hot loop ok");
+    group.bench_function("iex", |b| b.iter(|| hot_iex(black_box(1000)).into_result()));
+    group.bench_function("result", |b| b.iter(|| hot_result(black_box(1000))));
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,65 @@
+//! A synthetic binary with 400 small `#[iex]` functions, each with its own error type converted on
+//! propagation, for measuring the effect of the `always_box_error` feature on code size.
+//!
+//! Build it both ways, strip the binaries, and compare their sizes:
+//!
+//! ```text
+//! cargo build --release --example binary_size
+//! cargo build --release --example binary_size --features always_box_error --target-dir target/boxed
+//! strip -o inline target/release/examples/binary_size
+//! strip -o boxed target/boxed/release/examples/binary_size
+//! ls -l inline boxed
+//! ```
+
+use iex::{iex, Outcome};
+use std::hint::black_box;
+
+struct Error<const N: usize>(u32);
+
+struct Wide(usize, u32);
+
+impl<const N: usize> From<Error<N>> for Wide {
+    fn from(err: Error<N>) -> Self {
+        Self(N, err.0)
+    }
+}
+
+#[iex]
+#[inline(never)]
+fn step<const N: usize>(x: u32) -> Result<u32, Error<N>> {
+    if x == N as u32 {
+        Err(Error(x))
+    } else {
+        Ok(black_box(x) + 1)
+    }
+}
+
+#[iex]
+#[inline(never)]
+fn propagate<const N: usize>(x: u32) -> Result<u32, Wide> {
+    Ok(step::<N>(black_box(x))? * 2)
+}
+
+macro_rules! row {
+    ($total:ident, $x:ident, $a:literal; $($b:literal)*) => {
+        $(
+            $total += match propagate::<{ $a * 20 + $b }>($x).into_result() {
+                Ok(value) => value as usize,
+                Err(Wide(n, value)) => n + value as usize,
+            };
+        )*
+    };
+}
+
+macro_rules! all {
+    ($total:ident, $x:ident; $($a:literal)*) => {
+        $(row!($total, $x, $a; 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19);)*
+    };
+}
+
+fn main() {
+    let x = black_box(std::env::args().count() as u32);
+    let mut total = 0;
+    all!(total, x; 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19);
+    println!("{total}");
+}
@@ -0,0 +1,19 @@
+//! Sums the numbers passed as arguments, failing on the first invalid one.
+
+use iex::iex;
+use std::num::ParseIntError;
+
+#[iex]
+fn parse(s: &str) -> Result<u32, ParseIntError> {
+    s.parse()
+}
+
+#[iex::main]
+fn main() -> Result<(), ParseIntError> {
+    let mut sum = 0;
+    for arg in std::env::args().skip(1) {
+        sum += parse(&arg)?;
+    }
+    println!("{sum}");
+    Ok(())
+}
@@ -1,18 +1,31 @@
 use darling::{ast::NestedMeta, FromAttributes, FromMeta};
 use proc_macro2::{Group, Span, TokenStream, TokenTree};
-use quote::{quote, quote_spanned};
+use quote::{format_ident, quote, quote_spanned};
 use syn::{
-    parse, parse_macro_input, parse_quote, parse_quote_spanned, parse_str,
+    parse,
+    parse::{ParseStream, Parser},
+    parse_macro_input, parse_quote, parse_quote_spanned, parse_str,
+    punctuated::Punctuated,
     spanned::Spanned,
-    visit_mut::{visit_expr_mut, VisitMut},
-    Block, Expr, ExprClosure, ExprMethodCall, ExprTry, Ident, ImplItemFn, ItemFn, Lifetime, Macro,
-    ReturnType, Signature, Stmt, TraitItemFn, Type,
+    visit_mut::{visit_expr_mut, visit_stmt_mut, visit_type_reference_mut, VisitMut},
+    Attribute, Block, Expr, ExprAsync, ExprCall, ExprClosure, ExprIf, ExprMacro, ExprMethodCall,
+    ExprTry, FnArg, GenericArgument, GenericParam, Ident, ImplItemFn, ItemFn, Lifetime, Macro, Pat,
+    PatIdent, PathArguments, ReturnType, Signature, Stmt, StmtMacro, Token, TraitItemFn, Type,
+    TypeBareFn, TypeReference, Visibility,
 };
 
 #[derive(FromMeta)]
 struct MacroArgs {
     #[darling(multiple)]
     captures: Vec<String>,
+    #[darling(default)]
+    also_result: bool,
+    #[darling(default)]
+    fn_ptr: bool,
+    #[darling(default)]
+    named_body: bool,
+    output: Option<String>,
+    error: Option<String>,
 }
 
 #[derive(FromAttributes, Debug)]
@@ -22,12 +35,16 @@ struct MapErrMacroArgs {
     shares: Vec<Ident>,
 }
 
+// All bindings introduced by the macros are named `__iex_*` and use `Span::mixed_site()`. The span
+// hides them from user code, but it does not affect item lookup, so a constant or a unit struct
+// named e.g. `marker` in scope would turn `let marker = ..` into a pattern match. The prefix makes
+// such collisions unrealistic.
 struct ReplaceSelf;
 
 impl VisitMut for ReplaceSelf {
     fn visit_ident_mut(&mut self, node: &mut Ident) {
         if node == "self" {
-            *node = Ident::new("iex_self", Span::mixed_site());
+            *node = Ident::new("__iex_self", Span::mixed_site());
         }
     }
     fn visit_macro_mut(&mut self, node: &mut Macro) {
@@ -37,7 +54,7 @@ impl VisitMut for ReplaceSelf {
                 .into_iter()
                 .map(|tree| match tree {
                     TokenTree::Ident(ident) if ident == "self" => {
-                        TokenTree::Ident(Ident::new("iex_self", Span::mixed_site()))
+                        TokenTree::Ident(Ident::new("__iex_self", Span::mixed_site()))
                     }
                     TokenTree::Group(group) => {
                         let mut new_group =
@@ -71,7 +88,7 @@ fn generate_map_inspect_err(
             if ident == "self" {
                 ReplaceSelf.visit_expr_mut(outcome);
                 ReplaceSelf.visit_expr_mut(closure);
-                Ident::new("iex_self", Span::mixed_site())
+                Ident::new("__iex_self", Span::mixed_site())
             } else {
                 ident.clone()
             }
@@ -79,26 +96,26 @@ fn generate_map_inspect_err(
         .collect();
 
     let body = if method == "map_err" {
-        quote_spanned! { Span::mixed_site() => (#closure)(err) }
+        quote_spanned! { Span::mixed_site() => (#closure)(__iex_err) }
     } else if method == "inspect_err" {
-        quote_spanned! { Span::mixed_site() => { (#closure)(&err); err } }
+        quote_spanned! { Span::mixed_site() => { (#closure)(&__iex_err); __iex_err } }
     } else {
         unreachable!()
     };
 
     parse_quote_spanned! {
         Span::mixed_site() => {
-            let mut exception_mapper = ::iex::imp::ExceptionMapper::new(
-                marker,
+            let mut __iex_exception_mapper = ::iex::imp::ExceptionMapper::new(
+                __iex_marker,
                 (#(#shares_original,)*),
-                |(#(mut #shares,)*), err| #body,
+                |(#(mut #shares,)*), __iex_err| #body,
             );
-            let marker = exception_mapper.get_in_marker();
-            let (#(#shares,)*) = exception_mapper.get_state();
+            let __iex_marker = __iex_exception_mapper.get_in_marker();
+            let (#(#shares,)*) = __iex_exception_mapper.get_state();
             #(let mut #shares = #shares;)*
-            let value = (marker, ::core::mem::ManuallyDrop::new(#outcome))._iex_forward();
-            exception_mapper.swallow();
-            value
+            let __iex_value = (__iex_marker, ::core::mem::ManuallyDrop::new(#outcome))._iex_forward();
+            __iex_exception_mapper.swallow();
+            __iex_value
         }
     }
 }
@@ -137,44 +154,459 @@ fn try_parse_map_inspect_err(expr: &mut Expr) -> darling::Result<Option<Expr>> {
     )))
 }
 
+fn is_ctx_macro(mac: &Macro) -> bool {
+    let segments: Vec<_> = mac.path.segments.iter().map(|s| &s.ident).collect();
+    match segments[..] {
+        [name] => name == "ctx",
+        [krate, name] => krate == "iex" && name == "ctx",
+        _ => false,
+    }
+}
+
+// `bail!` and `ensure!`, either ours or anyhow's. Both expand to `return Err(..)`, which doesn't
+// type-check in #[iex] functions whose tail is an outcome, so they are rewritten to throw the
+// error via the marker instead.
+fn is_bail_or_ensure_macro(mac: &Macro) -> bool {
+    matches!(bail_or_ensure_name(mac), Some(name) if name == "bail" || name == "ensure")
+}
+
+fn is_bail_macro(mac: &Macro) -> bool {
+    matches!(bail_or_ensure_name(mac), Some(name) if name == "bail")
+}
+
+fn bail_or_ensure_name(mac: &Macro) -> Option<&Ident> {
+    let segments: Vec<_> = mac.path.segments.iter().map(|s| &s.ident).collect();
+    match segments[..] {
+        [name] => Some(name),
+        [krate, name] if krate == "iex" || krate == "anyhow" => Some(name),
+        _ => None,
+    }
+}
+
+// The macro is expanded in a closure returning a `Result`, so any `return Err(..)` it expands to
+// returns from the closure, and the error is thrown by the marker. For `ensure!(cond, err..)`, only
+// the failure branch is put in the closure, so that `err` isn't moved if `cond` holds.
+fn rewrite_bail_or_ensure(mac: &Macro) -> Expr {
+    let span = mac.bang_token.span;
+    let marker = Ident::new("__iex_marker", span.resolved_at(Span::mixed_site()));
+    if is_bail_macro(mac) {
+        return parse_quote_spanned! { span => #marker.bail(|| #mac) };
+    }
+    if let Ok((cond, err)) = mac.parse_body_with(parse_ensure_args) {
+        if !err.is_empty() {
+            let path = &mac.path;
+            return parse_quote_spanned! {
+                span =>
+                if !(#cond) {
+                    #marker.bail(|| {
+                        #path!(false, #err);
+                        ::core::unreachable!()
+                    })
+                }
+            };
+        }
+    }
+    // `anyhow::ensure!(cond)` derives the message from the condition, so it's kept as is
+    parse_quote_spanned! {
+        span =>
+        #marker.ensure(|| {
+            #mac;
+            ::core::result::Result::Ok(())
+        })
+    }
+}
+
+fn parse_ensure_args(input: ParseStream) -> syn::Result<(Expr, TokenStream)> {
+    let cond = input.parse()?;
+    if input.is_empty() {
+        return Ok((cond, TokenStream::new()));
+    }
+    input.parse::<Token![,]>()?;
+    Ok((cond, input.parse()?))
+}
+
+fn parse_ctx_args(mac: &Macro) -> syn::Result<(Expr, Expr)> {
+    let args = mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)?;
+    <[Expr; 2]>::try_from(args.into_iter().collect::<Vec<_>>())
+        .map(|[outcome, context]| (outcome, context))
+        .map_err(|_| syn::Error::new_spanned(&mac.tokens, "expected `ctx!(outcome, context)`"))
+}
+
+// `outcome.into_result()?` would catch the error only to throw it again. `outcome?` propagates the
+// same error with the same conversion, so the call is skipped if the receiver is an outcome. This is
+// decided by type rather than by name, because other types may have `into_result` methods too.
+fn skip_into_result(expr: &mut Box<Expr>) {
+    if let Expr::MethodCall(ExprMethodCall {
+        receiver,
+        method,
+        turbofish: None,
+        args,
+        ..
+    }) = &mut **expr
+    {
+        if method == "into_result" && args.is_empty() {
+            let inner = Ident::new("__iex_inner", Span::mixed_site());
+            *expr = parse_quote_spanned! {
+                method.span() =>
+                {
+                    #[allow(unused_imports)]
+                    use ::iex::imp::{_IexCallIntoResult as _, _IexSkipIntoResult as _};
+                    ::iex::imp::_IexIntoResult(::core::cell::Cell::new(::core::option::Option::Some(
+                        #receiver
+                    )))
+                    ._iex_into_result(|#inner| #inner.#method())
+                }
+            };
+        }
+    }
+}
+
 struct ReplaceTry {
     errors: darling::error::Accumulator,
 }
 
+impl ReplaceTry {
+    // A `bail!` that ends the body is left alone. The `return Err(..)` it expands to is what tells
+    // the compiler the return type of a body that never completes normally.
+    fn visit_body_mut(&mut self, stmts: &mut [Stmt]) {
+        let Some((last, rest)) = stmts.split_last_mut() else {
+            return;
+        };
+        for stmt in rest {
+            self.visit_stmt_mut(stmt);
+        }
+        if !is_tail_bail(last) {
+            self.visit_stmt_mut(last);
+        }
+    }
+}
+
+fn is_tail_bail(stmt: &Stmt) -> bool {
+    match stmt {
+        Stmt::Macro(StmtMacro { mac, .. }) | Stmt::Expr(Expr::Macro(ExprMacro { mac, .. }), _) => {
+            is_bail_macro(mac)
+        }
+        _ => false,
+    }
+}
+
 impl VisitMut for ReplaceTry {
     fn visit_expr_mut(&mut self, node: &mut Expr) {
-        if let Expr::Try(ExprTry { expr, .. }) = node {
+        if let Expr::Try(ExprTry {
+            expr,
+            question_token,
+            ..
+        }) = node
+        {
+            // Point conversion errors and `#[track_caller]` locations at the `?`. The location
+            // would point at the macro invocation if the call was resolved at the mixed site, so
+            // only our identifiers are hygienic.
+            let span = question_token.span;
+            let marker = Ident::new("__iex_marker", span.resolved_at(Span::mixed_site()));
+            skip_into_result(expr);
             *node = self
                 .errors
                 .handle_in(|| try_parse_map_inspect_err(expr))
                 .unwrap_or(None)
                 .unwrap_or_else(|| {
                     parse_quote_spanned! {
-                        Span::mixed_site() =>
-                        (marker, ::core::mem::ManuallyDrop::new(#expr))._iex_forward()
+                        span =>
+                        (#marker, ::core::mem::ManuallyDrop::new(#expr))._iex_forward()
                     }
                 });
         }
+        if let Expr::Macro(ExprMacro { mac, .. }) = node {
+            if is_bail_or_ensure_macro(mac) {
+                *node = rewrite_bail_or_ensure(mac);
+                return;
+            }
+        }
         visit_expr_mut(self, node);
     }
-    // Don't recurse into other functions or closures
+    fn visit_stmt_mut(&mut self, node: &mut Stmt) {
+        // `bail!(..);` is parsed as a macro statement rather than an expression
+        if let Stmt::Macro(StmtMacro {
+            attrs,
+            mac,
+            semi_token,
+        }) = node
+        {
+            if is_bail_or_ensure_macro(mac) {
+                let mut expr = rewrite_bail_or_ensure(mac);
+                if let Expr::MethodCall(ExprMethodCall {
+                    attrs: expr_attrs, ..
+                })
+                | Expr::If(ExprIf {
+                    attrs: expr_attrs, ..
+                }) = &mut expr
+                {
+                    *expr_attrs = std::mem::take(attrs);
+                }
+                *node = Stmt::Expr(expr, *semi_token);
+                return;
+            }
+        }
+        visit_stmt_mut(self, node);
+    }
+    fn visit_macro_mut(&mut self, node: &mut Macro) {
+        if !is_ctx_macro(node) {
+            return;
+        }
+        let Some((mut outcome, mut context)) =
+            self.errors.handle(parse_ctx_args(node).map_err(Into::into))
+        else {
+            return;
+        };
+        self.visit_expr_mut(&mut outcome);
+        self.visit_expr_mut(&mut context);
+        // Invoke ctx! again, but with access to the marker, so that the macro is considered used
+        let span = node.bang_token.span.resolved_at(Span::mixed_site());
+        node.tokens = quote_spanned! { span => @__iex_marker, #outcome, #context };
+    }
+    // Don't recurse into other functions, closures, or async blocks
     fn visit_item_fn_mut(&mut self, _node: &mut ItemFn) {}
     fn visit_impl_item_fn_mut(&mut self, _node: &mut ImplItemFn) {}
     fn visit_trait_item_fn_mut(&mut self, _node: &mut TraitItemFn) {}
     fn visit_expr_closure_mut(&mut self, _node: &mut ExprClosure) {}
+    fn visit_expr_async_mut(&mut self, _node: &mut ExprAsync) {}
+}
+
+// Used for the `_as_result` twin generated by #[iex(also_result)]: casts every outcome to a
+// `Result` before applying the built-in `?` to it.
+struct ReplaceTryWithIntoResult {
+    errors: darling::error::Accumulator,
 }
 
-fn transform_trait_item_fn(captures: Vec<Lifetime>, input: TraitItemFn) -> proc_macro::TokenStream {
+impl ReplaceTryWithIntoResult {
+    fn try_rewrite_map_inspect_err(&mut self, expr: &mut Expr) -> bool {
+        let Expr::MethodCall(ExprMethodCall {
+            receiver,
+            method,
+            args,
+            ..
+        }) = expr
+        else {
+            return false;
+        };
+        if (method != "map_err" && method != "inspect_err") || args.len() != 1 {
+            return false;
+        }
+        let Expr::Closure(ExprClosure { ref mut attrs, .. }) = args[0] else {
+            return false;
+        };
+        let Some(parsed_attrs) = self.errors.handle(MapErrMacroArgs::from_attributes(attrs)) else {
+            return false;
+        };
+        if parsed_attrs.shares.is_empty() {
+            return false;
+        }
+        // The outcome is consumed before the closure is invoked, so sharing is not an issue
+        attrs.retain(|attr| !attr.path().is_ident("iex"));
+        **receiver = parse_quote! { ::iex::Outcome::into_result(#receiver) };
+        true
+    }
+}
+
+impl VisitMut for ReplaceTryWithIntoResult {
+    fn visit_expr_mut(&mut self, node: &mut Expr) {
+        if let Expr::Try(ExprTry { expr, .. }) = node {
+            if !self.try_rewrite_map_inspect_err(expr) {
+                **expr = parse_quote! { ::iex::Outcome::into_result(#expr) };
+            }
+        } else if let Expr::Macro(ExprMacro { mac, .. }) = node {
+            if is_ctx_macro(mac) {
+                if let Some((outcome, context)) =
+                    self.errors.handle(parse_ctx_args(mac).map_err(Into::into))
+                {
+                    *node = parse_quote! {
+                        ::iex::Context::context(::iex::Outcome::into_result(#outcome), #context)?
+                    };
+                }
+            }
+        }
+        visit_expr_mut(self, node);
+    }
+    fn visit_stmt_mut(&mut self, node: &mut Stmt) {
+        // `ctx!(..);` is parsed as a macro statement rather than an expression
+        if let Stmt::Macro(StmtMacro {
+            attrs,
+            mac,
+            semi_token,
+        }) = node
+        {
+            if is_ctx_macro(mac) {
+                *node = Stmt::Expr(
+                    Expr::Macro(ExprMacro {
+                        attrs: std::mem::take(attrs),
+                        mac: mac.clone(),
+                    }),
+                    *semi_token,
+                );
+            }
+        }
+        visit_stmt_mut(self, node);
+    }
+    // Don't recurse into other functions, closures, or async blocks
+    fn visit_item_fn_mut(&mut self, _node: &mut ItemFn) {}
+    fn visit_impl_item_fn_mut(&mut self, _node: &mut ImplItemFn) {}
+    fn visit_trait_item_fn_mut(&mut self, _node: &mut TraitItemFn) {}
+    fn visit_expr_closure_mut(&mut self, _node: &mut ExprClosure) {}
+    fn visit_expr_async_mut(&mut self, _node: &mut ExprAsync) {}
+}
+
+fn result_twin_attrs(name: &Ident, attrs: &[Attribute]) -> Vec<Attribute> {
+    let doc = format!(
+        "Plain [`Result`] version of `{name}`, generated by `#[iex(also_result)]`.\n\nThis \
+         function has the same logic, but propagates errors by value instead of unwinding."
+    );
+    // The twin is only called when profiling, so don't warn if it's unused
+    let mut twin_attrs = vec![
+        parse_quote! { #[doc = #doc] },
+        parse_quote! { #[allow(dead_code)] },
+    ];
+    twin_attrs.extend(
+        attrs
+            .iter()
+            .filter(|attr| !attr.path().is_ident("doc"))
+            .cloned(),
+    );
+    twin_attrs
+}
+
+fn result_twin_ident(name: &Ident) -> Ident {
+    format_ident!("{}_as_result", name)
+}
+
+fn fn_ptr_shim_attrs(name: &Ident, attrs: &[Attribute]) -> Vec<Attribute> {
+    let doc = format!(
+        "Version of `{name}` that can be coerced to a `fn` pointer, generated by \
+         `#[iex(fn_ptr)]`.\n\nThis function calls `{name}` and casts the outcome to a [`Result`]."
+    );
+    let mut shim_attrs = vec![parse_quote! { #[doc = #doc] }];
+    shim_attrs.extend(
+        attrs
+            .iter()
+            .filter(|attr| !attr.path().is_ident("doc"))
+            .cloned(),
+    );
+    shim_attrs
+}
+
+fn fn_ptr_shim_ident(name: &Ident) -> Ident {
+    format_ident!("{}_fn_ptr", name)
+}
+
+fn returns_impl_trait(output: &ReturnType) -> bool {
+    matches!(output, ReturnType::Type(_, ty) if matches!(**ty, Type::ImplTrait(_)))
+}
+
+// The output and error types of the outcome. These are usually projected from the declared return
+// type, but `impl Trait` can't be projected through, so they must be annotated in that case.
+fn outcome_types(
+    output: &ReturnType,
+    annotated_types: Option<(Type, Type)>,
+) -> syn::Result<(Type, Type)> {
+    if let Some(types) = annotated_types {
+        return Ok(types);
+    }
+    let result_type = match output {
+        ReturnType::Default => parse_quote! { () },
+        ReturnType::Type(_, result_type) => result_type.clone(),
+    };
+    if let Type::ImplTrait(_) = *result_type {
+        return Err(syn::Error::new_spanned(
+            result_type,
+            "#[iex] cannot infer the output and error types from `impl Trait`, annotate them with \
+             #[iex(output = \"..\", error = \"..\")]",
+        ));
+    }
+    Ok((
+        parse_quote! { <#result_type as ::iex::Outcome>::Output },
+        parse_quote! { <#result_type as ::iex::Outcome>::Error },
+    ))
+}
+
+// The twin and the shim return a `Result`. If the function is declared to return a `Result`, its
+// return type is kept as written, otherwise it's spelled out from the outcome types.
+fn result_return_type(output: &ReturnType, output_type: &Type, error_type: &Type) -> ReturnType {
+    if returns_impl_trait(output) {
+        parse_quote! { -> ::core::result::Result<#output_type, #error_type> }
+    } else {
+        output.clone()
+    }
+}
+
+// `#[async_trait]` is applied to the whole trait or impl block, so it runs before `#[iex]` and
+// leaves a method returning a boxed future, recognizable by its `'async_trait` lifetime.
+// Whether a type contains an elided lifetime, like `&str` or `Error<'_>`
+fn has_elided_lifetime(ty: &Type) -> bool {
+    struct FindElided(bool);
+    impl VisitMut for FindElided {
+        fn visit_type_reference_mut(&mut self, node: &mut TypeReference) {
+            self.0 |= node.lifetime.is_none();
+            visit_type_reference_mut(self, node);
+        }
+        fn visit_lifetime_mut(&mut self, node: &mut Lifetime) {
+            self.0 |= node.ident == "_";
+        }
+        // Function pointers bind their own lifetimes
+        fn visit_type_bare_fn_mut(&mut self, _node: &mut TypeBareFn) {}
+    }
+    let mut find = FindElided(false);
+    find.visit_type_mut(&mut ty.clone());
+    find.0
+}
+
+fn reject_async(sig: &Signature) -> Option<TokenStream> {
+    if let Some(asyncness) = sig.asyncness {
+        return Some(quote_spanned! {
+            asyncness.span() => compile_error!("#[iex] does not support async functions");
+        });
+    }
+    sig.generics
+        .lifetimes()
+        .any(|param| param.lifetime.ident == "async_trait")
+        .then(|| {
+            quote_spanned! {
+                sig.ident.span() =>
+                compile_error!(
+                    "#[iex] does not support #[async_trait] methods, call #[iex] functions from \
+                     the method body instead"
+                );
+            }
+        })
+}
+
+fn transform_trait_item_fn(
+    captures: Vec<Lifetime>,
+    annotated_types: Option<(Type, Type)>,
+    also_result: bool,
+    fn_ptr: bool,
+    named_body: bool,
+    input: TraitItemFn,
+) -> proc_macro::TokenStream {
     // If default is Some(..), the input should have already been parsed as an ItemFn.
     assert!(input.default.is_none());
 
-    let result_type = match input.sig.output {
-        ReturnType::Default => parse_quote! { () },
-        ReturnType::Type(_, ref result_type) => result_type.clone(),
+    if named_body {
+        return quote! {
+            compile_error!("#[iex(named_body)] is useless on trait methods without a body")
+        }
+        .into();
+    }
+
+    if let Some(err) = reject_async(&input.sig) {
+        return err.into();
+    }
+
+    let (output_type, error_type) = match outcome_types(&input.sig.output, annotated_types) {
+        Ok(types) => types,
+        Err(e) => return e.into_compile_error().into(),
     };
-    let output_type: Type = parse_quote! { <#result_type as ::iex::Outcome>::Output };
-    let error_type: Type = parse_quote! { <#result_type as ::iex::Outcome>::Error };
-    let to_impl_outcome: ReturnType = parse_quote! {
+    // Point diagnostics and IDE navigation at the declared return type
+    let output_span = input.sig.output.span();
+    let to_impl_outcome: ReturnType = parse_quote_spanned! {
+        output_span =>
         -> impl ::iex::Outcome<
             Output = #output_type,
             Error = #error_type,
@@ -200,6 +632,7 @@ fn transform_trait_item_fn(captures: Vec<Lifetime>, input: TraitItemFn) -> proc_
 
     let mut wrapper_attrs = input.attrs.clone();
     wrapper_attrs.insert(0, parse_quote! { #[cfg(not(doc))] });
+    wrapper_attrs.push(parse_quote! { #[allow(clippy::type_complexity)] });
     let wrapper_fn = TraitItemFn {
         attrs: wrapper_attrs,
         sig: wrapper_sig,
@@ -208,6 +641,44 @@ fn transform_trait_item_fn(captures: Vec<Lifetime>, input: TraitItemFn) -> proc_
     };
 
     let name = &input.sig.ident;
+    let result_output = result_return_type(&input.sig.output, &output_type, &error_type);
+
+    let twin_fn = also_result.then(|| TraitItemFn {
+        attrs: result_twin_attrs(name, &input.attrs),
+        sig: Signature {
+            ident: result_twin_ident(name),
+            output: result_output.clone(),
+            ..input.sig.clone()
+        },
+        default: None,
+        semi_token: input.semi_token,
+    });
+
+    let shim_fn = fn_ptr.then(|| {
+        // Arguments may be patterns, so give them names to forward them by
+        let mut sig = input.sig.clone();
+        let mut args = Vec::new();
+        for (i, arg) in sig.inputs.iter_mut().enumerate() {
+            match arg {
+                FnArg::Receiver(_) => args.push(quote! { self }),
+                FnArg::Typed(pat_type) => {
+                    let ident = format_ident!("__iex_arg{}", i, span = Span::mixed_site());
+                    *pat_type.pat = parse_quote! { #ident };
+                    args.push(quote! { #ident });
+                }
+            }
+        }
+        sig.ident = fn_ptr_shim_ident(name);
+        sig.output = result_output.clone();
+        TraitItemFn {
+            attrs: fn_ptr_shim_attrs(name, &input.attrs),
+            sig,
+            default: Some(parse_quote! {{
+                ::iex::Outcome::into_result(Self::#name(#(#args),*))
+            }}),
+            semi_token: None,
+        }
+    });
 
     let doc = format!(
         "
@@ -232,11 +703,176 @@ fn transform_trait_item_fn(captures: Vec<Lifetime>, input: TraitItemFn) -> proc_
     quote! {
         #wrapper_fn
         #doc_fn
+        #twin_fn
+        #shim_fn
     }
     .into()
 }
 
-fn transform_item_fn(captures: Vec<Lifetime>, input: ItemFn) -> proc_macro::TokenStream {
+// Attributes that apply to the signature of the function rather than to its body. Lint levels
+// apply to both, and the body is nested inside the wrapper, so they belong there too.
+fn is_wrapper_attr(attr: &Attribute) -> bool {
+    [
+        "doc", "must_use", "allow", "warn", "deny", "forbid", "expect",
+    ]
+    .iter()
+    .any(|name| attr.path().is_ident(name))
+}
+
+// Whether the return type is spelled as `Result<(), ..>`, possibly with a path, e.g.
+// `io::Result<()>`. Macros can't see through type aliases, so this is a purely syntactic check.
+fn returns_unit_result(output: &ReturnType) -> bool {
+    let ReturnType::Type(_, ty) = output else {
+        return false;
+    };
+    let Type::Path(path) = &**ty else {
+        return false;
+    };
+    let Some(segment) = path.path.segments.last() else {
+        return false;
+    };
+    if segment.ident != "Result" {
+        return false;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return false;
+    };
+    matches!(
+        args.args.first(),
+        Some(GenericArgument::Type(Type::Tuple(tuple))) if tuple.elems.is_empty()
+    )
+}
+
+// Append `Ok(())` to bodies of functions returning `Result<(), E>` that don't end with an
+// expression. `for` and `while` loops and `if` without `else` always evaluate to `()`, so they
+// count as statements. Bodies ending with `return ..;` are left alone, because the returned value
+// might be an `#[iex] Result`, which can't be mixed with other return values.
+fn add_implicit_ok(block: &mut Block) {
+    match block.stmts.last() {
+        Some(Stmt::Expr(Expr::ForLoop(_) | Expr::While(_), None)) => {}
+        Some(Stmt::Expr(
+            Expr::If(ExprIf {
+                else_branch: None, ..
+            }),
+            None,
+        )) => {}
+        Some(Stmt::Expr(_, None)) => return,
+        Some(Stmt::Expr(Expr::Return(_), Some(_))) => return,
+        // Could be either a statement or a tail expression
+        Some(Stmt::Macro(StmtMacro {
+            semi_token: None, ..
+        })) => return,
+        _ => {}
+    }
+    let span = block.brace_token.span.close();
+    block.stmts.push(parse_quote_spanned! {
+        span =>
+        // The body might diverge, e.g. by calling `panic!()` at the end
+        #[allow(unreachable_code)]
+        return ::core::result::Result::Ok(());
+    });
+}
+
+enum Forward {
+    // `inner(a, b)`
+    Bare(Expr),
+    // `Ok(inner(a, b)?)`, with the span of the `?`
+    Try(Expr, Span),
+}
+
+// A body that only passes its parameters on to another function, as in `inner(a, b)` or
+// `Ok(inner(a, b)?)`, doesn't need a closure of its own: the callee's outcome is reused. The
+// arguments must be parameters or literals, so that calling the callee early has no visible effect
+// for `#[iex]` callees, which don't run until their outcome is driven.
+fn forwarded_call(sig: &Signature, block: &Block) -> Option<Forward> {
+    let [Stmt::Expr(tail, None)] = &block.stmts[..] else {
+        return None;
+    };
+    let params: Vec<Ident> = sig
+        .inputs
+        .iter()
+        .filter_map(|arg| match arg {
+            FnArg::Receiver(receiver) => Some(Ident::new("self", receiver.self_token.span)),
+            FnArg::Typed(pat_type) => match &*pat_type.pat {
+                Pat::Ident(PatIdent {
+                    by_ref: None,
+                    subpat: None,
+                    ident,
+                    ..
+                }) => Some(ident.clone()),
+                _ => None,
+            },
+        })
+        .collect();
+    let is_forwardable = |expr: &Expr| match expr {
+        Expr::Lit(_) => true,
+        Expr::Path(path) => {
+            path.qself.is_none()
+                && path
+                    .path
+                    .get_ident()
+                    .is_some_and(|ident| params.contains(ident))
+        }
+        _ => false,
+    };
+    let is_forwarding_call = |expr: &Expr| match expr {
+        Expr::Call(ExprCall {
+            attrs, func, args, ..
+        }) => {
+            // `Ok(x)` and `Err(x)` are plain results, which are thrown rather than forwarded
+            let is_callee = match &**func {
+                Expr::Path(path) => path
+                    .path
+                    .segments
+                    .last()
+                    .is_some_and(|segment| segment.ident != "Ok" && segment.ident != "Err"),
+                _ => false,
+            };
+            attrs.is_empty() && is_callee && args.iter().all(is_forwardable)
+        }
+        Expr::MethodCall(ExprMethodCall {
+            attrs,
+            receiver,
+            args,
+            ..
+        }) => attrs.is_empty() && is_forwardable(receiver) && args.iter().all(is_forwardable),
+        _ => false,
+    };
+    if is_forwarding_call(tail) {
+        return Some(Forward::Bare(tail.clone()));
+    }
+    let Expr::Call(ExprCall {
+        attrs, func, args, ..
+    }) = tail
+    else {
+        return None;
+    };
+    let Expr::Path(ok) = &**func else {
+        return None;
+    };
+    if !attrs.is_empty() || !ok.path.is_ident("Ok") || args.len() != 1 {
+        return None;
+    }
+    let Expr::Try(ExprTry {
+        attrs,
+        expr,
+        question_token,
+    }) = &args[0]
+    else {
+        return None;
+    };
+    (attrs.is_empty() && is_forwarding_call(expr))
+        .then(|| Forward::Try((**expr).clone(), question_token.span))
+}
+
+fn transform_item_fn(
+    captures: Vec<Lifetime>,
+    annotated_types: Option<(Type, Type)>,
+    also_result: bool,
+    fn_ptr: bool,
+    named_body: bool,
+    mut input: ItemFn,
+) -> proc_macro::TokenStream {
     let input_span = input.span();
 
     if let Some(constness) = input.sig.constness {
@@ -245,25 +881,38 @@ fn transform_item_fn(captures: Vec<Lifetime>, input: ItemFn) -> proc_macro::Toke
         }
         .into();
     }
-    if let Some(asyncness) = input.sig.asyncness {
+    if let Some(err) = reject_async(&input.sig) {
+        return err.into();
+    }
+    if let (true, Some(receiver)) = (named_body, input.sig.receiver()) {
         return quote_spanned! {
-            asyncness.span() => compile_error!("#[iex] does not support async functions");
+            receiver.span() => compile_error!("#[iex(named_body)] is not supported on methods");
         }
         .into();
     }
 
-    let result_type = match input.sig.output {
-        ReturnType::Default => parse_quote! { () },
-        ReturnType::Type(_, ref result_type) => result_type.clone(),
+    let (output_type, error_type) = match outcome_types(&input.sig.output, annotated_types) {
+        Ok(types) => types,
+        Err(e) => return e.into_compile_error().into(),
     };
-    let output_type: Type = parse_quote! { <#result_type as ::iex::Outcome>::Output };
-    let error_type: Type = parse_quote! { <#result_type as ::iex::Outcome>::Error };
-    let to_impl_outcome: ReturnType = parse_quote! {
+    // Point diagnostics and IDE navigation at the declared return type
+    let output_span = input.sig.output.span();
+    let to_impl_outcome: ReturnType = parse_quote_spanned! {
+        output_span =>
         -> impl ::iex::Outcome<
             Output = #output_type,
             Error = #error_type,
         > #(+ ::iex::imp::fix_hidden_lifetime_bug::Captures<#captures>)*
     };
+    // The nested function takes a marker for the error type, which would count as an argument for
+    // lifetime elision
+    if named_body && has_elided_lifetime(&error_type) {
+        return quote_spanned! {
+            output_span =>
+            compile_error!("#[iex(named_body)] requires the lifetimes in the error type to be named");
+        }
+        .into();
+    }
 
     // We used to add '#result_type: ::iex::Outcome' to the 'where' condition. This is wrong for the
     // same reason that *this* fails to typecheck:
@@ -277,24 +926,68 @@ fn transform_item_fn(captures: Vec<Lifetime>, input: ItemFn) -> proc_macro::Toke
     //         let x: <T as Trait>::Exact = loop {};
     //         let y: T = x;
     //     }
-    let wrapper_sig = Signature {
+    let mut wrapper_sig = Signature {
         output: to_impl_outcome,
         ..input.sig.clone()
     };
 
+    if returns_unit_result(&input.sig.output) {
+        add_implicit_ok(&mut input.block);
+    }
+
+    let result_output = result_return_type(&input.sig.output, &output_type, &error_type);
+
+    let twin_fn = if also_result {
+        let mut twin_block = input.block.clone();
+        let mut replace_try = ReplaceTryWithIntoResult {
+            errors: darling::Error::accumulator(),
+        };
+        replace_try.visit_block_mut(&mut twin_block);
+        if let Err(err) = replace_try.errors.finish() {
+            return err.write_errors().into();
+        }
+        if returns_impl_trait(&input.sig.output) {
+            // The body evaluates to an outcome
+            twin_block = parse_quote! {{
+                ::iex::Outcome::into_result(#twin_block)
+            }};
+        }
+        Some(ItemFn {
+            attrs: result_twin_attrs(&input.sig.ident, &input.attrs),
+            vis: input.vis.clone(),
+            sig: Signature {
+                ident: result_twin_ident(&input.sig.ident),
+                output: result_output.clone(),
+                ..input.sig.clone()
+            },
+            block: twin_block,
+        })
+    } else {
+        None
+    };
+
+    // Type and lifetime errors about the returned value are reported at the expression that
+    // produces it. Point them at the tail of the user's body rather than at the whole function.
+    let tail_span = match input.block.stmts.last() {
+        Some(Stmt::Expr(expr, None)) => expr.span(),
+        _ => input_span,
+    };
+
+    let original_block = input.block.clone();
     let mut closure_block = input.block;
     let mut replace_try = ReplaceTry {
         errors: darling::Error::accumulator(),
     };
-    replace_try.visit_block_mut(&mut closure_block);
+    replace_try.visit_body_mut(&mut closure_block.stmts);
     if let Err(err) = replace_try.errors.finish() {
         return err.write_errors().into();
     }
 
-    let no_copy: Ident = parse_quote_spanned! { Span::mixed_site() => no_copy };
+    let no_copy: Ident = parse_quote_spanned! { Span::mixed_site() => __iex_no_copy };
+    let closure_ident: Ident = parse_quote_spanned! { Span::mixed_site() => __iex_closure };
 
     let mut closure: ExprClosure = parse_quote_spanned! {
-        Span::mixed_site() => move |marker: ::iex::imp::Marker<#error_type>| {
+        Span::mixed_site() => move |__iex_marker: ::iex::imp::Marker<#error_type>| {
             let #no_copy = #no_copy; // Force FnOnce inference
             #closure_block
         }
@@ -303,11 +996,55 @@ fn transform_item_fn(captures: Vec<Lifetime>, input: ItemFn) -> proc_macro::Toke
     closure.attrs = input
         .attrs
         .iter()
-        .filter(|attr| !attr.path().is_ident("doc") && !attr.path().is_ident("inline"))
+        .filter(|attr| !is_wrapper_attr(attr) && !attr.path().is_ident("inline"))
         .cloned()
         .collect();
     closure.attrs.insert(0, parse_quote! { #[inline(always)] });
 
+    // With #[iex(named_body)], the body goes to a nested function instead, so that backtraces show
+    // `f::__iex_body` rather than `f::{{closure}}`. A nested function can't use the generic
+    // parameters and arguments of `f`, so it redeclares the former and takes the latter by value.
+    let body_fn = named_body.then(|| {
+        let mut args = Vec::new();
+        for (i, arg) in wrapper_sig.inputs.iter_mut().enumerate() {
+            if let FnArg::Typed(pat_type) = arg {
+                let ident = format_ident!("__iex_arg{}", i, span = Span::mixed_site());
+                *pat_type.pat = parse_quote! { #ident };
+                args.push(ident);
+            }
+        }
+        let generic_args = input.sig.generics.params.iter().filter_map(|param| match param {
+            GenericParam::Type(param) => Some(&param.ident),
+            GenericParam::Const(param) => Some(&param.ident),
+            GenericParam::Lifetime(_) => None,
+        });
+        let unsafety = input.sig.unsafety;
+        let call = quote_spanned! { Span::mixed_site() => __iex_body::<#(#generic_args),*>(__iex_marker, #(#args),*) };
+        let mut attrs = std::mem::take(&mut closure.attrs);
+        attrs.push(parse_quote! { #[allow(clippy::needless_lifetimes, clippy::too_many_arguments)] });
+        closure = parse_quote_spanned! {
+            Span::mixed_site() => move |__iex_marker: ::iex::imp::Marker<#error_type>| {
+                let #no_copy = #no_copy; // Force FnOnce inference
+                #unsafety { #call }
+            }
+        };
+        closure.attrs.push(parse_quote! { #[inline(always)] });
+        let mut sig = Signature {
+            ident: parse_quote! { __iex_body },
+            ..input.sig.clone()
+        };
+        sig.inputs.insert(
+            0,
+            parse_quote_spanned! { Span::mixed_site() => __iex_marker: ::iex::imp::Marker<#error_type> },
+        );
+        ItemFn {
+            attrs,
+            vis: Visibility::Inherited,
+            sig,
+            block: closure_block.clone(),
+        }
+    });
+
     let name = input.sig.ident.clone();
 
     // Doc comments must stay in the wrapper even without #[cfg(doc)] because rustc applies the
@@ -315,7 +1052,7 @@ fn transform_item_fn(captures: Vec<Lifetime>, input: ItemFn) -> proc_macro::Toke
     let mut wrapper_attrs: Vec<_> = input
         .attrs
         .iter()
-        .filter(|attr| attr.path().is_ident("doc"))
+        .filter(|attr| is_wrapper_attr(attr))
         .cloned()
         .collect();
     wrapper_attrs.extend([
@@ -328,6 +1065,9 @@ fn transform_item_fn(captures: Vec<Lifetime>, input: ItemFn) -> proc_macro::Toke
         // FIXME: removal blocked on
         // https://github.com/danielhenrymantilla/fix_hidden_lifetime_bug.rs/issues/14
         parse_quote! { #[allow(clippy::needless_lifetimes)] },
+        // The return type is spanned at the user's signature, so clippy no longer treats it as
+        // macro-generated
+        parse_quote! { #[allow(clippy::type_complexity)] },
         parse_quote! { #[inline(always)] },
     ]);
 
@@ -335,29 +1075,88 @@ fn transform_item_fn(captures: Vec<Lifetime>, input: ItemFn) -> proc_macro::Toke
         .attrs
         .iter()
         .find(|attr| attr.path().is_ident("inline"));
-    let wrapper_fn = ItemFn {
-        attrs: wrapper_attrs,
-        vis: input.vis.clone(),
-        sig: wrapper_sig,
-        block: parse_quote_spanned! {
-            // This span is required for dead code diagnostic
+    // Attributes meant for the body would be lost, so don't delegate if there are any
+    let delegation = if closure.attrs.len() == 1 && inline_attr.is_none() && !named_body {
+        forwarded_call(&input.sig, &original_block)
+    } else {
+        None
+    };
+    let outcome = quote_spanned! {
+        tail_span =>
+        ::iex::imp::IexResult(
+            #inline_attr move |__iex_marker| {
+                ::iex::imp::_IexReturn::<#error_type>::_iex_return(#closure_ident(__iex_marker), __iex_marker)
+            },
+            ::core::marker::PhantomData,
+        )
+    };
+    // This span is required for dead code diagnostic
+    let wrapper_block = match delegation {
+        Some(Forward::Bare(call)) => {
+            let delegate = quote_spanned! {
+                tail_span =>
+                ::iex::imp::_IexReturn::<#error_type>::_iex_delegate(#call)
+            };
+            parse_quote_spanned! { input_span => { #delegate } }
+        }
+        Some(Forward::Try(call, question_span)) => {
+            let inner: Ident = parse_quote_spanned! { Span::mixed_site() => __iex_inner };
+            let forward = quote_spanned! {
+                question_span =>
+                (__iex_marker, ::core::mem::ManuallyDrop::new(#inner))._iex_forward()
+            };
+            parse_quote_spanned! {
+                input_span =>
+                {
+                    #[allow(unused_imports)]
+                    use ::iex::imp::_IexForward;
+                    let #inner = #call;
+                    ::iex::imp::IexResult(
+                        move |__iex_marker: ::iex::imp::Marker<#error_type>| #forward,
+                        ::core::marker::PhantomData,
+                    )
+                }
+            }
+        }
+        None => parse_quote_spanned! {
             input_span =>
             {
                 #[allow(unused_imports)]
                 use ::iex::imp::_IexForward;
+                #body_fn
                 let #no_copy = ::iex::imp::NoCopy; // Force FnOnce inference
                 // We need { .. } to support the #[inline] attribute on the closure
                 #[allow(unused_mut)]
-                let mut #name = { #closure };
-                ::iex::imp::IexResult(
-                    #inline_attr move |marker| {
-                        ::iex::Outcome::get_value_or_panic(#name(marker), marker)
-                    },
-                    ::core::marker::PhantomData,
-                )
+                let mut #closure_ident = { #closure };
+                #outcome
             }
         },
     };
+    let wrapper_fn = ItemFn {
+        attrs: wrapper_attrs,
+        vis: input.vis.clone(),
+        sig: wrapper_sig,
+        block: wrapper_block,
+    };
+
+    // The shim duplicates the wrapper rather than calling it, because there's no way to tell
+    // whether the function is associated, i.e. whether it's called as `name` or `Self::name`
+    let shim_fn = fn_ptr.then(|| {
+        let wrapper_block = &wrapper_fn.block;
+        ItemFn {
+            attrs: fn_ptr_shim_attrs(&name, &input.attrs),
+            vis: input.vis.clone(),
+            sig: Signature {
+                ident: fn_ptr_shim_ident(&name),
+                inputs: wrapper_fn.sig.inputs.clone(),
+                output: result_output,
+                ..input.sig.clone()
+            },
+            block: parse_quote! {{
+                ::iex::Outcome::into_result(#wrapper_block)
+            }},
+        }
+    });
 
     let doc = format!(
         "
@@ -386,17 +1185,50 @@ fn transform_item_fn(captures: Vec<Lifetime>, input: ItemFn) -> proc_macro::Toke
     quote! {
         #wrapper_fn
         #doc_fn
+        #twin_fn
+        #shim_fn
     }
     .into()
 }
 
-fn transform_closure(captures: Vec<Lifetime>, input: ExprClosure) -> proc_macro::TokenStream {
+fn transform_closure(
+    captures: Vec<Lifetime>,
+    annotated_types: Option<(Type, Type)>,
+    also_result: bool,
+    fn_ptr: bool,
+    named_body: bool,
+    input: ExprClosure,
+) -> proc_macro::TokenStream {
     if !captures.is_empty() {
         return quote! {
             compile_error!("#[iex(captures = ..)] is useless on closures")
         }
         .into();
     }
+    if annotated_types.is_some() {
+        return quote! {
+            compile_error!("#[iex(output = .., error = ..)] is useless on closures")
+        }
+        .into();
+    }
+    if also_result {
+        return quote! {
+            compile_error!("#[iex(also_result)] is not supported on closures")
+        }
+        .into();
+    }
+    if fn_ptr {
+        return quote! {
+            compile_error!("#[iex(fn_ptr)] is not supported on closures")
+        }
+        .into();
+    }
+    if named_body {
+        return quote! {
+            compile_error!("#[iex(named_body)] is not supported on closures")
+        }
+        .into();
+    }
 
     if let Some(constness) = input.constness {
         return quote_spanned! {
@@ -430,7 +1262,11 @@ fn transform_closure(captures: Vec<Lifetime>, input: ExprClosure) -> proc_macro:
     let mut replace_try = ReplaceTry {
         errors: darling::Error::accumulator(),
     };
-    replace_try.visit_expr_mut(&mut closure_body);
+    match &mut *closure_body {
+        Expr::Block(block) => replace_try.visit_body_mut(&mut block.block.stmts),
+        Expr::Macro(ExprMacro { mac, .. }) if is_bail_macro(mac) => {}
+        expr => replace_try.visit_expr_mut(expr),
+    }
     if let Err(err) = replace_try.errors.finish() {
         return err.write_errors().into();
     }
@@ -440,11 +1276,11 @@ fn transform_closure(captures: Vec<Lifetime>, input: ExprClosure) -> proc_macro:
         expr => vec![Stmt::Expr(expr, None)],
     };
 
-    let no_copy: Ident = parse_quote_spanned! { Span::mixed_site() => no_copy };
-    let closure_ident: Ident = parse_quote_spanned! { Span::mixed_site() => closure };
+    let no_copy: Ident = parse_quote_spanned! { Span::mixed_site() => __iex_no_copy };
+    let closure_ident: Ident = parse_quote_spanned! { Span::mixed_site() => __iex_closure };
 
     let mut internal_closure: ExprClosure = parse_quote_spanned! {
-        Span::mixed_site() => move |marker: ::iex::imp::Marker<#error_type>| {
+        Span::mixed_site() => move |__iex_marker: ::iex::imp::Marker<#error_type>| {
             let #no_copy = #no_copy; // Force FnOnce inference
             #(#closure_body)*
         }
@@ -478,8 +1314,8 @@ fn transform_closure(captures: Vec<Lifetime>, input: ExprClosure) -> proc_macro:
                 #[allow(unused_mut)]
                 let mut #closure_ident = { #internal_closure };
                 ::iex::imp::IexResult::<#output_type, #error_type, _>(
-                    #inline_attr move |marker| {
-                        ::iex::Outcome::get_value_or_panic(#closure_ident(marker), marker)
+                    #inline_attr move |__iex_marker| {
+                        ::iex::Outcome::get_value_or_panic(#closure_ident(__iex_marker), __iex_marker)
                     },
                     ::core::marker::PhantomData,
                 )
@@ -513,13 +1349,99 @@ pub fn iex(
         }
     }
 
+    let annotated_types = match (args.output, args.error) {
+        (Some(output), Some(error)) => match (parse_str(&output), parse_str(&error)) {
+            (Ok(output), Ok(error)) => Some((output, error)),
+            (Err(e), _) | (_, Err(e)) => return e.into_compile_error().into(),
+        },
+        (None, None) => None,
+        _ => {
+            return quote! {
+                compile_error!("#[iex(output = ..)] and #[iex(error = ..)] must be used together");
+            }
+            .into();
+        }
+    };
+
     if let Ok(input) = parse(input.clone()) {
-        transform_item_fn(captures, input)
+        transform_item_fn(
+            captures,
+            annotated_types,
+            args.also_result,
+            args.fn_ptr,
+            args.named_body,
+            input,
+        )
     } else if let Ok(input) = parse(input.clone()) {
-        transform_closure(captures, input)
+        transform_closure(
+            captures,
+            annotated_types,
+            args.also_result,
+            args.fn_ptr,
+            args.named_body,
+            input,
+        )
     } else {
-        transform_trait_item_fn(captures, parse_macro_input!(input as TraitItemFn))
+        transform_trait_item_fn(
+            captures,
+            annotated_types,
+            args.also_result,
+            args.fn_ptr,
+            args.named_body,
+            parse_macro_input!(input as TraitItemFn),
+        )
+    }
+}
+
+#[proc_macro_attribute]
+pub fn main(
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    if !args.is_empty() {
+        return quote! {
+            compile_error!("#[iex::main] does not take arguments");
+        }
+        .into();
+    }
+    let input = parse_macro_input!(input as ItemFn);
+    if !input.sig.inputs.is_empty() {
+        return quote_spanned! {
+            input.sig.inputs.span() =>
+            compile_error!("#[iex::main] can only be applied to functions without arguments");
+        }
+        .into();
     }
+    if let ReturnType::Default = input.sig.output {
+        return quote_spanned! {
+            input.sig.span() =>
+            compile_error!("#[iex::main] requires the function to return a `Result`");
+        }
+        .into();
+    }
+
+    // The body is moved to a nested #[iex] function, and the outcome is cast back to the declared
+    // `Result`, which `main` reports via `Termination`
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = input;
+    let inner_ident = Ident::new("__iex_main", Span::mixed_site());
+    let inner_sig = Signature {
+        ident: inner_ident.clone(),
+        ..sig.clone()
+    };
+    quote! {
+        #(#attrs)*
+        #vis #sig {
+            #[::iex::iex]
+            #inner_sig #block
+            ::iex::Outcome::into_result(#inner_ident())
+        }
+    }
+    .into()
 }
 
 #[proc_macro]
@@ -529,9 +1451,7 @@ pub fn try_block(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let mut replace_try = ReplaceTry {
         errors: darling::Error::accumulator(),
     };
-    for stmt in &mut body {
-        replace_try.visit_stmt_mut(stmt);
-    }
+    replace_try.visit_body_mut(&mut body);
     if let Err(err) = replace_try.errors.finish() {
         return err.write_errors().into();
     }
@@ -540,12 +1460,12 @@ pub fn try_block(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
         Span::mixed_site() => {
             #[allow(unused_imports)]
             use ::iex::imp::_IexForward;
-            let no_copy = ::iex::imp::NoCopy; // Force FnOnce inference
+            let __iex_no_copy = ::iex::imp::NoCopy; // Force FnOnce inference
             ::iex::imp::IexResult(
                 {
                     #[inline(always)]
-                    move |marker: ::iex::imp::Marker<_>| {
-                        let no_copy = no_copy; // Force FnOnce inference
+                    move |__iex_marker: ::iex::imp::Marker<_>| {
+                        let __iex_no_copy = __iex_no_copy; // Force FnOnce inference
                         #(#body)*
                     }
                 },
@@ -555,3 +1475,62 @@ pub fn try_block(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     }
     .into()
 }
+
+#[proc_macro]
+pub fn ctx(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    // Inside #[iex] functions and try_block!, ctx!(outcome, context) is rewritten to
+    // ctx!(@__iex_marker, outcome, context) before it is expanded
+    let parser = |input: ParseStream| {
+        let at: Token![@] = input.parse()?;
+        let marker: Ident = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let outcome: Expr = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let context: Expr = input.parse()?;
+        Ok((at, marker, outcome, context))
+    };
+    let Ok((at, marker, outcome, context)) = parser.parse(input) else {
+        return quote! {
+            compile_error!("ctx! can only be used inside #[iex] functions and try_block!")
+        }
+        .into();
+    };
+
+    quote_spanned! {
+        at.span =>
+        {
+            #[allow(unused_imports)]
+            use ::iex::imp::_IexForward;
+            (#marker, ::core::mem::ManuallyDrop::new(
+                ::iex::Context::context(#outcome, #context)
+            ))._iex_forward()
+        }
+    }
+    .into()
+}
+
+#[proc_macro]
+pub fn as_result(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let mut call = parse_macro_input!(input as Expr);
+    let name = match &mut call {
+        Expr::Call(call) => match &mut *call.func {
+            Expr::Path(path) => path
+                .path
+                .segments
+                .last_mut()
+                .map(|segment| &mut segment.ident),
+            _ => None,
+        },
+        Expr::MethodCall(call) => Some(&mut call.method),
+        _ => None,
+    };
+    let Some(name) = name else {
+        return quote_spanned! {
+            call.span() => compile_error!("as_result! expects a function or method call")
+        }
+        .into();
+    };
+    // Keep the span so that a missing twin is reported at the name of the callee
+    *name = Ident::new(&result_twin_ident(name).to_string(), name.span());
+    quote! { #call }.into()
+}
@@ -56,12 +56,14 @@ pub trait Context<T, E> {
 }
 
 impl<T, E> Context<T, E> for Result<T, E> {
-    type ContextOutcome<C> = Result<T>
+    type ContextOutcome<C>
+        = Result<T>
     where
         Result<(), E>: anyhow::Context<(), E>,
         C: Display + Send + Sync + 'static;
 
-    type WithContextOutcome<C, F> = Result<T>
+    type WithContextOutcome<C, F>
+        = Result<T>
     where
         Result<(), E>: anyhow::Context<(), E>,
         C: Display + Send + Sync + 'static,
@@ -86,12 +88,52 @@ impl<T, E> Context<T, E> for Result<T, E> {
 }
 
 impl<T, E, Func: CallWithMarker<T, E>> Context<T, E> for IexResult<T, E, Func> {
-    type ContextOutcome<C> = IexResult<T, Error, GenericContext<Self, C>>
+    type ContextOutcome<C>
+        = IexResult<T, Error, GenericContext<Self, C>>
     where
         Result<(), E>: anyhow::Context<(), E>,
         C: Display + Send + Sync + 'static;
 
-    type WithContextOutcome<C, F> = IexResult<T, Error, GenericWithContext<Self, C, F>>
+    type WithContextOutcome<C, F>
+        = IexResult<T, Error, GenericWithContext<Self, C, F>>
+    where
+        Result<(), E>: anyhow::Context<(), E>,
+        C: Display + Send + Sync + 'static,
+        F: FnOnce() -> C;
+
+    fn context<C>(self, context: C) -> Self::ContextOutcome<C>
+    where
+        Result<(), E>: anyhow::Context<(), E>,
+        C: Display + Send + Sync + 'static,
+    {
+        IexResult(
+            GenericContext {
+                outcome: self,
+                context,
+            },
+            PhantomData,
+        )
+    }
+
+    fn with_context<C, F>(self, f: F) -> Self::WithContextOutcome<C, F>
+    where
+        Result<(), E>: anyhow::Context<(), E>,
+        C: Display + Send + Sync + 'static,
+        F: FnOnce() -> C,
+    {
+        IexResult(GenericWithContext { outcome: self, f }, PhantomData)
+    }
+}
+
+impl<T, E, Func: FnOnce() -> Result<T, E>> Context<T, E> for crate::IexResult<T, E, Func> {
+    type ContextOutcome<C>
+        = IexResult<T, Error, GenericContext<Self, C>>
+    where
+        Result<(), E>: anyhow::Context<(), E>,
+        C: Display + Send + Sync + 'static;
+
+    type WithContextOutcome<C, F>
+        = IexResult<T, Error, GenericWithContext<Self, C, F>>
     where
         Result<(), E>: anyhow::Context<(), E>,
         C: Display + Send + Sync + 'static,
@@ -157,11 +199,13 @@ where
 }
 
 impl<T> Context<T, Infallible> for Option<T> {
-    type ContextOutcome<C> = Result<T>
+    type ContextOutcome<C>
+        = Result<T>
     where
         C: Display + Send + Sync + 'static;
 
-    type WithContextOutcome<C, F> = Result<T>
+    type WithContextOutcome<C, F>
+        = Result<T>
     where
         C: Display + Send + Sync + 'static,
         F: FnOnce() -> C;
@@ -0,0 +1,78 @@
+use crate::{iex, Outcome};
+use std::cell::{Cell, OnceCell};
+
+/// A fallible computation that is evaluated at most once.
+///
+/// Outcomes are single-use, so an expensive computation would normally have to be repeated to
+/// propagate its result again. `CachedOutcome` evaluates the thunk on the first call to
+/// [`get`](Self::get), stores the [`Result`], and re-throws a clone of the error (or returns a
+/// clone of the value) on each call.
+///
+/// # Thread safety
+///
+/// `CachedOutcome` is [`Send`] if its contents are, but it is never [`Sync`]: it's meant to be
+/// owned by one thread, like the errors that `#[iex]` propagates. To share a cached result between
+/// threads, cache the [`Result`] in a [`std::sync::OnceLock`] instead.
+///
+/// The thunk must not call `get` on the same `CachedOutcome`; this panics.
+///
+/// # Example
+///
+/// ```
+/// use iex::{iex, CachedOutcome, Outcome};
+///
+/// #[iex]
+/// fn load_config(calls: &mut u32) -> Result<String, String> {
+///     *calls += 1;
+///     Err("File not found".to_string())
+/// }
+///
+/// let mut calls = 0;
+/// let config = CachedOutcome::new(|| load_config(&mut calls));
+/// assert_eq!(config.get().into_result(), Err("File not found".to_string()));
+/// assert_eq!(config.get().into_result(), Err("File not found".to_string()));
+/// drop(config);
+/// assert_eq!(calls, 1);
+/// ```
+pub struct CachedOutcome<T, E, F> {
+    result: OnceCell<Result<T, E>>,
+    thunk: Cell<Option<F>>,
+}
+
+impl<T, E, O, F> CachedOutcome<T, E, F>
+where
+    O: Outcome<Output = T, Error = E>,
+    F: FnOnce() -> O,
+{
+    /// Wrap a thunk producing an outcome. The thunk is not invoked until the first call to
+    /// [`get`](Self::get).
+    pub fn new(thunk: F) -> Self {
+        Self {
+            result: OnceCell::new(),
+            thunk: Cell::new(Some(thunk)),
+        }
+    }
+
+    /// Evaluate the computation if it hasn't been evaluated yet and propagate its result.
+    #[iex]
+    pub fn get(&self) -> Result<T, E>
+    where
+        T: Clone,
+        E: Clone,
+    {
+        self.result
+            .get_or_init(|| {
+                let thunk = self
+                    .thunk
+                    .take()
+                    .expect("CachedOutcome::get called from its own thunk");
+                thunk().into_result()
+            })
+            .clone()
+    }
+
+    /// Returns the cached result, if the computation has been evaluated.
+    pub fn peek(&self) -> Option<&Result<T, E>> {
+        self.result.get()
+    }
+}
@@ -0,0 +1,95 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// A flag for cancelling long-running computations cooperatively.
+///
+/// Clones of a token share the flag, so a token can be cloned into another thread and cancelled
+/// from there. The computation itself decides where it can be interrupted by calling
+/// [`check`](Self::check) at checkpoints, e.g. once per loop iteration, and propagating the
+/// [`Cancelled`] error with `?`. Nothing is interrupted preemptively: a computation that doesn't
+/// check the token runs to completion.
+///
+/// [`Outcome::with_cancellation`](crate::Outcome::with_cancellation) adds checkpoints around an
+/// outcome that doesn't know about the token.
+///
+/// # Example
+///
+/// ```
+/// use iex::{iex, CancellationToken, Cancelled, Outcome};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum EvalError {
+///     Overflow,
+///     Cancelled,
+/// }
+///
+/// impl From<Cancelled> for EvalError {
+///     fn from(_: Cancelled) -> Self {
+///         Self::Cancelled
+///     }
+/// }
+///
+/// #[iex]
+/// fn sum_up_to(n: u64, token: &CancellationToken) -> Result<u64, EvalError> {
+///     let mut sum = 0u64;
+///     for i in 0..n {
+///         token.check()?;
+///         sum = sum.checked_add(i).ok_or(EvalError::Overflow)?;
+///     }
+///     Ok(sum)
+/// }
+///
+/// let token = CancellationToken::new();
+/// assert_eq!(sum_up_to(5, &token).into_result(), Ok(10));
+/// token.cancel();
+/// assert_eq!(sum_up_to(5, &token).into_result(), Err(EvalError::Cancelled));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Create a token that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel the token and all its clones.
+    ///
+    /// Cancellation is permanent: there is no way to reset the flag.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if the token was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// A checkpoint: returns `Err(Cancelled)` if the token was cancelled.
+    ///
+    /// Propagate the error with `?` in an [`#[iex]`](macro@crate::iex) function to unwind the
+    /// computation. The error is converted with [`From`] like any other.
+    pub fn check(&self) -> Result<(), Cancelled> {
+        if self.is_cancelled() {
+            Err(Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// The error returned by [`CancellationToken::check`] when the token was cancelled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Cancelled;
+
+impl Display for Cancelled {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "operation cancelled")
+    }
+}
+
+impl Error for Cancelled {}
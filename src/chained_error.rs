@@ -0,0 +1,35 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+/// An error with another error attached as its [source](Error::source).
+///
+/// This is produced by [`Outcome::chain_err`](crate::Outcome::chain_err).
+///
+/// The alternate format (`{:#}`) displays the whole chain of sources, separated by colons.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ChainedError<E, S> {
+    /// The new error.
+    pub error: E,
+    /// The original error.
+    pub source: S,
+}
+
+impl<E: Display, S: Error + 'static> Display for ChainedError<E, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error)?;
+        if f.alternate() {
+            let mut source: Option<&dyn Error> = Some(&self.source);
+            while let Some(error) = source {
+                write!(f, ": {error}")?;
+                source = error.source();
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<E: Display + fmt::Debug, S: Error + 'static> Error for ChainedError<E, S> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
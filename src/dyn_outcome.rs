@@ -0,0 +1,63 @@
+use crate::{imp::Marker, outcome::Sealed, Outcome};
+
+/// An object-safe subset of [`Outcome`].
+///
+/// [`Outcome`] itself is not object-safe, because its methods take `self` by value and return
+/// opaque types. `DynOutcome` is implemented for all outcomes and can be used as
+/// `Box<dyn DynOutcome<Output = T, Error = E>>`, e.g. to store outcomes of different functions in
+/// one collection and resolve them later.
+///
+/// Like [`Outcome`], this trait is sealed.
+///
+/// # Example
+///
+/// ```
+/// use iex::{iex, DynOutcome};
+///
+/// #[iex]
+/// fn parse(s: &str) -> Result<i32, String> {
+///     s.parse().map_err(|_| format!("Invalid number: {s}"))
+/// }
+///
+/// #[iex]
+/// fn negate(x: i32) -> Result<i32, String> {
+///     x.checked_neg().ok_or_else(|| format!("Cannot negate {x}"))
+/// }
+///
+/// let pending: Vec<Box<dyn DynOutcome<Output = i32, Error = String>>> = vec![
+///     Box::new(parse("1")),
+///     Box::new(negate(2)),
+///     Box::new(Err("Plain error".to_string())),
+/// ];
+/// let results: Vec<_> = pending.into_iter().map(|outcome| outcome.into_result_boxed()).collect();
+/// assert_eq!(results, [Ok(1), Ok(-2), Err("Plain error".to_string())]);
+/// ```
+pub trait DynOutcome: Sealed {
+    /// The type of the success value.
+    type Output;
+
+    /// The type of the error value.
+    type Error;
+
+    // Same contract as `Outcome::get_value_or_panic`.
+    #[doc(hidden)]
+    fn get_value_or_panic_boxed(self: Box<Self>, marker: Marker<Self::Error>) -> Self::Output;
+
+    /// Cast a boxed generic result to a [`Result`].
+    ///
+    /// This is the object-safe counterpart of [`Outcome::into_result`].
+    fn into_result_boxed(self: Box<Self>) -> Result<Self::Output, Self::Error>;
+}
+
+impl<R: Outcome> DynOutcome for R {
+    type Output = R::Output;
+    type Error = R::Error;
+
+    fn get_value_or_panic_boxed(self: Box<Self>, marker: Marker<Self::Error>) -> Self::Output {
+        (*self).get_value_or_panic(marker)
+    }
+
+    fn into_result_boxed(self: Box<Self>) -> Result<Self::Output, Self::Error> {
+        (*self).into_result()
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{
+    imp::{ExceptionMapper, IexResult, Marker},
+    Outcome,
+};
+use std::marker::PhantomData;
+
+/// A building block for custom [`map_err`](Outcome::map_err)-like adapters.
+///
+/// `ErrorMapper::new(state, f)` owns some state and an error handler. [`run`](Self::run) calls a
+/// function with mutable access to the state and drives the outcome it returns. If the outcome
+/// fails, its error and the state are passed to `f`, and whatever `f` returns is propagated
+/// instead. If it succeeds, `f` is not called and the state is dropped.
+///
+/// This is the mechanism behind `#[iex(shares = ..)]`: the state is available both on the happy
+/// path and to the error handler, without cloning it or wrapping it in a [`RefCell`]. If the
+/// handler doesn't need the state, [`map_err`](Outcome::map_err) is simpler.
+///
+/// Like the closure passed to `map_err`, `f` may call `#[iex]` functions. The original error is
+/// removed from the exception slot before `f` is called.
+///
+/// [`RefCell`]: std::cell::RefCell
+///
+/// # Example
+///
+/// A combinator that tags errors with the ID of the request that caused them, while also passing
+/// the ID to the handler of the request:
+///
+/// ```
+/// use iex::{iex, ErrorMapper, Outcome};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Tagged<E> {
+///     request_id: u64,
+///     error: E,
+/// }
+///
+/// fn with_request_id<O, F>(
+///     request_id: u64,
+///     handler: F,
+/// ) -> impl Outcome<Output = O::Output, Error = Tagged<O::Error>>
+/// where
+///     O: Outcome,
+///     F: FnOnce(u64) -> O,
+/// {
+///     ErrorMapper::new(request_id, |request_id, error| Tagged { request_id, error })
+///         .run(|request_id| handler(*request_id))
+/// }
+///
+/// #[iex]
+/// fn handle(request_id: u64, path: &str) -> Result<String, &'static str> {
+///     match path {
+///         "/" => Ok(format!("index for request {request_id}")),
+///         _ => Err("Not found"),
+///     }
+/// }
+///
+/// #[iex]
+/// fn serve(request_id: u64, path: &str) -> Result<String, Tagged<&'static str>> {
+///     with_request_id(request_id, |request_id| handle(request_id, path))
+/// }
+///
+/// assert_eq!(serve(1, "/").into_result(), Ok("index for request 1".to_string()));
+/// assert_eq!(serve(2, "/x").into_result(), Err(Tagged { request_id: 2, error: "Not found" }));
+/// ```
+#[must_use = "ErrorMapper does nothing unless `run` is called"]
+pub struct ErrorMapper<S, F> {
+    state: S,
+    f: F,
+}
+
+impl<S, F> ErrorMapper<S, F> {
+    /// Create a mapper from the state and the error handler.
+    pub fn new(state: S, f: F) -> Self {
+        Self { state, f }
+    }
+
+    /// Drive the outcome returned by `body`, mapping its error with the handler.
+    ///
+    /// `body` is called lazily, when the returned outcome is driven. The outcome `body` returns
+    /// can't borrow from the state; copy what it needs out of the reference.
+    pub fn run<O, E, B>(self, body: B) -> impl Outcome<Output = O::Output, Error = E>
+    where
+        O: Outcome,
+        B: FnOnce(&mut S) -> O,
+        F: FnOnce(S, O::Error) -> E,
+    {
+        IexResult(
+            move |marker: Marker<E>| {
+                let mut exception_mapper = ExceptionMapper::new(marker, self.state, self.f);
+                let in_marker = exception_mapper.get_in_marker();
+                let outcome = body(exception_mapper.get_state());
+                let output = outcome.get_value_or_panic(in_marker);
+                exception_mapper.swallow();
+                output
+            },
+            PhantomData,
+        )
+    }
+}
@@ -1,6 +1,7 @@
 //! Examples of rendered documentation for [`#[iex]`](macro@iex) functions.
 
-use crate::iex;
+use crate::{apply, iex, Outcome};
+use std::fmt::Display;
 
 /// A simple struct containing an [`#[iex]`](macro@iex) method.
 pub struct HasIexMethod;
@@ -49,3 +50,26 @@ impl SayHello for String {
 pub fn add(a: i32, b: i32) -> Result<i32, i32> {
     a.checked_add(b).ok_or(a.wrapping_add(b))
 }
+
+/// Add the outputs of two outcomes and check for overflow.
+///
+/// This function is generic over outcomes, so it accepts both `#[iex] Result` and [`Result`]. The
+/// errors of the arguments are propagated as-is.
+#[iex]
+pub fn add_outcomes<O: Outcome<Output = i32>>(a: O, b: O) -> Result<i32, O::Error>
+where
+    O::Error: From<i32>,
+{
+    Ok(add(a?, b?)?)
+}
+
+/// Format the output of an outcome without resolving it.
+///
+/// The returned outcome fails if and only if the argument fails.
+pub fn describe<O>(outcome: O) -> impl Outcome<Output = String, Error = O::Error>
+where
+    O: Outcome,
+    O::Output: Display,
+{
+    apply(outcome, |value| format!("The answer is {value}"))
+}
@@ -1,7 +1,31 @@
 use std::mem::{align_of, size_of, MaybeUninit};
+use std::ops::Deref;
+
+// With `always_box_error`, every error is boxed, so the slot only needs to fit a pointer.
+#[cfg(not(feature = "always_box_error"))]
+type Slot = [usize; 8];
+#[cfg(feature = "always_box_error")]
+type Slot = [usize; 1];
 
 pub(crate) struct Exception {
-    data: MaybeUninit<[usize; 8]>,
+    data: MaybeUninit<Slot>,
+}
+
+// An error owned outside of the slot, either moved out of it or still in its box.
+pub(crate) enum Stored<T> {
+    Inline(T),
+    Boxed(Box<T>),
+}
+
+impl<T> Deref for Stored<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self {
+            Self::Inline(value) => value,
+            Self::Boxed(value) => value,
+        }
+    }
 }
 
 #[repr(C)]
@@ -18,7 +42,7 @@ impl Exception {
     }
 
     const fn is_small<T>() -> bool {
-        size_of::<Just<T>>() <= size_of::<Exception>()
+        !cfg!(feature = "always_box_error") && size_of::<Just<T>>() <= size_of::<Exception>()
     }
 
     unsafe fn write_raw<T>(&mut self, value: T) {
@@ -47,6 +71,19 @@ impl Exception {
         unsafe { self.write_raw(0usize) }
     }
 
+    // Both `Just<T>` and `Option<Box<T>>` store zero in the first word iff there is no error.
+    pub(crate) fn is_clear(&self) -> bool {
+        unsafe { self.read_raw::<usize>() == 0 }
+    }
+
+    // Moves the stored error, if any, out of the slot, leaving it empty. The returned value can be
+    // assigned back to restore the error.
+    pub(crate) fn take(&mut self) -> Self {
+        let exception = Self { data: self.data };
+        self.clear();
+        exception
+    }
+
     unsafe fn read_raw<T>(&self) -> T {
         let ptr = self.data.as_ptr().cast::<T>();
         if align_of::<T>() <= align_of::<usize>() {
@@ -69,6 +106,15 @@ impl Exception {
         }
     }
 
+    // Like `read`, but leaves boxed errors in their box, so that large errors aren't moved.
+    pub(crate) unsafe fn read_stored<T>(&self) -> Option<Stored<T>> {
+        if Self::is_small::<T>() {
+            self.read::<T>().map(Stored::Inline)
+        } else {
+            self.read_raw::<Option<Box<T>>>().map(Stored::Boxed)
+        }
+    }
+
     pub(crate) unsafe fn read_unchecked<T>(&self) -> T {
         if Self::is_small::<T>() {
             self.read_raw::<Just<T>>().value.assume_init()
@@ -82,6 +128,20 @@ impl Exception {
 mod test {
     use super::*;
 
+    #[test]
+    fn slot_size() {
+        let pointers = if cfg!(feature = "always_box_error") {
+            1
+        } else {
+            8
+        };
+        assert_eq!(size_of::<Exception>(), pointers * size_of::<usize>());
+        assert_eq!(
+            Exception::is_small::<u8>(),
+            !cfg!(feature = "always_box_error")
+        );
+    }
+
     #[test]
     fn overaligned() {
         let mut exc = Exception::new();
@@ -1,6 +1,24 @@
 use crate::{imp::Marker, EXCEPTION};
+use std::any::Any;
+use std::cell::Cell;
 use std::marker::PhantomData;
 use std::mem::ManuallyDrop;
+use std::panic::AssertUnwindSafe;
+
+thread_local! {
+    // A panic raised by the handler of an `ExceptionMapper` while an error was propagating. It
+    // can't unwind out of the destructor without aborting the process, so it's stashed here and
+    // re-raised when the error is caught.
+    static DEFERRED_PANIC: Cell<Option<Box<dyn Any + Send>>> = const { Cell::new(None) };
+}
+
+pub(crate) fn take_deferred_panic() -> Option<Box<dyn Any + Send>> {
+    DEFERRED_PANIC.take()
+}
+
+pub(crate) fn restore_deferred_panic(payload: Option<Box<dyn Any + Send>>) {
+    DEFERRED_PANIC.set(payload);
+}
 
 pub struct ExceptionMapper<S, T, U, F: FnOnce(S, T) -> U> {
     state: ManuallyDrop<S>,
@@ -43,7 +61,63 @@ impl<S, T, U, F: FnOnce(S, T) -> U> Drop for ExceptionMapper<S, T, U, F> {
             if let Some(error) = (*exception).read::<T>() {
                 let state = ManuallyDrop::take(&mut self.state);
                 let f = ManuallyDrop::take(&mut self.f);
-                (*exception).write::<U>(f(state, error));
+                match std::panic::catch_unwind(AssertUnwindSafe(|| f(state, error))) {
+                    Ok(error) => (*exception).write::<U>(error),
+                    Err(payload) => {
+                        // The error was moved out and dropped by the unwinding of 'f'. Leave the
+                        // slot empty, so that mappers further up pass through, and let the catch
+                        // site turn the unwinding into the panic.
+                        (*exception).clear();
+                        DEFERRED_PANIC.set(Some(payload));
+                    }
+                }
+            }
+        })
+    }
+}
+
+// Like `ExceptionMapper`, but `f` borrows the error where it's stored instead of taking it by
+// value, and the error is only dropped once the new one is written.
+pub struct ExceptionRefMapper<T, U, F: FnOnce(&T) -> U> {
+    f: ManuallyDrop<F>,
+    phantom: PhantomData<fn(&T) -> U>,
+}
+
+impl<T, U, F: FnOnce(&T) -> U> ExceptionRefMapper<T, U, F> {
+    pub fn new(_marker: Marker<U>, f: F) -> Self {
+        Self {
+            f: ManuallyDrop::new(f),
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn get_in_marker(&self) -> Marker<T> {
+        unsafe { Marker::new() }
+    }
+
+    pub fn swallow(self) {
+        let mut exception_mapper = ManuallyDrop::new(self);
+        let _f = unsafe { ManuallyDrop::take(&mut exception_mapper.f) };
+    }
+}
+
+impl<T, U, F: FnOnce(&T) -> U> Drop for ExceptionRefMapper<T, U, F> {
+    fn drop(&mut self) {
+        EXCEPTION.with(|exception| unsafe {
+            let exception = exception.get();
+            if let Some(error) = (*exception).read_stored::<T>() {
+                let f = ManuallyDrop::take(&mut self.f);
+                // Both `f` and the destructor of the error may panic, see `ExceptionMapper`
+                let result = std::panic::catch_unwind(AssertUnwindSafe(|| f(&error))).and_then(
+                    |new_error| {
+                        (*exception).write::<U>(new_error);
+                        std::panic::catch_unwind(AssertUnwindSafe(|| drop(error)))
+                    },
+                );
+                if let Err(payload) = result {
+                    (*exception).clear();
+                    DEFERRED_PANIC.set(Some(payload));
+                }
             }
         })
     }
@@ -0,0 +1,132 @@
+//! Custom storage for the error in flight.
+//!
+//! Available with the `exception_store` feature only.
+//!
+//! An error thrown by an `#[iex]` function is stored in a slot until it is caught. By default,
+//! there is one slot per thread. This is wrong for runtimes where one thread interleaves several
+//! logical tasks on their own stacks, e.g. green threads: if a task is switched out while an error
+//! is propagating through it (say, in a destructor), another task on the same thread can overwrite
+//! the slot. Such runtimes can install an [`ExceptionStore`] that returns one slot per task.
+//!
+//! # Example
+//!
+//! ```
+//! use iex::exception_store::{set_exception_store, ExceptionSlot, ExceptionStore};
+//! use iex::{iex, Outcome};
+//! use std::cell::Cell;
+//! use std::ptr::NonNull;
+//!
+//! struct Task {
+//!     slot: ExceptionSlot,
+//! }
+//!
+//! thread_local! {
+//!     static CURRENT_TASK: Cell<Option<NonNull<Task>>> = const { Cell::new(None) };
+//! }
+//!
+//! struct TaskStore;
+//!
+//! // SAFETY: Each task has its own slot, and a task never migrates between threads while an error
+//! // is propagating. The slot of a task is never used once the task is dropped.
+//! unsafe impl ExceptionStore for TaskStore {
+//!     fn slot(&self) -> NonNull<ExceptionSlot> {
+//!         let task = CURRENT_TASK.get().expect("not running a task");
+//!         unsafe { NonNull::from(&(*task.as_ptr()).slot) }
+//!     }
+//! }
+//!
+//! #[iex]
+//! fn fails() -> Result<(), i32> {
+//!     Err(123)
+//! }
+//!
+//! unsafe {
+//!     set_exception_store(&TaskStore);
+//! }
+//!
+//! let task = Task { slot: ExceptionSlot::new() };
+//! CURRENT_TASK.set(Some(NonNull::from(&task)));
+//! assert_eq!(fails().into_result(), Err(123));
+//! CURRENT_TASK.set(None);
+//! ```
+
+use crate::exception::Exception;
+use std::cell::UnsafeCell;
+use std::ptr::NonNull;
+use std::sync::OnceLock;
+
+/// Storage for one error in flight.
+///
+/// The contents are opaque. An empty slot is created with [`new`](Self::new), and the slot is
+/// empty again whenever no error is propagating through the task that owns it.
+pub struct ExceptionSlot(UnsafeCell<Exception>);
+
+impl ExceptionSlot {
+    /// Create an empty slot.
+    pub const fn new() -> Self {
+        Self(UnsafeCell::new(Exception::new()))
+    }
+}
+
+impl Default for ExceptionSlot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A source of [`ExceptionSlot`]s.
+///
+/// # Safety
+///
+/// An error is written to the slot when it's thrown, and read from the slot when it's caught,
+/// possibly after being rewritten by [`map_err`](crate::Outcome::map_err) and similar methods
+/// along the way. All these accesses happen on the stack of one logical task, and they must all
+/// reach the same slot. Concretely, [`slot`](Self::slot) must:
+///
+/// - Return a pointer to a valid slot that is not accessed by anything else until the task
+///   finishes or stops propagating errors,
+/// - Return the same slot whenever it's called from the same logical task, and different slots
+///   for logical tasks that may run concurrently or interleave on one thread.
+///
+/// A slot may only be dropped or reused by another task while it's empty, i.e. when no error is
+/// propagating through the task that owned it.
+pub unsafe trait ExceptionStore: Sync {
+    /// Get the slot of the current logical task.
+    fn slot(&self) -> NonNull<ExceptionSlot>;
+}
+
+static STORE: OnceLock<&'static dyn ExceptionStore> = OnceLock::new();
+
+/// Install a custom exception store for the whole program.
+///
+/// Until this is called, each thread uses a thread-local slot.
+///
+/// # Panics
+///
+/// Panics if a store has already been installed.
+///
+/// # Safety
+///
+/// No error may be propagating on any thread when the store is installed, because such an error
+/// would be written to one slot and read from another.
+pub unsafe fn set_exception_store(store: &'static dyn ExceptionStore) {
+    if STORE.set(store).is_err() {
+        panic!("an exception store has already been installed");
+    }
+}
+
+thread_local! {
+    static THREAD_EXCEPTION: UnsafeCell<Exception> = const { UnsafeCell::new(Exception::new()) };
+}
+
+// Replaces the thread-local `EXCEPTION` when the feature is enabled, with the same interface.
+pub(crate) struct CurrentException;
+
+impl CurrentException {
+    pub(crate) fn with<R>(&'static self, f: impl FnOnce(&UnsafeCell<Exception>) -> R) -> R {
+        match STORE.get() {
+            Some(store) => f(unsafe { &store.slot().as_ref().0 }),
+            None => THREAD_EXCEPTION.with(f),
+        }
+    }
+}
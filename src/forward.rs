@@ -2,32 +2,105 @@ use crate::{
     imp::{ExceptionMapper, Marker},
     Outcome,
 };
+use std::cell::Cell;
 use std::mem::ManuallyDrop;
 
+#[cfg(feature = "location")]
+use crate::location::LocationGuard;
+
+// The conversion bound is checked as a bound on `_iex_forward` rather than on the impl, so that a
+// missing conversion is reported as an unsatisfied trait bound instead of an opaque "method exists
+// but its trait bounds were not satisfied" error.
+#[diagnostic::on_unimplemented(
+    message = "cannot forward: the callee's error type `{Self}` does not convert into this \
+               function's error type `{E}`",
+    label = "`?` cannot convert `{Self}` into `{E}`",
+    note = "`?` in an `#[iex]` function converts errors with `Into`, consider implementing \
+            `From<{Self}>` for `{E}`"
+)]
+pub trait _IexIntoError<E> {
+    fn _iex_into_error(self) -> E;
+}
+
+#[diagnostic::do_not_recommend]
+impl<T: Into<E>, E> _IexIntoError<E> for T {
+    fn _iex_into_error(self) -> E {
+        self.into()
+    }
+}
+
+// The tail of an #[iex] function is returned as is, so its error type has to match exactly. Without
+// this trait, a mismatch is reported as a type error inside `get_value_or_panic`. The impl is the
+// only one, so it still drives type inference of e.g. `Ok(x)` tails.
+#[diagnostic::on_unimplemented(
+    message = "the tail of an `#[iex]` function must be an outcome with error type `{E}`",
+    label = "expected an outcome with error type exactly `{E}`",
+    note = "returning an outcome directly doesn't convert its error, use `Ok(callee()?)` to \
+            convert it with `Into`"
+)]
+pub trait _IexReturn<E> {
+    type Output;
+    type Outcome: Outcome<Output = Self::Output, Error = E>;
+    fn _iex_return(self, marker: Marker<E>) -> Self::Output;
+    // Pure forwarders return the callee's outcome itself, with the same check
+    fn _iex_delegate(self) -> Self::Outcome;
+}
+
+#[diagnostic::do_not_recommend]
+impl<R: Outcome<Error = E>, E> _IexReturn<E> for R {
+    type Output = R::Output;
+    type Outcome = R;
+    #[inline(always)]
+    fn _iex_return(self, marker: Marker<E>) -> R::Output {
+        self.get_value_or_panic(marker)
+    }
+    #[inline(always)]
+    fn _iex_delegate(self) -> R {
+        self
+    }
+}
+
 pub trait _IexForward {
     type Output;
-    fn _iex_forward(self) -> Self::Output;
+    type SourceError;
+    type TargetError;
+    fn _iex_forward(self) -> Self::Output
+    where
+        Self::SourceError: _IexIntoError<Self::TargetError>;
 }
 
-impl<E, R: Outcome> _IexForward for &mut (Marker<E>, ManuallyDrop<R>)
-where
-    R::Error: Into<E>,
-{
+impl<E, R: Outcome> _IexForward for &mut (Marker<E>, ManuallyDrop<R>) {
     type Output = R::Output;
-    fn _iex_forward(self) -> R::Output {
+    type SourceError = R::Error;
+    type TargetError = E;
+    #[cfg_attr(feature = "location", track_caller)]
+    fn _iex_forward(self) -> R::Output
+    where
+        R::Error: _IexIntoError<E>,
+    {
+        #[cfg(feature = "location")]
+        let location_guard = LocationGuard::new();
         let outcome = unsafe { ManuallyDrop::take(&mut self.1) };
-        if typeid::of::<E>() == typeid::of::<R::Error>() {
-            // SAFETY: If we enter this conditional, E and R::Error differ only in lifetimes.
-            // Lifetimes are erased in runtime, so `impl Into<E> for R::Error` has the same
-            // implementation as `impl Into<T> for T` for some `T`, and that blanket
-            // implementation is a no-op. Therefore, no conversion needs to happen.
+        let output = if typeid::of::<E>() == typeid::of::<R::Error>() {
+            // SAFETY: If we enter this conditional, E and R::Error differ only in lifetimes:
+            // `typeid::of` replaces free lifetimes with 'static, but keeps higher-rank lifetimes
+            // in trait objects, so e.g. `dyn for<'a> Fn(&'a T)` and `dyn Fn(&'static T)` take
+            // the other branch. Coherence ignores lifetimes, so any `From`/`Into` impl between
+            // two such types other than the reflexive `impl From<T> for T` would overlap with it
+            // and be rejected. Lifetimes are erased in runtime, so the `R::Error: Into<E>` bound
+            // is satisfied by that blanket implementation, which is a no-op. Therefore, no
+            // conversion needs to happen.
             outcome.get_value_or_panic(unsafe { Marker::new() })
         } else {
-            let exception_mapper = ExceptionMapper::new(self.0, (), |_, err| Into::<E>::into(err));
+            let exception_mapper =
+                ExceptionMapper::new(self.0, (), |_, err: R::Error| err._iex_into_error());
             let output = outcome.get_value_or_panic(exception_mapper.get_in_marker());
             exception_mapper.swallow();
             output
-        }
+        };
+        #[cfg(feature = "location")]
+        location_guard.disarm();
+        output
     }
 }
 
@@ -36,7 +109,48 @@ where
 // actually matters for serde.
 impl<R: Outcome> _IexForward for (Marker<R::Error>, ManuallyDrop<R>) {
     type Output = R::Output;
+    type SourceError = R::Error;
+    type TargetError = R::Error;
+    #[cfg_attr(feature = "location", track_caller)]
     fn _iex_forward(self) -> R::Output {
-        ManuallyDrop::into_inner(self.1).get_value_or_panic(self.0)
+        #[cfg(feature = "location")]
+        let location_guard = LocationGuard::new();
+        let output = ManuallyDrop::into_inner(self.1).get_value_or_panic(self.0);
+        #[cfg(feature = "location")]
+        location_guard.disarm();
+        output
+    }
+}
+
+// `x.into_result()?` in #[iex] functions would catch the error only to throw it again, so it is
+// compiled as `_IexIntoResult(..)._iex_into_result(|x| x.into_result())?`. Autoref specialization
+// picks the first impl if `x` is an outcome, which is then propagated directly with the same
+// conversion. Otherwise, `x` is some other type with its own `into_result` method, which is called
+// as written.
+pub struct _IexIntoResult<T>(pub Cell<Option<T>>);
+
+pub trait _IexSkipIntoResult {
+    type Inner;
+    fn _iex_into_result<R>(self, f: impl FnOnce(Self::Inner) -> R) -> Self::Inner;
+}
+
+impl<O: Outcome> _IexSkipIntoResult for _IexIntoResult<O> {
+    type Inner = O;
+    #[inline(always)]
+    fn _iex_into_result<R>(self, _f: impl FnOnce(O) -> R) -> O {
+        self.0.into_inner().unwrap()
+    }
+}
+
+pub trait _IexCallIntoResult {
+    type Inner;
+    fn _iex_into_result<R>(self, f: impl FnOnce(Self::Inner) -> R) -> R;
+}
+
+impl<T> _IexCallIntoResult for &_IexIntoResult<T> {
+    type Inner = T;
+    #[inline(always)]
+    fn _iex_into_result<R>(self, f: impl FnOnce(T) -> R) -> R {
+        f(self.0.take().unwrap())
     }
 }
@@ -0,0 +1,105 @@
+use crate::{imp::Marker, outcome::Sealed, Outcome};
+use std::marker::PhantomData;
+
+/// The outcome returned by [`from_fn`](crate::from_fn).
+///
+/// Functions annotated with [`#[iex]`](macro@crate::iex) return an opaque
+/// `impl Outcome<Output = T, Error = E>`. Functions that build an outcome by hand with
+/// [`from_fn`](crate::from_fn) can name this type instead, e.g. to store the outcome in a struct
+/// field or to avoid spelling out the `impl Outcome` bound. It is an [`Outcome`] like any other:
+/// it can be propagated with `?` from `#[iex]` functions and combined with the adapters.
+///
+/// `F` is the closure producing the [`Result`]. It defaults to a function pointer, so producers
+/// that don't capture anything can be named as just `IexResult<T, E>`. Closures that capture
+/// variables can't be named, so functions returning them have to use
+/// `IexResult<T, E, impl FnOnce() -> Result<T, E>>`.
+///
+/// Unlike the outcomes generated by `#[iex]`, the closure returns a plain [`Result`], so resolving
+/// this outcome with [`into_result`](Outcome::into_result) just calls the closure and doesn't need
+/// to catch anything.
+///
+/// # Example
+///
+/// ```
+/// use iex::{from_fn, iex, IexResult, Outcome};
+///
+/// fn default_port() -> IexResult<u16, String> {
+///     from_fn(|| Ok(8080))
+/// }
+///
+/// fn parse_port(s: &str) -> IexResult<u16, String, impl FnOnce() -> Result<u16, String> + '_> {
+///     from_fn(move || s.parse().map_err(|_| format!("Invalid port: {s}")))
+/// }
+///
+/// #[iex]
+/// fn port(s: Option<&str>) -> Result<u16, String> {
+///     match s {
+///         Some(s) => Ok(parse_port(s)?),
+///         None => Ok(default_port()?),
+///     }
+/// }
+///
+/// assert_eq!(port(None).into_result(), Ok(8080));
+/// assert_eq!(port(Some("80")).into_result(), Ok(80));
+/// assert_eq!(port(Some("x")).into_result(), Err("Invalid port: x".to_string()));
+/// ```
+#[must_use]
+pub struct IexResult<T, E, F = fn() -> Result<T, E>>(F, PhantomData<fn() -> (T, E)>);
+
+impl<T, E, F: FnOnce() -> Result<T, E>> IexResult<T, E, F> {
+    pub(crate) fn new(f: F) -> Self {
+        Self(f, PhantomData)
+    }
+
+    fn into_iex_result(self) -> impl Outcome<Output = T, Error = E> {
+        crate::iex_result::IexResult(move |marker| self.get_value_or_panic(marker), PhantomData)
+    }
+}
+
+impl<T, E, F> Sealed for IexResult<T, E, F> {}
+
+impl<T, E, F: FnOnce() -> Result<T, E>> Outcome for IexResult<T, E, F> {
+    type Output = T;
+
+    type Error = E;
+
+    fn get_value_or_panic(self, marker: Marker<E>) -> T {
+        (self.0)().get_value_or_panic(marker)
+    }
+
+    #[cfg(doc)]
+    #[crate::iex]
+    fn inspect_err<G>(self, f: G) -> Result<T, E>
+    where
+        G: FnOnce(&Self::Error),
+    {
+    }
+
+    #[cfg(not(doc))]
+    fn inspect_err<G>(self, f: G) -> impl Outcome<Output = T, Error = E>
+    where
+        G: FnOnce(&Self::Error),
+    {
+        self.into_iex_result().inspect_err(f)
+    }
+
+    #[cfg(doc)]
+    #[crate::iex]
+    fn map_err<E2, O>(self, op: O) -> Result<T, E2>
+    where
+        O: FnOnce(E) -> E2,
+    {
+    }
+
+    #[cfg(not(doc))]
+    fn map_err<E2, O>(self, op: O) -> impl Outcome<Output = Self::Output, Error = E2>
+    where
+        O: FnOnce(E) -> E2,
+    {
+        self.into_iex_result().map_err(op)
+    }
+
+    fn into_result(self) -> Result<T, E> {
+        (self.0)()
+    }
+}
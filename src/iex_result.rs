@@ -3,11 +3,20 @@ use crate::{
     outcome::Sealed,
     IexPanic, Outcome, EXCEPTION,
 };
+use std::any::Any;
+use std::convert::Infallible;
 use std::marker::PhantomData;
 use std::panic::AssertUnwindSafe;
 
-pub(crate) trait CallWithMarker<T, E> {
+pub(crate) trait CallWithMarker<T, E>: Sized {
     fn call_with_marker(self, marker: Marker<E>) -> T;
+
+    // Overridden by `MapErr` so that `.map_err(f).map_err(g)` installs a single `ExceptionMapper`
+    // calling `g(f(err))` instead of two nested ones.
+    #[inline(always)]
+    fn map_err<F, O: FnOnce(E) -> F>(self, op: O) -> impl CallWithMarker<T, F> {
+        MapErr(self, op, PhantomData)
+    }
 }
 
 impl<T, E, Func: FnOnce(Marker<E>) -> T> CallWithMarker<T, E> for Func {
@@ -17,6 +26,26 @@ impl<T, E, Func: FnOnce(Marker<E>) -> T> CallWithMarker<T, E> for Func {
     }
 }
 
+struct MapErr<Func, O, E>(Func, O, PhantomData<fn(E)>);
+
+impl<T, E, F, Func: CallWithMarker<T, E>, O: FnOnce(E) -> F> CallWithMarker<T, F>
+    for MapErr<Func, O, E>
+{
+    #[inline(always)]
+    fn call_with_marker(self, marker: Marker<F>) -> T {
+        let exception_mapper = ExceptionMapper::new(marker, (), |(), err| (self.1)(err));
+        let value = self.0.call_with_marker(exception_mapper.get_in_marker());
+        exception_mapper.swallow();
+        value
+    }
+
+    #[inline(always)]
+    fn map_err<G, P: FnOnce(F) -> G>(self, op: P) -> impl CallWithMarker<T, G> {
+        let inner_op = self.1;
+        MapErr(self.0, move |err| op(inner_op(err)), PhantomData)
+    }
+}
+
 pub struct IexResult<T, E, Func>(pub Func, pub PhantomData<fn() -> (T, E)>);
 
 impl<T, E, Func> Sealed for IexResult<T, E, Func> {}
@@ -65,34 +94,140 @@ impl<T, E, Func: CallWithMarker<T, E>> Outcome for IexResult<T, E, Func> {
     where
         O: FnOnce(E) -> F,
     {
-        IexResult(
-            |marker| {
-                let exception_mapper = ExceptionMapper::new(marker, (), |(), err| op(err));
-                let value = self.get_value_or_panic(exception_mapper.get_in_marker());
-                exception_mapper.swallow();
-                value
-            },
-            PhantomData,
-        )
+        // Unlike in `_IexForward`, there is no shortcut for `F == E` here: `op` may still change
+        // the value of the error, so the mapper has to be installed regardless of the types.
+        IexResult(self.0.map_err(op), PhantomData)
     }
 
     fn into_result(self) -> Result<T, E> {
-        std::panic::catch_unwind(AssertUnwindSafe(|| {
-            self.0.call_with_marker(unsafe { Marker::new() })
-        }))
-        .map_err(
-            #[cold]
-            |payload| {
-                if !payload.is::<IexPanic>() {
+        if typeid::of::<E>() == typeid::of::<Infallible>() {
+            return Ok(catch_infallible(self.0));
+        }
+        if std::thread::panicking() {
+            while_unwinding(|| catch(self.0))
+        } else {
+            catch(self.0)
+        }
+    }
+}
+
+fn catch<T, E, Func: CallWithMarker<T, E>>(func: Func) -> Result<T, E> {
+    std::panic::catch_unwind(AssertUnwindSafe(|| {
+        func.call_with_marker(unsafe { Marker::new() })
+    }))
+    .map_err(take_caught_error)
+}
+
+// Like `catch`, but hands the error straight to `handler` instead of building a `Result`, so that
+// all of the error handling stays in the cold path.
+pub(crate) fn catch_or_else<T, E, Func: CallWithMarker<T, E>>(
+    func: Func,
+    handler: impl FnOnce(E) -> T,
+) -> T {
+    if std::thread::panicking() {
+        return while_unwinding(|| catch(func)).unwrap_or_else(handler);
+    }
+    match std::panic::catch_unwind(AssertUnwindSafe(|| {
+        func.call_with_marker(unsafe { Marker::new() })
+    })) {
+        Ok(value) => value,
+        Err(payload) => handler(take_caught_error(payload)),
+    }
+}
+
+#[cold]
+fn take_caught_error<E>(payload: Box<dyn Any + Send>) -> E {
+    if !payload.is::<IexPanic>() {
+        std::panic::resume_unwind(payload);
+    }
+    #[cfg(feature = "location")]
+    crate::location::on_catch();
+    // An error handler panicked while the error was propagating
+    if let Some(payload) = crate::exception_mapper::take_deferred_panic() {
+        std::panic::resume_unwind(payload);
+    }
+    // See the contract of `Outcome::get_value_or_panic`
+    debug_assert!(
+        !EXCEPTION.with(|exception| unsafe { &*exception.get() }.is_clear()),
+        "an outcome unwound with IexPanic without storing an error",
+    );
+    EXCEPTION.with(|exception| unsafe {
+        let exception = &mut *exception.get();
+        let error = exception.read_unchecked();
+        exception.clear();
+        error
+    })
+}
+
+// An error of type `Infallible` cannot be constructed, so there is no error to read. But an error
+// handler mapping an inner error to `Infallible` may panic, in which case the inner error still
+// unwinds with `IexPanic` and the handler's panic is deferred. Re-raise it, as `catch` would.
+fn catch_infallible<T, E, Func: CallWithMarker<T, E>>(func: Func) -> T {
+    std::panic::catch_unwind(AssertUnwindSafe(|| {
+        func.call_with_marker(unsafe { Marker::new() })
+    }))
+    .unwrap_or_else(
+        #[cold]
+        |payload| {
+            if payload.is::<IexPanic>() {
+                if let Some(payload) = crate::exception_mapper::take_deferred_panic() {
                     std::panic::resume_unwind(payload);
                 }
-                EXCEPTION.with(|exception| unsafe {
-                    let exception = &mut *exception.get();
-                    let error = exception.read_unchecked();
-                    exception.clear();
-                    error
-                })
-            },
-        )
+            }
+            std::panic::resume_unwind(payload)
+        },
+    )
+}
+
+// If we're called from a destructor while an error is being propagated, EXCEPTION holds that
+// error. Stash it for the duration of the call, so that an error thrown and caught here does not
+// clobber it. The same goes for a panic deferred by an error handler: it belongs to the outer
+// catch, and re-raising it here would panic in a destructor during unwinding.
+#[cold]
+fn while_unwinding<R>(f: impl FnOnce() -> R) -> R {
+    let outer_exception = EXCEPTION.with(|exception| unsafe { (*exception.get()).take() });
+    let outer_panic = crate::exception_mapper::take_deferred_panic();
+    #[cfg(feature = "location")]
+    let outer_location = crate::location::take_in_flight();
+    let result = f();
+    EXCEPTION.with(|exception| unsafe { *exception.get() = outer_exception });
+    crate::exception_mapper::restore_deferred_panic(outer_panic);
+    #[cfg(feature = "location")]
+    crate::location::restore_in_flight(outer_location);
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::panic::catch_unwind;
+
+    #[test]
+    fn ok_and_err() {
+        let ok = IexResult(|_marker: Marker<String>| 1, PhantomData);
+        assert_eq!(ok.into_result(), Ok(1));
+        let err = IexResult(
+            |marker| Err::<i32, _>(String::from("error")).get_value_or_panic(marker),
+            PhantomData,
+        );
+        assert_eq!(err.into_result(), Err(String::from("error")));
+    }
+
+    // An implementation that unwinds without storing an error would make `catch` read garbage
+    #[cfg(debug_assertions)]
+    #[test]
+    fn unwinding_without_error_is_caught() {
+        let payload = catch_unwind(|| {
+            IexResult(
+                |_marker: Marker<String>| -> i32 { std::panic::resume_unwind(Box::new(IexPanic)) },
+                PhantomData,
+            )
+            .into_result()
+        })
+        .unwrap_err();
+        assert_eq!(
+            payload.downcast_ref::<&str>(),
+            Some(&"an outcome unwound with IexPanic without storing an error")
+        );
     }
 }
@@ -0,0 +1,89 @@
+//! Reading from [`std::io::Read`] with outcomes.
+//!
+//! Available with the `io` feature only.
+//!
+//! Binary parsers read many small values, and applying `?` to the [`io::Result`] of each read
+//! returns the error by value through every frame of the parser. The methods of [`ReadIexExt`]
+//! propagate errors as exceptions instead, like [`#[iex]`](macro@crate::iex) functions do.
+//!
+//! # Example
+//!
+//! ```
+//! use iex::{iex, io::ReadIexExt, Outcome};
+//! use std::io::{self, Read};
+//!
+//! #[iex]
+//! fn read_record(reader: &mut impl Read) -> io::Result<(u8, u32)> {
+//!     let tag = reader.read_u8()?;
+//!     let length = reader.read_u32_le()?;
+//!     Ok((tag, length))
+//! }
+//!
+//! let mut input: &[u8] = &[1, 0x78, 0x56, 0x34, 0x12, 2, 0];
+//! assert_eq!(read_record(&mut input).into_result().unwrap(), (1, 0x12345678));
+//! let err = read_record(&mut input).into_result().unwrap_err();
+//! assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+//! ```
+
+use crate::iex;
+use std::io::{self, Read};
+
+macro_rules! read_int {
+    ($($(#[$attr:meta])* $name:ident: $ty:ty = $from_bytes:ident;)*) => {
+        $(
+            $(#[$attr])*
+            ///
+            /// Throws an error of kind [`UnexpectedEof`](io::ErrorKind::UnexpectedEof) if the reader
+            /// ends early.
+            #[iex]
+            fn $name(&mut self) -> io::Result<$ty> {
+                Ok(<$ty>::$from_bytes(self.read_array_iex()?))
+            }
+        )*
+    };
+}
+
+/// Extension methods for [`Read`] that propagate errors as exceptions.
+///
+/// This trait is implemented for all readers. See the [module documentation](self) for an example.
+pub trait ReadIexExt: Read {
+    /// Read the exact number of bytes required to fill `buf`.
+    ///
+    /// This is [`Read::read_exact`] for outcomes. If the reader ends early, an error of kind
+    /// [`UnexpectedEof`](io::ErrorKind::UnexpectedEof) is thrown, and the contents of `buf` are
+    /// unspecified.
+    #[iex]
+    fn read_exact_iex<'a>(&'a mut self, buf: &'a mut [u8]) -> io::Result<()> {
+        self.read_exact(buf)
+    }
+
+    /// Read exactly `N` bytes into an array.
+    ///
+    /// Throws an error of kind [`UnexpectedEof`](io::ErrorKind::UnexpectedEof) if the reader ends
+    /// early.
+    #[iex]
+    fn read_array_iex<const N: usize>(&mut self) -> io::Result<[u8; N]> {
+        let mut buf = [0; N];
+        self.read_exact(&mut buf)?;
+        Ok(buf)
+    }
+
+    read_int! {
+        /// Read a byte.
+        read_u8: u8 = from_le_bytes;
+        /// Read a little-endian `u16`.
+        read_u16_le: u16 = from_le_bytes;
+        /// Read a big-endian `u16`.
+        read_u16_be: u16 = from_be_bytes;
+        /// Read a little-endian `u32`.
+        read_u32_le: u32 = from_le_bytes;
+        /// Read a big-endian `u32`.
+        read_u32_be: u32 = from_be_bytes;
+        /// Read a little-endian `u64`.
+        read_u64_le: u64 = from_le_bytes;
+        /// Read a big-endian `u64`.
+        read_u64_be: u64 = from_be_bytes;
+    }
+}
+
+impl<R: Read + ?Sized> ReadIexExt for R {}
@@ -0,0 +1,266 @@
+use crate::{
+    iex,
+    imp::{_IexForward, IexResult, Marker},
+    Outcome,
+};
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+
+/// Fallible iteration with outcomes.
+///
+/// The fallible methods of [`Iterator`], such as [`try_for_each`](Iterator::try_for_each), expect
+/// closures returning [`Result`] or [`ControlFlow`](std::ops::ControlFlow), so `#[iex]` functions
+/// can only be passed to them via [`into_result`](Outcome::into_result). The methods of this trait
+/// take closures returning outcomes and propagate errors as exceptions instead.
+pub trait IexIteratorExt: Iterator {
+    /// Call a fallible function on each item, stopping at the first error.
+    ///
+    /// This is [`Iterator::try_for_each`] for closures returning outcomes. Once `f` fails, the
+    /// error is propagated, and the remaining items are not visited.
+    ///
+    /// Outcomes are lazy, so the outcome returned by `f` can't borrow variables that `f` captures
+    /// by mutable reference. Use a [`RefCell`](std::cell::RefCell) or pass the state as an item
+    /// instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, IexIteratorExt, Outcome};
+    ///
+    /// #[iex]
+    /// fn check(x: u32) -> Result<(), String> {
+    ///     if x > 10 {
+    ///         Err(format!("{x} is too large"))
+    ///     } else {
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// #[iex]
+    /// fn check_all(xs: &[u32]) -> Result<(), String> {
+    ///     xs.iter().iex_try_for_each(|&x| check(x))
+    /// }
+    ///
+    /// assert_eq!(check_all(&[1, 2, 3]).into_result(), Ok(()));
+    /// assert_eq!(check_all(&[1, 20, 30]).into_result(), Err("20 is too large".to_string()));
+    /// ```
+    #[iex]
+    fn iex_try_for_each<O, F>(self, mut f: F) -> Result<(), O::Error>
+    where
+        Self: Sized,
+        O: Outcome<Output = ()>,
+        F: FnMut(Self::Item) -> O,
+    {
+        for item in self {
+            f(item)?;
+        }
+        Ok(())
+    }
+
+    /// Resolve an iterator of outcomes, moving errors to `sink` and yielding successful outputs.
+    ///
+    /// This is the iterator counterpart of [`Outcome::sink_err`]: failed items are skipped
+    /// instead of being replaced by a default. Each outcome is resolved when the corresponding
+    /// item is requested, so errors only accumulate as the iterator is consumed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, IexIteratorExt, Outcome};
+    ///
+    /// #[iex]
+    /// fn parse(s: &str) -> Result<i32, String> {
+    ///     s.parse().map_err(|_| format!("Invalid number: {s}"))
+    /// }
+    ///
+    /// let mut errors = Vec::new();
+    /// let sum: i32 = ["1", "x", "2", "y"].into_iter().map(parse).sink_errors(&mut errors).sum();
+    /// assert_eq!(sum, 3);
+    /// assert_eq!(errors, ["Invalid number: x", "Invalid number: y"]);
+    /// ```
+    fn sink_errors<C>(self, sink: &mut C) -> impl Iterator<Item = <Self::Item as Outcome>::Output>
+    where
+        Self: Sized,
+        Self::Item: Outcome,
+        C: Extend<<Self::Item as Outcome>::Error>,
+    {
+        self.filter_map(|outcome| match outcome.into_result() {
+            Ok(value) => Some(value),
+            Err(err) => {
+                sink.extend(Some(err));
+                None
+            }
+        })
+    }
+}
+
+impl<I: Iterator> IexIteratorExt for I {}
+
+/// Collect the outputs of outcomes into a boxed slice, stopping at the first error.
+///
+/// If the iterator reports an exact size, as arrays, slices and [`Vec`]s mapped with
+/// [`Iterator::map`] do, the slice is allocated once, at the right size. Otherwise, this behaves
+/// like collecting into a [`Vec`] and converting it with [`Vec::into_boxed_slice`].
+///
+/// Once an outcome fails, the error is propagated, the outputs collected so far are dropped, and
+/// the remaining items are not visited.
+///
+/// # Example
+///
+/// ```
+/// use iex::{collect_boxed_slice, iex, Outcome};
+///
+/// #[iex]
+/// fn parse(s: &str) -> Result<u8, String> {
+///     s.parse().map_err(|_| format!("Invalid number: {s}"))
+/// }
+///
+/// #[iex]
+/// fn parse_all(input: &str) -> Result<Box<[u8]>, String> {
+///     collect_boxed_slice(input.split(',').map(parse))
+/// }
+///
+/// assert_eq!(parse_all("1,2,3").into_result().as_deref(), Ok(&[1, 2, 3][..]));
+/// assert_eq!(parse_all("1,x,3").into_result(), Err("Invalid number: x".to_string()));
+/// ```
+#[iex]
+pub fn collect_boxed_slice<I, T, E>(iter: I) -> Result<Box<[T]>, E>
+where
+    I: IntoIterator,
+    I::Item: Outcome<Output = T, Error = E>,
+{
+    let iter = iter.into_iter();
+    let mut items = Vec::with_capacity(iter.size_hint().0);
+    for outcome in iter {
+        items.push(outcome?);
+    }
+    Ok(items.into_boxed_slice())
+}
+
+/// Collect the outputs of outcomes, stopping at the first error and reporting progress.
+///
+/// This is like collecting an iterator of [`Result`]s into `Result<C, E>`, but for outcomes,
+/// with `cb` called with the number of outputs collected so far after every `every` successful
+/// items, e.g. to report the progress of a long deserialization job. Checking whether to call
+/// `cb` costs a decrement and a comparison per item.
+///
+/// `cb` is only called on the happy path. Once an outcome fails, the error is propagated without
+/// calling `cb`, the outputs collected so far are dropped, and the remaining items are not
+/// visited. `cb` is not called for the items after the last full interval either, so the caller
+/// has to report completion, if necessary.
+///
+/// # Panics
+///
+/// Panics if `every` is zero.
+///
+/// # Example
+///
+/// ```
+/// use iex::{iex, try_collect_iex_with_progress, Outcome};
+///
+/// #[iex]
+/// fn parse(s: &str) -> Result<u8, String> {
+///     s.parse().map_err(|_| format!("Invalid number: {s}"))
+/// }
+///
+/// #[iex]
+/// fn parse_all(input: &str, progress: &mut Vec<usize>) -> Result<Vec<u8>, String> {
+///     try_collect_iex_with_progress(input.split(',').map(parse), 2, |n| progress.push(n))
+/// }
+///
+/// let mut progress = Vec::new();
+/// assert_eq!(parse_all("1,2,3,4,5", &mut progress).into_result(), Ok(vec![1, 2, 3, 4, 5]));
+/// assert_eq!(progress, [2, 4]);
+/// ```
+pub fn try_collect_iex_with_progress<I, C, F>(
+    iter: I,
+    every: usize,
+    mut cb: F,
+) -> impl Outcome<Output = C, Error = <I::Item as Outcome>::Error>
+where
+    I: IntoIterator,
+    I::Item: Outcome,
+    C: FromIterator<<I::Item as Outcome>::Output>,
+    F: FnMut(usize),
+{
+    assert_ne!(every, 0, "progress interval must be non-zero");
+    IexResult(
+        move |marker: Marker<<I::Item as Outcome>::Error>| {
+            let mut collected = 0;
+            let mut until_report = every;
+            iter.into_iter()
+                .map(|outcome| {
+                    let output = outcome.get_value_or_panic(marker);
+                    collected += 1;
+                    until_report -= 1;
+                    if until_report == 0 {
+                        until_report = every;
+                        cb(collected);
+                    }
+                    output
+                })
+                .collect()
+        },
+        PhantomData,
+    )
+}
+
+/// Collect the outputs of an iterator of outcomes that is itself produced by an outcome.
+///
+/// This drives `outcome` first, then the outcomes it yields, collecting their outputs. An error at
+/// either level is propagated, with the errors of the items converted with [`Into`] like `?` does.
+/// Once an item fails, the outputs collected so far are dropped, and the remaining items are not
+/// visited.
+///
+/// This is useful for two-level parsers, where the outer outcome parses a header and returns an
+/// iterator parsing the records.
+///
+/// # Example
+///
+/// ```
+/// use iex::{flatten_collect, iex, Outcome};
+///
+/// #[iex]
+/// fn parse(s: &str) -> Result<u8, String> {
+///     s.parse().map_err(|_| format!("Invalid number: {s}"))
+/// }
+///
+/// // "<count>:<items>". `#[iex]` can't return `Result<impl Trait, _>`, so this is a plain function.
+/// fn records(
+///     input: &str,
+/// ) -> Result<impl Iterator<Item = impl Outcome<Output = u8, Error = String> + '_>, String> {
+///     let (count, items) = input.split_once(':').ok_or("Missing count")?;
+///     let count = parse(count).into_result()?;
+///     Ok(items.split(',').take(count as usize).map(parse))
+/// }
+///
+/// #[iex]
+/// fn parse_all(input: &str) -> Result<Vec<u8>, String> {
+///     flatten_collect(records(input))
+/// }
+///
+/// assert_eq!(parse_all("2:1,2,3").into_result(), Ok(vec![1, 2]));
+/// assert_eq!(parse_all("x:1").into_result(), Err("Invalid number: x".to_string()));
+/// assert_eq!(parse_all("2:1,y").into_result(), Err("Invalid number: y".to_string()));
+/// ```
+#[doc(alias = "flatten_iter")]
+pub fn flatten_collect<O, C>(outcome: O) -> impl Outcome<Output = C, Error = O::Error>
+where
+    O: Outcome,
+    O::Output: IntoIterator,
+    <O::Output as IntoIterator>::Item: Outcome,
+    <<O::Output as IntoIterator>::Item as Outcome>::Error: Into<O::Error>,
+    C: FromIterator<<<O::Output as IntoIterator>::Item as Outcome>::Output>,
+{
+    IexResult(
+        move |marker: Marker<O::Error>| {
+            outcome
+                .get_value_or_panic(marker)
+                .into_iter()
+                // What `?` expands to in #[iex] functions
+                .map(|item| (marker, ManuallyDrop::new(item))._iex_forward())
+                .collect()
+        },
+        PhantomData,
+    )
+}
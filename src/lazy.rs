@@ -0,0 +1,73 @@
+use crate::{iex, imp::IexResult, DynOutcome, Outcome};
+use std::marker::PhantomData;
+
+/// A pending fallible computation with a nameable type.
+///
+/// The outcomes returned by [`#[iex]`](macro@crate::iex) functions have opaque types, so they
+/// can't be stored in struct fields or collections without making the container generic.
+/// `Lazy<'a, T, E>` boxes any outcome with output `T` and error `E` that lives for `'a`. Use
+/// `Lazy<'static, T, E>` for computations that don't borrow anything.
+///
+/// The computation is driven exactly once, by [`run`](Self::run), which consumes the `Lazy`.
+/// Dropping a `Lazy` without running it drops the computation without driving it.
+///
+/// # Example
+///
+/// ```
+/// use iex::{iex, Lazy, Outcome};
+/// use std::collections::VecDeque;
+///
+/// #[iex]
+/// fn parse(s: String) -> Result<i32, String> {
+///     s.parse().map_err(|_| format!("Invalid number: {s}"))
+/// }
+///
+/// #[derive(Default)]
+/// struct WorkQueue {
+///     jobs: VecDeque<Lazy<'static, i32, String>>,
+/// }
+///
+/// impl WorkQueue {
+///     fn push(&mut self, input: &str) {
+///         self.jobs.push_back(Lazy::new(parse(input.to_string())));
+///     }
+///
+///     #[iex]
+///     fn run_all(&mut self) -> Result<i32, String> {
+///         let mut sum = 0;
+///         while let Some(job) = self.jobs.pop_front() {
+///             sum += job.run()?;
+///         }
+///         Ok(sum)
+///     }
+/// }
+///
+/// let mut queue = WorkQueue::default();
+/// queue.push("1");
+/// queue.push("2");
+/// assert_eq!(queue.run_all().into_result(), Ok(3));
+///
+/// queue.push("x");
+/// queue.push("4");
+/// assert_eq!(queue.run_all().into_result(), Err("Invalid number: x".to_string()));
+/// // The remaining job was not run
+/// assert_eq!(queue.jobs.len(), 1);
+/// ```
+#[must_use]
+pub struct Lazy<'a, T, E>(Box<dyn DynOutcome<Output = T, Error = E> + 'a>);
+
+impl<'a, T, E> Lazy<'a, T, E> {
+    /// Wrap an outcome.
+    pub fn new(outcome: impl Outcome<Output = T, Error = E> + 'a) -> Self {
+        Self(Box::new(outcome))
+    }
+
+    /// Drive the computation.
+    #[iex(captures = "'a")]
+    pub fn run(self) -> Result<T, E> {
+        IexResult(
+            move |marker| self.0.get_value_or_panic_boxed(marker),
+            PhantomData,
+        )
+    }
+}
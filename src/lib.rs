@@ -120,17 +120,19 @@
 //!
 //! # All you need to know
 //!
-//! Functions marked [`#[iex]`](macro@iex) are supposed to return a [`Result<T, E>`] in their
-//! definition. The macro rewrites them to return an opaque type `#[iex] Result<T, E>` instead. This
-//! type implements [`Outcome`], so you can call methods like [`map_err`](Outcome::map_err), but
-//! other than that, you must immediately propagate the error via `?`.
+//! Functions marked [`#[iex]`](macro@iex) are supposed to return a [`Result<T, E>`] (or a type alias
+//! to it, like `io::Result<T>`) in their definition. The macro rewrites them to return an opaque
+//! type `#[iex] Result<T, E>` instead. This type implements [`Outcome`], so you can call methods
+//! like [`map_err`](Outcome::map_err), but other than that, you must immediately propagate the
+//! error via `?`.
 //!
 //! Alternatively, you can cast it to a [`Result`] via [`.into_result()`](Outcome::into_result).
 //! This is the only way to avoid immediate propagation.
 //!
 //! Doing anything else to the return value, e.g. storing it in a variable and using it later will
 //! not cause UB, but will not work the way you think either. If you want to swallow the error, use
-//! `let _ = func().into_result();` instead.
+//! `let _ = func().into_result();` instead. If you need to store an outcome and resolve it later,
+//! wrap it in a [`PollableOutcome`].
 //!
 //! Directly returning an `#[iex] Result` (obtained from a function call) from another
 //! [`#[iex]`](macro@iex) function also works, provided that it's the only `return` statement in the
@@ -140,19 +142,45 @@
 //! block, the corresponding function in the `trait Trait` block should also be marked with
 //! [`#[iex]`](macro@iex). Such traits are not object-safe, unless the method is restricted to
 //! `where Self: Sized` (open an issue if you want me to spend time developing a workaround).
+//!
+//! # Features
+//!
+//! - `anyhow`: [`anyhow`](https://docs.rs/anyhow) compatibility via [`Context`], `ctx!` and
+//!   `Outcome::context_or_recover`.
+//! - `rayon`: [`rayon`](https://docs.rs/rayon) compatibility via `IexParallelIteratorExt`.
+//! - `debug`: format outcomes for debugging via `Outcome::debug_drive`.
+//! - `log`: log errors recovered by `Outcome::unwrap_or_log` via [`log`](https://docs.rs/log).
+//! - `metrics`: measure how long outcomes take to succeed or fail via `Outcome::inspect_timing`.
+//! - `location`: track where errors were last propagated, see `Outcome::into_result_located` and
+//!   `Located`.
+//! - `testing`: helpers for asserting that no error was left unhandled and random `#[iex]` call
+//!   trees for stress testing, see `testing`.
+//! - `exception_store`: store the error in flight somewhere other than a thread-local, see
+//!   `exception_store`.
+//! - `future`: resolve outcomes in `async` code via `Outcome::into_future`.
+//! - `io`: read integers and byte arrays from [`std::io::Read`] with outcomes, see `io`.
+//! - `always_box_error`: box all errors instead of storing small errors inline. This makes the
+//!   thread-local error slot a single pointer and moves more error handling code out of line, at
+//!   the cost of an allocation per thrown error. Whether this makes the binary smaller depends on
+//!   the code: on the `binary_size` example, which has instructions for measuring it, the stripped
+//!   release binary gets larger instead. Measure on your code before enabling it.
 
-#![cfg_attr(doc, feature(doc_auto_cfg))]
+#![cfg_attr(doc, feature(doc_cfg))]
 
 mod macros;
-pub use macros::{iex, try_block};
+#[cfg(feature = "anyhow")]
+pub use macros::ctx;
+pub use macros::{as_result, iex, main, try_block};
 
+#[cfg(not(feature = "exception_store"))]
 use std::cell::UnsafeCell;
 
 mod exception;
+#[cfg(not(feature = "exception_store"))]
 use exception::Exception;
 
 mod outcome;
-pub use outcome::Outcome;
+pub use outcome::{apply, from_fn, partition_iex, race, validate_all, Outcome};
 
 #[cfg(feature = "anyhow")]
 mod anyhow_compat;
@@ -166,11 +194,67 @@ impl<T, E> Context<T, E> for Result<T, E> {}
 #[cfg(not(feature = "anyhow"))]
 impl<T, E, Func: iex_result::CallWithMarker<T, E>> Context<T, E> for imp::IexResult<T, E, Func> {}
 #[cfg(not(feature = "anyhow"))]
+impl<T, E, Func: FnOnce() -> Result<T, E>> Context<T, E> for IexResult<T, E, Func> {}
+#[cfg(not(feature = "anyhow"))]
 impl<T> Context<T, std::convert::Infallible> for Option<T> {}
 
+mod option;
+pub use option::OptionIexExt;
+
+mod iterator;
+pub use iterator::{
+    collect_boxed_slice, flatten_collect, try_collect_iex_with_progress, IexIteratorExt,
+};
+
+#[cfg(feature = "rayon")]
+mod rayon_compat;
+#[cfg(feature = "rayon")]
+pub use rayon_compat::IexParallelIteratorExt;
+
+#[cfg(feature = "testing")]
+pub mod testing;
+
+#[cfg(feature = "location")]
+mod location;
+#[cfg(feature = "location")]
+pub use location::Located;
+
+#[cfg(feature = "exception_store")]
+pub mod exception_store;
+
+#[cfg(feature = "io")]
+pub mod io;
+
 mod iex_result;
 mod result;
 
+mod cancellation;
+pub use cancellation::{CancellationToken, Cancelled};
+
+mod chained_error;
+pub use chained_error::ChainedError;
+
+mod from_fn;
+pub use from_fn::IexResult;
+
+mod set_location;
+pub use set_location::SetLocation;
+
+mod error_mapper;
+pub use error_mapper::ErrorMapper;
+
+mod pollable;
+pub use pollable::PollableOutcome;
+
+mod dyn_outcome;
+pub use dyn_outcome::DynOutcome;
+
+mod cached;
+pub use cached::CachedOutcome;
+
+mod lazy;
+pub use lazy::Lazy;
+
 mod exception_mapper;
 mod forward;
 mod marker;
@@ -179,16 +263,21 @@ pub mod example;
 
 struct IexPanic;
 
+#[cfg(not(feature = "exception_store"))]
 thread_local! {
     static EXCEPTION: UnsafeCell<Exception> = const { UnsafeCell::new(Exception::new()) };
 }
+#[cfg(feature = "exception_store")]
+static EXCEPTION: exception_store::CurrentException = exception_store::CurrentException;
 
 #[doc(hidden)]
 pub mod imp {
     use super::*;
-    pub use exception_mapper::ExceptionMapper;
+    pub use exception_mapper::{ExceptionMapper, ExceptionRefMapper};
     pub use fix_hidden_lifetime_bug;
-    pub use forward::_IexForward;
+    pub use forward::{
+        _IexCallIntoResult, _IexForward, _IexIntoResult, _IexReturn, _IexSkipIntoResult,
+    };
     pub use iex_result::IexResult;
     pub use marker::Marker;
     pub struct NoCopy;
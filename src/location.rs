@@ -0,0 +1,90 @@
+use crate::EXCEPTION;
+use std::cell::Cell;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::panic::Location;
+
+type StaticLocation = &'static Location<'static>;
+
+thread_local! {
+    // The location of the last `?` the in-flight error has passed through
+    static IN_FLIGHT: Cell<Option<StaticLocation>> = const { Cell::new(None) };
+    // The location of the most recently caught error
+    static CAUGHT: Cell<Option<StaticLocation>> = const { Cell::new(None) };
+}
+
+// Installed around each `?`. Only its destructor does any work, and it's forgotten on the happy
+// path, so dropping it means that an error is propagating through the `?`.
+pub(crate) struct LocationGuard(StaticLocation);
+
+impl LocationGuard {
+    #[track_caller]
+    pub(crate) fn new() -> Self {
+        Self(Location::caller())
+    }
+
+    pub(crate) fn disarm(self) {
+        std::mem::forget(self);
+    }
+}
+
+impl Drop for LocationGuard {
+    fn drop(&mut self) {
+        // Ignore ordinary panics
+        if EXCEPTION.with(|exception| unsafe { &*exception.get() }.is_clear()) {
+            return;
+        }
+        // Each `?` overwrites the previous one, so that the outermost `?` before the error is
+        // caught wins
+        IN_FLIGHT.with(|location| location.set(Some(self.0)));
+    }
+}
+
+pub(crate) fn on_catch() {
+    CAUGHT.with(|caught| caught.set(IN_FLIGHT.with(Cell::take)));
+}
+
+pub(crate) fn take_in_flight() -> Option<StaticLocation> {
+    IN_FLIGHT.with(Cell::take)
+}
+
+pub(crate) fn restore_in_flight(location: Option<StaticLocation>) {
+    IN_FLIGHT.with(|in_flight| in_flight.set(location));
+}
+
+pub(crate) fn reset_caught() {
+    CAUGHT.with(|caught| caught.set(None));
+}
+
+pub(crate) fn take_caught() -> Option<StaticLocation> {
+    CAUGHT.with(Cell::take)
+}
+
+/// An error along with the location of the outermost `?` it was propagated through.
+///
+/// This is produced by [`Outcome::into_result_with_location`](crate::Outcome::into_result_with_location).
+///
+/// The location is displayed after the error, e.g. `Oops at src/main.rs:10:5`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Located<E> {
+    /// The original error.
+    pub error: E,
+    /// Where the error was last propagated, or `None` if it didn't pass through any `?`.
+    pub location: Option<StaticLocation>,
+}
+
+impl<E: Display> Display for Located<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error)?;
+        if let Some(location) = self.location {
+            write!(f, " at {location}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<E: Error> Error for Located<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.error.source()
+    }
+}
@@ -92,6 +92,13 @@
 /// }
 /// ```
 ///
+/// Error types may be generic associated types, including ones that borrow from the arguments,
+/// e.g. `Result<T, Self::Error<'a>>` with `type Error<'a>`. Required methods and methods in `impl`
+/// blocks support any number of lifetimes, but provided trait methods (those with a default body)
+/// fail with a `Captures<'a>` lifetime mismatch if their signature mentions more than one lifetime,
+/// elided ones included. Give all references the same lifetime there, e.g.
+/// `fn parse_pair<'a>(&'a self, a: &'a str, b: &'a str)`, or move the body to a free function.
+///
 /// ## Closures
 ///
 /// `#[iex]` closures can't take arguments whose types contain non-`'static` lifetimes. Sorry. Also,
@@ -99,6 +106,34 @@
 /// [`stmt_expr_attributes`](https://github.com/rust-lang/rust/issues/15701) and
 /// [`proc_macro_hygiene`](https://github.com/rust-lang/rust/issues/54727) to be enabled.
 ///
+/// ## Async functions
+///
+/// `#[iex]` doesn't support `async` functions: an error can only be propagated by unwinding within
+/// a single call, and a future is polled many times. This includes methods of
+/// [`#[async_trait]`](https://docs.rs/async-trait) traits. The order of attributes doesn't help
+/// there, because `#[async_trait]` is applied to the whole `trait` or `impl` block and thus always
+/// runs first, turning the method into one returning `Pin<Box<dyn Future>>`. `#[iex]` recognizes
+/// such methods and reports that it ``does not support #[async_trait] methods``.
+///
+/// Instead, leave the `async` method alone and call `#[iex]` functions from its body, resolving
+/// them with [`.into_result()?`](crate::Outcome::into_result) or, with the `future` feature,
+/// `.into_future().await?`. The outcome is resolved before the next `.await`, so this doesn't
+/// affect whether the future is `Send`.
+///
+/// ## Panicking destructors
+///
+/// An error is propagated by unwinding, so destructors of local variables run while the error is
+/// in flight, just like during a panic. If such a destructor panics, Rust aborts the process with
+/// "panic in a destructor during cleanup". This can't be intercepted by the crate. If a destructor
+/// may panic, catch the panic inside `drop` with [`catch_unwind`](std::panic::catch_unwind) or
+/// check [`std::thread::panicking`] before doing anything fallible.
+///
+/// Error handlers passed to [`map_err`](crate::Outcome::map_err),
+/// [`inspect_err`](crate::Outcome::inspect_err), [`ErrorMapper`](crate::ErrorMapper) and the like
+/// also run during unwinding, but they are guarded: if one of them panics, the error is dropped,
+/// handlers further up the stack are skipped, and the panic resumes as a regular panic where the
+/// error would have been caught, e.g. at [`into_result`](crate::Outcome::into_result).
+///
 /// ## `?` in macros
 ///
 /// `#[iex]` needs to replace the `?` operator with a custom implementation in the function body.
@@ -123,6 +158,85 @@
 /// }
 /// ```
 ///
+/// ## Error conversion
+///
+/// Just like the built-in `?`, the `?` operator in `#[iex]` functions converts the error with
+/// [`Into`]. If the callee's error type does not convert into the error type of the current
+/// function, the compiler says so:
+///
+/// ```compile_fail
+/// use iex::iex;
+///
+/// struct LowLevelError;
+/// struct HighLevelError;
+///
+/// #[iex]
+/// fn low_level() -> Result<(), LowLevelError> {
+///     Err(LowLevelError)
+/// }
+///
+/// #[iex]
+/// fn high_level() -> Result<(), HighLevelError> {
+///     // cannot forward: the callee's error type `LowLevelError` does not convert into this
+///     // function's error type `HighLevelError`
+///     low_level()?;
+///     Ok(())
+/// }
+/// ```
+///
+/// Only one conversion is applied, so errors aren't converted along chains like `A → B → C`
+/// automatically. Use [`rethrow_via`](crate::Outcome::rethrow_via) to spell out the intermediate
+/// type: `a().rethrow_via::<B>()?`.
+///
+/// Unlike the built-in `?`, the `#[iex]` one needs to know the callee's error type at the `?`
+/// rather than later in the function. If it's not known yet, e.g. in `let x: i32 = s.parse()?`
+/// where the type annotation comes after the call, `?` assumes no conversion is necessary, and the
+/// compiler reports a mismatch like ``expected `anyhow::Error`, found `ParseIntError` ``. Use a
+/// turbofish, as in `s.parse::<i32>()?`, to fix this.
+///
+/// Directly returning an `#[iex] Result` from an `#[iex]` function performs no conversion at all,
+/// so the error types must match exactly. Otherwise, the compiler reports that ``the tail of an
+/// `#[iex]` function must be an outcome with error type `HighLevelError` ``. Use `Ok(..?)` to
+/// convert.
+///
+/// If `outcome` is an [`Outcome`](crate::Outcome), `outcome.into_result()?` is compiled as
+/// `outcome?`, which converts the error the same way but doesn't catch and rethrow it. Other types
+/// with an `into_result` method are unaffected.
+///
+/// # Implicit `Ok(())`
+///
+/// Functions returning `Result<(), E>` may omit the trailing `Ok(())`:
+///
+/// ```
+/// use iex::{iex, Outcome};
+///
+/// #[iex]
+/// fn check_positive(x: i32) -> Result<(), String> {
+///     if x <= 0 {
+///         return Err(format!("{x} is not positive"));
+///     }
+/// }
+///
+/// assert_eq!(check_positive(1).into_result(), Ok(()));
+/// assert!(check_positive(0).into_result().is_err());
+/// ```
+///
+/// The exact rule is: if the return type is written as a path ending with `Result` whose first
+/// generic argument is `()` (e.g. `Result<(), E>` or `io::Result<()>`, but not an alias like
+/// `MyUnitResult`), and the body does not end with an expression, `Ok(())` is appended to the
+/// body. For this purpose, trailing `for` and `while` loops and `if` without `else` count as
+/// statements, while a trailing macro call without a semicolon counts as an expression. Bodies
+/// ending with a `return` statement are left unchanged.
+///
+/// # Forwarding functions
+///
+/// If the body only passes the function's parameters, or literals, on to another function, as in
+/// `inner(a, b)`, `self.inner(a)` or `Ok(inner(a, b)?)`, the callee's outcome is reused instead of
+/// being wrapped in a new one, so such functions cost no more than calling the callee directly.
+/// The callee is then called as soon as the function is. For `#[iex]` callees, this makes no
+/// difference, because they don't run until their outcome is driven, but a callee returning a
+/// plain [`Result`] runs even if the outcome is never used.
+///
 /// # Attributes
 ///
 /// Rust evaluates attribute macros from top to bottom, so if `#[iex]` is not the only attribute
@@ -133,6 +247,52 @@
 /// Note that this only applies to attribute *macros*; normal attributes, such as `#[inline]` and
 /// `#[cfg]`, do the right thing independently from their location.
 ///
+/// For example, platform-specific variants of a function can be written either as several
+/// `#[iex]` functions gated by `#[cfg]`, or as one `#[iex]` function with `#[cfg]` on statements
+/// and blocks in the body. `?` and `cfg!` work as usual in both cases.
+///
+/// In particular, `#[must_use]` applies to the function, so ignoring its result triggers your
+/// message in addition to the generic one:
+///
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+/// use iex::iex;
+///
+/// #[must_use = "the file might not have been removed"]
+/// #[iex]
+/// fn remove_file() -> Result<(), ()> {
+///     Ok(())
+/// }
+///
+/// fn main() {
+///     // unused return value of `remove_file` that must be used
+///     remove_file();
+/// }
+/// ```
+///
+/// Lint attributes, such as `#[allow(..)]`, apply to both the signature and the body.
+///
+/// # Unsafe functions
+///
+/// `#[iex] unsafe fn` is still unsafe to call. Its body is an unsafe context or not, just like
+/// the body of an ordinary `unsafe fn`, depending on the `unsafe_op_in_unsafe_fn` lint:
+///
+/// ```compile_fail
+/// use iex::{iex, Outcome};
+///
+/// #[iex]
+/// unsafe fn read(ptr: *const i32) -> Result<i32, ()> {
+///     if ptr.is_null() {
+///         Err(())
+///     } else {
+///         Ok(*ptr)
+///     }
+/// }
+///
+/// // call to unsafe function `read` is unsafe and requires unsafe function or block
+/// read(&1).into_result();
+/// ```
+///
 /// # Documentation
 ///
 /// `#[iex]` functions are documented (by rustdoc) to return an algebraic [`Result`], just like in
@@ -142,6 +302,162 @@
 ///
 /// For a rendered example, see [`example`](crate::example).
 ///
+/// # IDE support
+///
+/// The function generated by `#[iex]` keeps the name, generics and arguments of the original
+/// function, including their spans, and its `impl Outcome` return type is spanned at the declared
+/// return type. Go to definition on a call lands on the name of the function, and type errors about
+/// the return type point at the declared `Result`.
+///
+/// IDEs don't enable `cfg(doc)`, so hovering over the function shows the signature of the
+/// generated function rather than the documented one:
+/// `fn f() -> impl Outcome<Output = <Result<T, E> as Outcome>::Output, Error = <Result<T, E> as
+/// Outcome>::Error>`. The `Result` inside is the declared one.
+///
+/// # Backtraces
+///
+/// `#[iex]` functions are lazy: calling one merely captures the arguments, and the body runs only
+/// once the outcome is propagated with `?` or cast with
+/// [`.into_result()`](crate::Outcome::into_result). The body is compiled as a closure nested in the
+/// original function, because only a closure can capture generic parameters and arguments, so it
+/// is named after that function in backtraces and profiles:
+///
+/// ```text
+///    0: my_crate::parse_header::{{closure}}
+///    1: my_crate::parse_header::{{closure}}
+///    2: <Func as iex::iex_result::CallWithMarker<T,E>>::call_with_marker
+///    3: <iex::iex_result::IexResult<T,E,Func> as iex::outcome::Outcome>::get_value_or_panic
+///    4: <(iex::marker::Marker<..>,core::mem::manually_drop::ManuallyDrop<R>) as iex::forward::_IexForward>::_iex_forward
+///    5: my_crate::parse::{{closure}}
+///    6: my_crate::parse::{{closure}}
+///    ...
+///   14: <iex::iex_result::IexResult<T,E,Func> as iex::outcome::Outcome>::into_result
+///   15: my_crate::main
+/// ```
+///
+/// The frames belonging to `iex` itself are typically inlined in release builds.
+///
+/// # `#[iex(named_body)]`
+///
+/// To tell the body apart from the glue in backtraces and profiles, `#[iex(named_body)]` compiles
+/// the body as a nested function named `__iex_body` instead. Generic parameters are redeclared on
+/// it, and the arguments are passed to it explicitly. The closures are then a thin shim pointing at
+/// the attribute, and the frame that points into the body is named:
+///
+/// ```text
+///    0: my_crate::parse_header::__iex_body
+///              at ./src/lib.rs:12:5
+///    1: my_crate::parse_header::{{closure}}
+///              at ./src/lib.rs:9:1
+///    2: my_crate::parse_header::{{closure}}
+///              at ./src/lib.rs:9:1
+///    3: <Func as iex::iex_result::CallWithMarker<T,E>>::call_with_marker
+///    4: <iex::iex_result::IexResult<T,E,Func> as iex::outcome::Outcome>::get_value_or_panic
+///    5: <(iex::marker::Marker<..>,core::mem::manually_drop::ManuallyDrop<R>) as iex::forward::_IexForward>::_iex_forward
+///    6: my_crate::parse::__iex_body
+///              at ./src/lib.rs:20:5
+///    7: my_crate::parse::{{closure}}
+///    8: my_crate::parse::{{closure}}
+///    ...
+/// ```
+///
+/// ```
+/// use iex::{iex, Outcome};
+///
+/// #[iex(named_body)]
+/// fn checked_divide<T: From<u32>>(a: u32, b: u32) -> Result<T, &'static str> {
+///     Ok(a.checked_div(b).ok_or("Cannot divide by zero")?.into())
+/// }
+///
+/// assert_eq!(checked_divide::<u64>(6, 2).into_result(), Ok(3));
+/// ```
+///
+/// A nested function can't refer to `Self` or take `self`, so this is only supported on free
+/// functions and associated functions that don't mention `Self`. The lifetimes in the error type
+/// must be named, as the function takes a marker for the error type, which would change how
+/// lifetimes are elided in its signature.
+///
+/// # `#[iex(also_result)]`
+///
+/// The only way to find out whether `#[iex]` speeds up your code is to measure it. To simplify
+/// comparing the two approaches on identical logic, `#[iex(also_result)]` additionally generates a
+/// twin function, suffixed with `_as_result`, that returns a plain [`Result`]. In the twin, `?` is
+/// applied to [`.into_result()`](crate::Outcome::into_result) of the operand, so it works with both
+/// `Result` and `#[iex] Result` callees. The twin does not call the `_as_result` twins of its
+/// callees, even if they have one: the macro can't tell from the call site. So the twin of a
+/// function that calls `#[iex]` functions is not a like-for-like baseline, as casting an
+/// `#[iex] Result` is slow. The comparison is only meaningful at leaf functions or when the callees
+/// return plain `Result`s; otherwise, write the `Result` version by hand.
+///
+/// ```
+/// use iex::{iex, Outcome};
+///
+/// #[iex(also_result)]
+/// fn checked_divide(a: u32, b: u32) -> Result<u32, &'static str> {
+///     a.checked_div(b).ok_or("Cannot divide by zero")
+/// }
+///
+/// assert_eq!(checked_divide(6, 2).into_result(), Ok(3));
+/// assert_eq!(checked_divide_as_result(6, 0), Err("Cannot divide by zero"));
+/// ```
+///
+/// This is supported on functions, methods and trait methods, but not on closures. To call the twin
+/// of a single call site without renaming it, use [`as_result!`](crate::as_result).
+///
+/// # `#[iex(fn_ptr)]`
+///
+/// An `#[iex]` function returns an opaque type, so it can't be coerced to a `fn` pointer returning
+/// [`Result`], as required by e.g. callback tables. `#[iex(fn_ptr)]` additionally generates a
+/// function suffixed with `_fn_ptr` that has the original signature and casts the outcome to a
+/// [`Result`], just like calling [`.into_result()`](crate::Outcome::into_result) on it would:
+///
+/// ```
+/// use iex::iex;
+///
+/// #[iex(fn_ptr)]
+/// fn checked_divide(a: u32, b: u32) -> Result<u32, &'static str> {
+///     a.checked_div(b).ok_or("Cannot divide by zero")
+/// }
+///
+/// let callbacks: [fn(u32, u32) -> Result<u32, &'static str>; 1] = [checked_divide_fn_ptr];
+/// assert_eq!(callbacks[0](6, 0), Err("Cannot divide by zero"));
+/// ```
+///
+/// Unlike the `_as_result` twin, the body still propagates errors by unwinding, so the errors are
+/// materialized only once, at the boundary. This costs as much as any other `.into_result()`:
+/// cheap on success, but slow if errors are common, so call the `#[iex]` function directly where
+/// no pointer is needed. This is supported on functions, methods and trait methods, but not on closures.
+///
+/// # `#[iex(output = .., error = ..)]`
+///
+/// `#[iex]` can also be applied to a function that returns a hand-written `impl Outcome`, so that
+/// `?` can be used in its body. The output and error types can't be inferred from an `impl Trait`
+/// return type, so they have to be annotated:
+///
+/// ```
+/// use iex::{apply, iex, Outcome};
+///
+/// #[iex]
+/// fn parse(s: &str) -> Result<u32, std::num::ParseIntError> {
+///     s.parse()
+/// }
+///
+/// #[iex(output = "u32", error = "std::num::ParseIntError")]
+/// fn parse_sum<'a>(
+///     a: &'a str,
+///     b: &'a str,
+/// ) -> impl Outcome<Output = u32, Error = std::num::ParseIntError> + 'a {
+///     let a = parse(a)?;
+///     apply(parse(b), move |b| a + b)
+/// }
+///
+/// assert_eq!(parse_sum("1", "2").into_result(), Ok(3));
+/// assert!(parse_sum("1", "x").into_result().is_err());
+/// ```
+///
+/// The body must evaluate to an outcome with these output and error types. The `_as_result` twin
+/// and the `_fn_ptr` shim return `Result<output, error>`.
+///
 /// # `#[iex(shares = ..)]`
 ///
 /// This use is specific for `map_err` and `inspect_err`. See the documentation for
@@ -204,6 +520,38 @@
 /// ```
 pub use iex_derive::iex;
 
+/// Use `?` on `#[iex]` functions in `main`.
+///
+/// `main` can return a [`Result`], but not an `#[iex] Result`, so without this attribute, every
+/// call to an `#[iex]` function in `main` needs [`.into_result()`](crate::Outcome::into_result)
+/// before `?`. With it, the body of `main` is compiled as an [`#[iex]`](macro@crate::iex)
+/// function, and its outcome is cast to the declared [`Result`] once, when `main` returns. The
+/// error is then reported as usual: it's printed to stderr with [`Debug`], and the process exits
+/// with a non-zero code.
+///
+/// # Example
+///
+/// ```
+/// use iex::iex;
+/// use std::num::ParseIntError;
+///
+/// #[iex]
+/// fn parse(s: &str) -> Result<u32, ParseIntError> {
+///     s.parse()
+/// }
+///
+/// #[iex::main]
+/// fn main() -> Result<(), ParseIntError> {
+///     let port = parse("8080")?;
+///     assert_eq!(port, 8080);
+///     Ok(())
+/// }
+/// ```
+///
+/// The attribute can be applied to any function without arguments that returns a [`Result`], but
+/// it's only useful on `main`: other functions might as well be `#[iex]`.
+pub use iex_derive::main;
+
 /// Try block.
 ///
 /// This is an implementation of the [nightly `try` blocks][1] for [`#[iex]`](macro@crate::iex).
@@ -230,5 +578,350 @@ pub use iex_derive::iex;
 /// }
 /// ```
 ///
+/// # Early success
+///
+/// The trailing expression of the block is the output; it's not wrapped in `Ok`. To exit the
+/// block with an output early, label the block and `break` out of it:
+///
+/// ```
+/// use iex::{iex, Outcome, try_block};
+///
+/// #[iex]
+/// fn check(x: i32) -> Result<i32, String> {
+///     if x < 0 { Err(format!("{x} is negative")) } else { Ok(x) }
+/// }
+///
+/// fn first_large(xs: &[i32]) -> Result<i32, String> {
+///     try_block!('blk: {
+///         for &x in xs {
+///             if check(x)? > 10 {
+///                 break 'blk x;
+///             }
+///         }
+///         0
+///     })
+///     .into_result()
+/// }
+///
+/// assert_eq!(first_large(&[1, 20, -1]), Ok(20));
+/// assert_eq!(first_large(&[1, -1, 20]), Err("-1 is negative".to_string()));
+/// assert_eq!(first_large(&[1, 2, 3]), Ok(0));
+/// ```
+///
+/// `?` always propagates the error out of the whole `try_block!`, regardless of labels, and
+/// `break 'blk value` always succeeds with `value`. A `break` is only reached if no `?` before it
+/// failed.
+///
+/// The body is compiled as a closure, so `return value` also exits the try block with `value`
+/// rather than returning from the enclosing function. Prefer the labeled form, which says what it
+/// does.
+///
 /// [1]: https://doc.rust-lang.org/nightly/unstable-book/language-features/try-blocks.html
 pub use iex_derive::try_block;
+
+/// Propagate an error with additional context.
+///
+/// `ctx!(outcome, context)` is a shorthand for
+/// [`outcome.context(context)?`](crate::Context::context) that can only be used inside
+/// [`#[iex]`](macro@crate::iex) functions and [`try_block!`](crate::try_block). The context is
+/// evaluated eagerly; use `outcome.with_context(|| context)?` if it is expensive to construct.
+///
+/// For `#[iex]` to recognize the macro, it must be invoked as `ctx!` or `iex::ctx!`.
+///
+/// # Example
+///
+/// ```
+/// use anyhow::{bail, Result};
+/// use iex::{ctx, iex, Outcome};
+///
+/// #[iex]
+/// fn read_header() -> Result<u8> {
+///     bail!("Unexpected EOF");
+/// }
+///
+/// #[iex]
+/// fn read_file() -> Result<u8> {
+///     Ok(ctx!(read_header(), "While reading header"))
+/// }
+///
+/// assert_eq!(
+///     format!("{:#}", read_file().into_result().unwrap_err()),
+///     "While reading header: Unexpected EOF",
+/// );
+/// ```
+#[cfg(feature = "anyhow")]
+pub use iex_derive::ctx;
+
+/// Call the `Result`-returning twin of an [`#[iex(also_result)]`](macro@crate::iex) function.
+///
+/// `as_result!(foo(args))` expands to `foo_as_result(args)`, and `as_result!(x.foo(args))` to
+/// `x.foo_as_result(args)`. This switches a single call to the branchful implementation without
+/// editing the callee, which is handy for measuring whether `#[iex]` pays off at a particular call
+/// site. Reverting is a matter of removing the macro.
+///
+/// The callee must be annotated with `#[iex(also_result)]`; otherwise, the twin doesn't exist, and
+/// the compiler reports that `foo_as_result` can't be found. Only the outermost call is switched;
+/// the twin still calls `#[iex]` functions, if any, through
+/// [`into_result`](crate::Outcome::into_result).
+///
+/// # Example
+///
+/// ```
+/// use iex::{as_result, iex, Outcome};
+///
+/// #[iex(also_result)]
+/// fn checked_divide(a: u32, b: u32) -> Result<u32, &'static str> {
+///     a.checked_div(b).ok_or("Cannot divide by zero")
+/// }
+///
+/// #[iex]
+/// fn average(sum: u32, count: u32) -> Result<u32, &'static str> {
+///     // Suspected to be slow with exceptions, so use the twin
+///     Ok(as_result!(checked_divide(sum, count))?)
+/// }
+///
+/// assert_eq!(average(6, 2).into_result(), Ok(3));
+/// assert_eq!(as_result!(checked_divide(6, 0)), Err("Cannot divide by zero"));
+/// ```
+pub use iex_derive::as_result;
+
+/// Get the output of an outcome or return its error from the enclosing function.
+///
+/// This is a bridge for functions that can't be [`#[iex]`](macro@crate::iex) themselves, but call
+/// `#[iex]` functions. `unwrap_or_return!(outcome)` casts the outcome to a [`Result`] via
+/// [`into_result`](crate::Outcome::into_result) and either evaluates to the output or returns
+/// `Err(..)`, converting the error via [`From`] like `?` does. `unwrap_or_return!(outcome, f)`
+/// maps the error with `f` instead.
+///
+/// # Example
+///
+/// ```
+/// use iex::{iex, unwrap_or_return};
+///
+/// #[iex]
+/// fn parse(s: &str) -> Result<u8, std::num::ParseIntError> {
+///     s.parse()
+/// }
+///
+/// fn sum(a: &str, b: &str) -> Result<u8, String> {
+///     let a = unwrap_or_return!(parse(a), |err| format!("Invalid lhs: {err}"));
+///     let b = unwrap_or_return!(parse(b), |err| format!("Invalid rhs: {err}"));
+///     a.checked_add(b).ok_or_else(|| "Overflow".to_string())
+/// }
+///
+/// assert_eq!(sum("1", "2"), Ok(3));
+/// assert_eq!(sum("1", "x"), Err("Invalid rhs: invalid digit found in string".to_string()));
+/// ```
+#[macro_export]
+macro_rules! unwrap_or_return {
+    ($outcome:expr $(,)?) => {
+        match $crate::Outcome::into_result($outcome) {
+            ::core::result::Result::Ok(value) => value,
+            ::core::result::Result::Err(err) => {
+                return ::core::result::Result::Err(::core::convert::From::from(err));
+            }
+        }
+    };
+    ($outcome:expr, $map_err:expr $(,)?) => {
+        match $crate::Outcome::into_result($outcome) {
+            ::core::result::Result::Ok(value) => value,
+            ::core::result::Result::Err(err) => {
+                return ::core::result::Result::Err(($map_err)(err));
+            }
+        }
+    };
+}
+
+/// Return an error from the enclosing function, converting it via [`From`].
+///
+/// `bail!(err)` is a shorthand for `return Err(err.into())`, like `anyhow::bail!`, but for any
+/// error type. In [`#[iex]`](macro@crate::iex) functions, it throws the error directly, so it
+/// works even if the function returns an outcome in another branch, where `return Err(..)`
+/// wouldn't type-check. `anyhow::bail!` is treated the same way.
+///
+/// For `#[iex]` to recognize the macro, it must be invoked as `bail!`, `iex::bail!` or
+/// `anyhow::bail!`. Like other macros, the arguments can't use `?` on outcomes.
+///
+/// A `bail!` that ends the function body is left as `return Err(..)`, because that's how the
+/// compiler learns the return type of a body that never completes normally. If such a body ends
+/// with an `if` or a `match` instead, e.g. with `bail!` in every arm, the compiler reports
+/// ``the trait bound `(): Outcome` is not satisfied``. Move one of the `bail!`s to the end.
+///
+/// # Example
+///
+/// ```
+/// use iex::{bail, iex, Outcome};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Error {
+///     Empty,
+///     Invalid(String),
+/// }
+///
+/// impl From<String> for Error {
+///     fn from(s: String) -> Self {
+///         Self::Invalid(s)
+///     }
+/// }
+///
+/// #[iex]
+/// fn parse(s: &str) -> Result<i32, Error> {
+///     Ok(s.parse::<i32>().map_err(|_| format!("Invalid number: {s}"))?)
+/// }
+///
+/// #[iex]
+/// fn parse_non_empty(s: &str) -> Result<i32, Error> {
+///     if s.is_empty() {
+///         bail!(Error::Empty);
+///     }
+///     parse(s)
+/// }
+///
+/// assert_eq!(parse_non_empty("1").into_result(), Ok(1));
+/// assert_eq!(parse_non_empty("").into_result(), Err(Error::Empty));
+/// ```
+#[macro_export]
+macro_rules! bail {
+    ($err:expr $(,)?) => {
+        return ::core::result::Result::Err(::core::convert::From::from($err))
+    };
+}
+
+/// Return an error from the enclosing function if a condition doesn't hold.
+///
+/// `ensure!(cond, err)` is a shorthand for `if !cond { bail!(err) }`. See
+/// [`bail!`](crate::bail) for how it interacts with [`#[iex]`](macro@crate::iex); `anyhow::ensure!`
+/// is treated the same way.
+///
+/// As with a plain `if`, `err` is only evaluated, and the variables it uses are only moved, if the
+/// condition doesn't hold.
+///
+/// # Example
+///
+/// ```
+/// use iex::{ensure, iex, Outcome};
+///
+/// #[iex]
+/// fn checked_div(a: i32, b: i32) -> Result<i32, String> {
+///     ensure!(b != 0, format!("Cannot divide {a} by zero"));
+///     Ok(a / b)
+/// }
+///
+/// assert_eq!(checked_div(6, 3).into_result(), Ok(2));
+/// assert_eq!(checked_div(1, 0).into_result(), Err("Cannot divide 1 by zero".to_string()));
+/// ```
+#[macro_export]
+macro_rules! ensure {
+    ($cond:expr, $err:expr $(,)?) => {
+        if !$cond {
+            $crate::bail!($err);
+        }
+    };
+}
+
+/// Drive several outcomes in order, short-circuiting on the first error.
+///
+/// `join!(a, b, c)` evaluates the expressions `a`, `b`, and `c` immediately, like a tuple would,
+/// and returns an outcome with output `(A, B, C)`. When that outcome is propagated or cast to a
+/// [`Result`], the outcomes are driven left to right; if one of them fails, its error is
+/// propagated, and the outcomes to its right are dropped without being driven. All outcomes must
+/// have the same error type.
+///
+/// This is the analogue of `tokio::join!` for outcomes. Unlike collecting into a tuple of
+/// [`Result`]s, the successful path doesn't materialize any intermediate [`Result`]s. Use
+/// [`try_join_all!`](crate::try_join_all) to drive all outcomes and collect every error.
+///
+/// Between 2 and 12 outcomes are supported.
+///
+/// # Example
+///
+/// ```
+/// use iex::{iex, join, Outcome};
+///
+/// #[iex]
+/// fn parse(s: &str) -> Result<u8, std::num::ParseIntError> {
+///     s.parse()
+/// }
+///
+/// #[iex]
+/// fn parse_pair(a: &str, b: &str) -> Result<u16, std::num::ParseIntError> {
+///     let (a, b) = join!(parse(a), parse(b))?;
+///     Ok(a as u16 * b as u16)
+/// }
+///
+/// assert_eq!(parse_pair("12", "34").into_result(), Ok(408));
+/// assert!(parse_pair("12", "x").into_result().is_err());
+/// ```
+#[macro_export]
+macro_rules! join {
+    ($first:expr, $($rest:expr),+ $(,)?) => {
+        $crate::join!(@bind [] [$first, $($rest,)+] [a b c d e f g h i j k l])
+    };
+    (@bind [$(($bound:ident $bound_outcome:expr))*] [$outcome:expr, $($rest:expr,)*] [$name:ident $($names:ident)*]) => {
+        $crate::join!(@bind [$(($bound $bound_outcome))* ($name $outcome)] [$($rest,)*] [$($names)*])
+    };
+    (@bind [$(($name:ident $outcome:expr))*] [] [$($unused:ident)*]) => {
+        match ($($outcome,)*) {
+            ($($name,)*) => $crate::imp::IexResult(
+                move |marker: $crate::imp::Marker<_>| {
+                    ($($crate::Outcome::get_value_or_panic($name, marker),)*)
+                },
+                ::core::marker::PhantomData,
+            ),
+        }
+    };
+}
+
+/// Drive several outcomes in order, collecting all errors.
+///
+/// This is a variant of [`join!`](crate::join) that drives every outcome, even after one of them
+/// fails. If all outcomes succeed, the output is a tuple of their outputs; otherwise, the error is
+/// a [`Vec`] of the errors, in the order of the arguments.
+///
+/// Between 2 and 12 outcomes are supported.
+///
+/// # Example
+///
+/// ```
+/// use iex::{iex, try_join_all, Outcome};
+///
+/// #[iex]
+/// fn parse(s: &str) -> Result<u8, String> {
+///     s.parse().map_err(|_| format!("invalid number {s:?}"))
+/// }
+///
+/// assert_eq!(try_join_all!(parse("1"), parse("2")).into_result(), Ok((1, 2)));
+/// assert_eq!(
+///     try_join_all!(parse("x"), parse("2"), parse("y")).into_result(),
+///     Err(vec![r#"invalid number "x""#.to_string(), r#"invalid number "y""#.to_string()]),
+/// );
+/// ```
+#[macro_export]
+macro_rules! try_join_all {
+    ($first:expr, $($rest:expr),+ $(,)?) => {
+        $crate::try_join_all!(@bind [] [$first, $($rest,)+] [a b c d e f g h i j k l])
+    };
+    (@bind [$(($bound:ident $bound_outcome:expr))*] [$outcome:expr, $($rest:expr,)*] [$name:ident $($names:ident)*]) => {
+        $crate::try_join_all!(@bind [$(($bound $bound_outcome))* ($name $outcome)] [$($rest,)*] [$($names)*])
+    };
+    (@bind [$(($name:ident $outcome:expr))*] [] [$($unused:ident)*]) => {
+        match ($($outcome,)*) {
+            ($($name,)*) => $crate::imp::IexResult(
+                move |marker: $crate::imp::Marker<::std::vec::Vec<_>>| {
+                    let mut errors = ::std::vec::Vec::new();
+                    let ($($name,)*) = ($(
+                        $crate::Outcome::into_result($name).map_err(|err| errors.push(err)).ok(),
+                    )*);
+                    match ($($name,)*) {
+                        ($(::core::option::Option::Some($name),)*) => ($($name,)*),
+                        _ => $crate::Outcome::get_value_or_panic(
+                            ::core::result::Result::Err(errors),
+                            marker,
+                        ),
+                    }
+                },
+                ::core::marker::PhantomData,
+            ),
+        }
+    };
+}
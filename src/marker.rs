@@ -1,3 +1,5 @@
+use crate::Outcome;
+use std::convert::Infallible;
 use std::marker::PhantomData;
 
 pub struct Marker<E>(PhantomData<E>);
@@ -6,6 +8,19 @@ impl<E> Marker<E> {
     pub(crate) unsafe fn new() -> Self {
         Self(PhantomData)
     }
+
+    // `bail!(..)` inside #[iex] functions is rewritten to `marker.bail(|| bail!(..))`, and
+    // `ensure!(..)` to `marker.ensure(|| { ensure!(..); Ok(()) })`, so that the error is thrown
+    // regardless of the type of the function's tail.
+    #[cold]
+    pub fn bail(self, f: impl FnOnce() -> Result<Infallible, E>) -> ! {
+        match f().get_value_or_panic(self) {}
+    }
+
+    #[inline(always)]
+    pub fn ensure(self, f: impl FnOnce() -> Result<(), E>) {
+        f().get_value_or_panic(self)
+    }
 }
 
 impl<E> Clone for Marker<E> {
@@ -0,0 +1,67 @@
+use crate::{
+    imp::{IexResult, Marker},
+    Outcome,
+};
+use std::marker::PhantomData;
+
+/// Conversion of [`Option`]s to outcomes.
+///
+/// `option.ok_or(err)?` builds a [`Result`] only to take it apart again. The methods of this trait
+/// return outcomes that yield the contained value directly and throw the error on `None`, like
+/// [`#[iex]`](macro@crate::iex) functions do.
+///
+/// # Example
+///
+/// ```
+/// use iex::{iex, OptionIexExt, Outcome};
+/// use std::collections::HashMap;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum ConfigError {
+///     Missing(&'static str),
+///     Invalid(String),
+/// }
+///
+/// #[iex]
+/// fn port(config: &HashMap<&str, &str>) -> Result<u16, ConfigError> {
+///     let port = config.get("port").ok_or_iex(ConfigError::Missing("port"))?;
+///     port.parse().map_err(|_| ConfigError::Invalid(port.to_string()))
+/// }
+///
+/// assert_eq!(port(&HashMap::from([("port", "80")])).into_result(), Ok(80));
+/// assert_eq!(port(&HashMap::new()).into_result(), Err(ConfigError::Missing("port")));
+/// ```
+pub trait OptionIexExt<T> {
+    /// Yield the contained value, or throw `err` if `None`.
+    ///
+    /// This is [`Option::ok_or`] for outcomes. `err` is evaluated eagerly; use
+    /// [`ok_or_else_iex`](Self::ok_or_else_iex) if it is expensive to construct.
+    fn ok_or_iex<E>(self, err: E) -> impl Outcome<Output = T, Error = E>;
+
+    /// Yield the contained value, or throw the error returned by `f` if `None`.
+    ///
+    /// This is [`Option::ok_or_else`] for outcomes. `f` is only called if the option is `None`,
+    /// and may call `#[iex]` functions.
+    fn ok_or_else_iex<E, F>(self, f: F) -> impl Outcome<Output = T, Error = E>
+    where
+        F: FnOnce() -> E;
+}
+
+impl<T> OptionIexExt<T> for Option<T> {
+    fn ok_or_iex<E>(self, err: E) -> impl Outcome<Output = T, Error = E> {
+        self.ok_or_else_iex(move || err)
+    }
+
+    fn ok_or_else_iex<E, F>(self, f: F) -> impl Outcome<Output = T, Error = E>
+    where
+        F: FnOnce() -> E,
+    {
+        IexResult(
+            move |marker: Marker<E>| match self {
+                Some(value) => value,
+                None => Err(f()).get_value_or_panic(marker),
+            },
+            PhantomData,
+        )
+    }
+}
@@ -1,5 +1,13 @@
-use crate::{iex, imp::Marker};
+use crate::{
+    iex,
+    iex_result::catch_or_else,
+    imp::{ExceptionMapper, ExceptionRefMapper, Marker},
+    CancellationToken, Cancelled, ChainedError, IexResult, PollableOutcome, SetLocation,
+};
+use std::marker::PhantomData;
 
+// Not reachable from outside the crate, so `Outcome` cannot be implemented by users. See the
+// "Sealed" section in the documentation of `Outcome` for the reasoning.
 pub trait Sealed {}
 
 /// Properties of a generalized result type.
@@ -81,7 +89,138 @@ pub trait Sealed {}
 ///     }
 /// }
 /// ```
-#[must_use]
+///
+/// # Naming
+///
+/// Methods that behave like the methods of [`Result`] have the same names, so that chains like
+/// this one read the same with and without `#[iex]`:
+///
+/// ```
+/// use iex::{iex, Outcome};
+/// use std::cell::RefCell;
+/// use std::num::ParseIntError;
+///
+/// fn parse_result(s: &str) -> Result<i32, ParseIntError> {
+///     s.parse()
+/// }
+///
+/// #[iex]
+/// fn parse_iex(s: &str) -> Result<i32, ParseIntError> {
+///     s.parse()
+/// }
+///
+/// fn with_result(s: &str, log: &RefCell<Vec<String>>) -> Result<i32, String> {
+///     parse_result(s)
+///         .inspect(|x| log.borrow_mut().push(format!("parsed {x}")))
+///         .inspect_err(|err| log.borrow_mut().push(format!("failed: {err}")))
+///         .map_err(|err| err.to_string())
+/// }
+///
+/// #[iex]
+/// fn with_iex(s: &str, log: &RefCell<Vec<String>>) -> Result<i32, String> {
+///     parse_iex(s)
+///         .inspect(|x| log.borrow_mut().push(format!("parsed {x}")))
+///         .inspect_err(|err| log.borrow_mut().push(format!("failed: {err}")))
+///         .map_err(|err| err.to_string())
+/// }
+///
+/// let (log_result, log_iex) = (RefCell::new(Vec::new()), RefCell::new(Vec::new()));
+/// for s in ["1", "x"] {
+///     assert_eq!(with_result(s, &log_result), with_iex(s, &log_iex).into_result());
+/// }
+/// assert_eq!(log_result, log_iex);
+/// ```
+///
+/// The closures can't capture the same variable by mutable reference, though, because an outcome
+/// is lazy and keeps all of them alive at once; see [Ownership](#ownership).
+///
+/// Methods whose semantics differ from the [`Result`] methods have different names, with a
+/// `#[doc(alias)]` for the closest [`Result`] method, so searching the documentation for e.g.
+/// `and_then` finds [`try_map`](Self::try_map).
+///
+/// # `Result`
+///
+/// [`Result`] is the reference implementation of this trait, and the one every `#[iex]` function
+/// bottoms out in: an `#[iex]` function is a [`Result`]-returning body whose `?` and tail
+/// expression go through the impl for [`Result`].
+///
+/// - [`into_result`](Self::into_result) returns the [`Result`] unchanged, without touching the
+///   exception state.
+/// - When an `Ok(value)` is propagated with `?` inside `#[iex]`, `?` evaluates to `value`. No
+///   exception state is read or written.
+/// - When an `Err(error)` is propagated, `error` is first moved to the exception slot of the
+///   current thread (or to the [`exception_store`](crate::exception_store), if enabled), and then
+///   the thread starts unwinding with a private payload. The unwinding is stopped, and the slot is
+///   emptied, by the closest enclosing [`into_result`](Self::into_result) or a method that
+///   recovers from errors. Destructors run during the unwinding observe the slot as occupied.
+///
+/// The slot can be inspected in tests with the [`testing`](crate::testing) module.
+///
+/// # Sealed
+///
+/// This trait is sealed: it is implemented for [`Result`] and `#[iex] Result` only, and cannot be
+/// implemented outside this crate. Propagating errors relies on a protocol between the producer
+/// and the consumer of the error that is easy to get wrong, and a single incorrect implementation
+/// could cause undefined behavior in otherwise safe code.
+///
+/// ```compile_fail
+/// use iex::{imp::Marker, Outcome};
+/// use std::fmt::Error;
+///
+/// struct MyOutcome;
+/// # #[cfg(not(feature = "anyhow"))]
+/// # impl iex::Context<(), Error> for MyOutcome {}
+/// # #[cfg(feature = "anyhow")]
+/// # impl iex::Context<(), Error> for MyOutcome {
+/// #     type ContextOutcome<C> = Result<(), anyhow::Error>
+/// #     where
+/// #         C: std::fmt::Display + Send + Sync + 'static;
+/// #     type WithContextOutcome<C, F> = Result<(), anyhow::Error>
+/// #     where
+/// #         C: std::fmt::Display + Send + Sync + 'static,
+/// #         F: FnOnce() -> C;
+/// #     fn context<C>(self, _context: C) -> Self::ContextOutcome<C>
+/// #     where
+/// #         C: std::fmt::Display + Send + Sync + 'static,
+/// #     {
+/// #         Ok(())
+/// #     }
+/// #     fn with_context<C, F>(self, _f: F) -> Self::WithContextOutcome<C, F>
+/// #     where
+/// #         C: std::fmt::Display + Send + Sync + 'static,
+/// #         F: FnOnce() -> C,
+/// #     {
+/// #         Ok(())
+/// #     }
+/// # }
+///
+/// // the trait bound `MyOutcome: Sealed` is not satisfied
+/// impl Outcome for MyOutcome {
+///     type Output = ();
+///     type Error = Error;
+///
+///     fn get_value_or_panic(self, _marker: Marker<Error>) {}
+///
+///     fn inspect_err<F: FnOnce(&Error)>(self, _f: F) -> impl Outcome<Output = (), Error = Error> {
+///         Ok(())
+///     }
+///
+///     fn map_err<F, O: FnOnce(Error) -> F>(self, _op: O) -> impl Outcome<Output = (), Error = F> {
+///         Ok(())
+///     }
+///
+///     fn into_result(self) -> Result<(), Error> {
+///         Ok(())
+///     }
+/// }
+/// ```
+///
+/// To produce a custom outcome, use one of the blessed paths instead:
+/// - Write an [`#[iex]`](macro@crate::iex) function or closure,
+/// - Wrap a block in [`try_block!`](crate::try_block),
+/// - Wrap a closure returning [`Result`] in [`from_fn`].
+#[must_use = "outcomes are lazy and do nothing unless propagated with `?` or resolved with \
+              `.into_result()`"]
 pub trait Outcome: Sealed + crate::Context<Self::Output, Self::Error> {
     /// The type of the success value.
     type Output;
@@ -89,6 +228,19 @@ pub trait Outcome: Sealed + crate::Context<Self::Output, Self::Error> {
     /// The type of the error value.
     type Error;
 
+    // The primitive all propagation is built on. Every implementation must uphold this contract:
+    //
+    // - `marker` witnesses that a frame catching errors of type `Self::Error` is active on the
+    //   current thread, i.e. that whoever catches the unwind reads exactly this type from
+    //   `EXCEPTION`. Markers are only created by `Marker::new()`, which is unsafe for this reason,
+    //   and are passed down unchanged or replaced by `ExceptionMapper` when the error type changes.
+    // - On success, the value is returned, and `EXCEPTION` is left untouched.
+    // - On failure, an error of type `Self::Error` is written to `EXCEPTION`, and the function
+    //   unwinds with `IexPanic`. Nothing else may be written to the slot in between, and the error
+    //   must not be written without unwinding, because no one would read it.
+    // - Any other panic is propagated as is, without writing to `EXCEPTION`.
+    //
+    // In debug builds, `into_result` asserts that an `IexPanic` is accompanied by an error.
     #[doc(hidden)]
     fn get_value_or_panic(self, marker: Marker<Self::Error>) -> Self::Output;
 
@@ -143,6 +295,259 @@ pub trait Outcome: Sealed + crate::Context<Self::Output, Self::Error> {
     where
         O: FnOnce(Self::Error) -> F;
 
+    /// Apply a function to a reference to the `Err` value, leaving `Ok` untouched.
+    ///
+    /// This is [`map_err`](Self::map_err) for conversions that only need part of the error, e.g.
+    /// an error code of a large error struct. `f` borrows the error where it is stored, so large
+    /// errors, which are boxed while they propagate, are never moved. The original error is dropped
+    /// after the new error is stored. Both `f` and the destructor of the error may call `#[iex]`
+    /// functions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, Outcome};
+    ///
+    /// struct ParseError {
+    ///     line: usize,
+    ///     source: String,
+    /// }
+    ///
+    /// #[iex]
+    /// fn parse(source: &str) -> Result<u32, ParseError> {
+    ///     source.trim().parse().map_err(|_| ParseError {
+    ///         line: 1,
+    ///         source: source.to_string(),
+    ///     })
+    /// }
+    ///
+    /// #[iex]
+    /// fn parse_line(source: &str) -> Result<u32, usize> {
+    ///     parse(source).map_err_ref(|err| err.line)
+    /// }
+    ///
+    /// assert_eq!(parse_line("12").into_result(), Ok(12));
+    /// assert_eq!(parse_line("x").into_result(), Err(1));
+    /// ```
+    #[iex]
+    fn map_err_ref<E2, F>(self, f: F) -> Result<Self::Output, E2>
+    where
+        Self: Sized,
+        F: FnOnce(&Self::Error) -> E2,
+    {
+        crate::imp::IexResult(
+            move |marker| {
+                let exception_mapper = ExceptionRefMapper::new(marker, f);
+                let output = self.get_value_or_panic(exception_mapper.get_in_marker());
+                exception_mapper.swallow();
+                output
+            },
+            PhantomData,
+        )
+    }
+
+    /// Calls a function with a reference to the contained value if `Ok`.
+    ///
+    /// Returns the original result.
+    ///
+    /// This is a generalized version of [`Result::inspect`].
+    #[doc(alias = "inspect_ok")]
+    #[iex]
+    fn inspect<F>(self, f: F) -> Result<Self::Output, Self::Error>
+    where
+        Self: Sized,
+        F: FnOnce(&Self::Output),
+    {
+        let value = self?;
+        f(&value);
+        Ok(value)
+    }
+
+    /// Wrap the `Err` value in a new error, keeping the original error as its source.
+    ///
+    /// `f` is only called if the outcome is `Err`. This is useful for building error chains, in
+    /// the style of `error-chain`, without giving up on `#[iex]`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, ChainedError, Outcome};
+    /// use std::num::ParseIntError;
+    ///
+    /// #[iex]
+    /// fn parse_port(s: &str) -> Result<u16, ParseIntError> {
+    ///     s.parse()
+    /// }
+    ///
+    /// #[iex]
+    /// fn parse_address(s: &str) -> Result<u16, ChainedError<String, ParseIntError>> {
+    ///     parse_port(s).chain_err(move || format!("Invalid port {s:?}"))
+    /// }
+    ///
+    /// let err = parse_address("http").into_result().unwrap_err();
+    /// assert_eq!(
+    ///     format!("{err:#}"),
+    ///     "Invalid port \"http\": invalid digit found in string",
+    /// );
+    /// ```
+    #[iex]
+    fn chain_err<E2, F>(self, f: F) -> Result<Self::Output, ChainedError<E2, Self::Error>>
+    where
+        Self: Sized,
+        F: FnOnce() -> E2,
+    {
+        self.map_err(|source| ChainedError { error: f(), source })
+    }
+
+    /// Convert the `Err` value to an intermediate error type.
+    ///
+    /// `?` converts errors with a single [`Into`], just like the built-in `?`, so an error of type
+    /// `A` is not propagated into a function returning `C` if only `A: Into<B>` and `B: Into<C>`
+    /// exist. `outcome.rethrow_via::<B>()?` performs the first hop explicitly and leaves the
+    /// second one to `?`. Longer chains can be built by calling `rethrow_via` several times.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, Outcome};
+    ///
+    /// struct IoError;
+    ///
+    /// struct StorageError(IoError);
+    ///
+    /// impl From<IoError> for StorageError {
+    ///     fn from(err: IoError) -> Self {
+    ///         Self(err)
+    ///     }
+    /// }
+    ///
+    /// struct AppError(StorageError);
+    ///
+    /// impl From<StorageError> for AppError {
+    ///     fn from(err: StorageError) -> Self {
+    ///         Self(err)
+    ///     }
+    /// }
+    ///
+    /// #[iex]
+    /// fn read() -> Result<Vec<u8>, IoError> {
+    ///     Err(IoError)
+    /// }
+    ///
+    /// #[iex]
+    /// fn load() -> Result<Vec<u8>, AppError> {
+    ///     Ok(read().rethrow_via::<StorageError>()?)
+    /// }
+    ///
+    /// assert!(matches!(load().into_result(), Err(AppError(StorageError(IoError)))));
+    /// ```
+    #[iex]
+    fn rethrow_via<E2>(self) -> Result<Self::Output, E2>
+    where
+        Self: Sized,
+        Self::Error: Into<E2>,
+    {
+        self.map_err(Into::into)
+    }
+
+    /// Record the location of this call in the `Err` value.
+    ///
+    /// If the outcome fails, [`SetLocation::set_location`] is called on the error with the
+    /// location of the `with_location` call, so `f().with_location()?` stamps the location of
+    /// the `?`. Nothing is done on the success path, except for keeping the location around.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, Outcome, SetLocation};
+    /// use std::panic::Location;
+    ///
+    /// #[derive(Debug)]
+    /// struct Error {
+    ///     message: String,
+    ///     location: Option<&'static Location<'static>>,
+    /// }
+    ///
+    /// impl SetLocation for Error {
+    ///     fn set_location(&mut self, location: &'static Location<'static>) {
+    ///         // Keep the innermost location
+    ///         self.location.get_or_insert(location);
+    ///     }
+    /// }
+    ///
+    /// #[iex]
+    /// fn fails() -> Result<(), Error> {
+    ///     Err(Error { message: "Oops".to_string(), location: None })
+    /// }
+    ///
+    /// #[iex]
+    /// fn calls() -> Result<(), Error> {
+    ///     fails().with_location()?;
+    ///     Ok(())
+    /// }
+    ///
+    /// let err = calls().into_result().unwrap_err();
+    /// // Prints "Oops at", followed by the location of `fails().with_location()?`
+    /// println!("{} at {}", err.message, err.location.unwrap());
+    /// assert_eq!(err.location.unwrap().file(), file!());
+    /// ```
+    #[track_caller]
+    fn with_location(self) -> impl Outcome<Output = Self::Output, Error = Self::Error>
+    where
+        Self: Sized,
+        Self::Error: SetLocation,
+    {
+        let location = std::panic::Location::caller();
+        self.map_err(move |mut err| {
+            err.set_location(location);
+            err
+        })
+    }
+
+    /// Call a function with a reference to the `Err` value, discarding its return value.
+    ///
+    /// This is [`inspect_err`](Outcome::inspect_err) for closures that return something, e.g.
+    /// [`HashSet::insert`](std::collections::HashSet::insert). The intended use is collecting
+    /// statistics about errors in a pipeline that still propagates them: the error is only
+    /// borrowed, and propagates unchanged after `f` returns.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, Outcome};
+    /// use std::collections::HashSet;
+    /// use std::num::ParseIntError;
+    ///
+    /// #[iex]
+    /// fn parse(s: &str) -> Result<u8, ParseIntError> {
+    ///     s.parse()
+    /// }
+    ///
+    /// #[iex]
+    /// fn parse_all(input: &[&str], seen: &mut HashSet<String>) -> Result<Vec<u8>, ParseIntError> {
+    ///     let mut values = Vec::new();
+    ///     for s in input {
+    ///         values.push(parse(s).peek_err(|err| seen.insert(err.to_string()))?);
+    ///     }
+    ///     Ok(values)
+    /// }
+    ///
+    /// let mut seen = HashSet::new();
+    /// assert!(parse_all(&["1", "x"], &mut seen).into_result().is_err());
+    /// assert!(parse_all(&["y"], &mut seen).into_result().is_err());
+    /// assert_eq!(seen.len(), 1);
+    /// ```
+    #[iex]
+    fn peek_err<R, F>(self, f: F) -> Result<Self::Output, Self::Error>
+    where
+        Self: Sized,
+        F: FnOnce(&Self::Error) -> R,
+    {
+        self.inspect_err(|err| {
+            f(err);
+        })
+    }
+
     /// Cast a generic result to a [`Result`].
     ///
     /// The [`Result`] can then be matched on, returned from a function that doesn't use
@@ -176,5 +581,1372 @@ pub trait Outcome: Sealed + crate::Context<Self::Output, Self::Error> {
     /// ```
     ///
     /// despite repetitions.
+    ///
+    /// If the error type is [`Infallible`](std::convert::Infallible), no error can be thrown, so
+    /// this method never reads the error slot, and the happy path is as cheap as calling the
+    /// function directly.
     fn into_result(self) -> Result<Self::Output, Self::Error>;
+
+    /// Cast a generic result to a [`Result`] before crossing a thread boundary.
+    ///
+    /// This is exactly [`into_result`](Self::into_result), named for the place where it is
+    /// required. Errors propagate by unwinding the stack of the thread that drives the outcome,
+    /// and unwinding cannot cross threads. A closure passed to `std::thread::spawn`,
+    /// `tokio::task::spawn_blocking`, `rayon::spawn` and the like has to return an ordinary
+    /// value, so the outcome must be resolved inside the closure, on the worker thread:
+    ///
+    /// ```
+    /// use iex::{iex, Outcome};
+    ///
+    /// #[iex]
+    /// fn parse(s: &str) -> Result<i32, String> {
+    ///     s.parse().map_err(|_| format!("Invalid number: {s}"))
+    /// }
+    ///
+    /// let handle = std::thread::spawn(|| parse("x").resolve());
+    /// assert_eq!(handle.join().unwrap(), Err("Invalid number: x".to_string()));
+    /// ```
+    ///
+    /// An outcome that is [`Send`] may also be moved to another thread unresolved and resolved
+    /// there; it is lazy, so it runs on whichever thread resolves it.
+    fn resolve(self) -> Result<Self::Output, Self::Error>
+    where
+        Self: Sized,
+    {
+        self.into_result()
+    }
+
+    /// Cast a generic result to a [`Result`], along with the location where the error was last
+    /// propagated.
+    ///
+    /// The location is that of the outermost `?` the error passed through before reaching this
+    /// call, i.e. the closest one to where the error is handled. Directly returning an outcome from
+    /// an `#[iex]` function does not involve `?`, so tail calls are skipped over: if the outermost
+    /// functions forward the outcome directly, the location is that of the last `?` below them. If
+    /// the error did not pass through any `?`, or the outcome succeeded, the location is `None`.
+    ///
+    /// Available with the `location` feature only. Without it, `?` does not track locations and has
+    /// no overhead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, Outcome};
+    ///
+    /// #[iex]
+    /// fn fails() -> Result<(), &'static str> {
+    ///     Err("Oops")
+    /// }
+    ///
+    /// #[iex]
+    /// fn propagates() -> Result<(), &'static str> {
+    ///     fails()?;
+    ///     Ok(())
+    /// }
+    ///
+    /// let (result, location) = propagates().into_result_located();
+    /// assert_eq!(result, Err("Oops"));
+    /// assert_eq!(location.unwrap().file(), file!());
+    /// ```
+    #[cfg(feature = "location")]
+    fn into_result_located(
+        self,
+    ) -> (
+        Result<Self::Output, Self::Error>,
+        Option<&'static std::panic::Location<'static>>,
+    )
+    where
+        Self: Sized,
+    {
+        crate::location::reset_caught();
+        let result = self.into_result();
+        let location = if result.is_err() {
+            crate::location::take_caught()
+        } else {
+            None
+        };
+        (result, location)
+    }
+
+    /// Cast a generic result to a [`Result`], attaching the location where the error was last
+    /// propagated to the error.
+    ///
+    /// This is [`into_result_located`](Self::into_result_located) with the location stored in a
+    /// [`Located`](crate::Located) wrapper, which displays it after the error.
+    ///
+    /// Available with the `location` feature only.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, Outcome};
+    ///
+    /// #[iex]
+    /// fn fails() -> Result<(), &'static str> {
+    ///     Err("Oops")
+    /// }
+    ///
+    /// #[iex]
+    /// fn propagates() -> Result<(), &'static str> {
+    ///     fails()?;
+    ///     Ok(())
+    /// }
+    ///
+    /// let err = propagates().into_result_with_location().unwrap_err();
+    /// assert_eq!(err.error, "Oops");
+    /// assert!(err.to_string().starts_with(concat!("Oops at ", file!())));
+    /// ```
+    #[cfg(feature = "location")]
+    fn into_result_with_location(self) -> Result<Self::Output, crate::Located<Self::Error>>
+    where
+        Self: Sized,
+    {
+        match self.into_result_located() {
+            (Ok(value), _) => Ok(value),
+            (Err(error), location) => Err(crate::Located { error, location }),
+        }
+    }
+
+    /// Resolve the outcome and format the [`Result`] for debugging.
+    ///
+    /// Outcomes are lazy, so they can't implement [`Debug`](std::fmt::Debug) without running:
+    /// this method consumes the outcome, drives it once, and returns the `{:?}` representation
+    /// of the result, e.g. `Ok(1)` or `Err("Invalid number")`. It's meant to be used while
+    /// debugging, e.g. as `eprintln!("{}", outcome.debug_drive())`.
+    ///
+    /// Available with the `debug` feature only.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, Outcome};
+    ///
+    /// #[iex]
+    /// fn parse(s: &str) -> Result<i32, String> {
+    ///     s.parse().map_err(|_| format!("Invalid number: {s}"))
+    /// }
+    ///
+    /// assert_eq!(parse("1").debug_drive(), "Ok(1)");
+    /// assert_eq!(parse("x").debug_drive(), "Err(\"Invalid number: x\")");
+    /// ```
+    #[cfg(feature = "debug")]
+    fn debug_drive(self) -> String
+    where
+        Self: Sized,
+        Self::Output: std::fmt::Debug,
+        Self::Error: std::fmt::Debug,
+    {
+        format!("{:?}", self.into_result())
+    }
+
+    /// Measure how long the outcome takes to resolve.
+    ///
+    /// `f` is called with the time it took to drive the outcome and whether it succeeded. On
+    /// failure, `f` is called while the error propagates through this outcome, so the duration
+    /// includes the time spent throwing the error up to this point, and the error is propagated
+    /// afterwards. This allows attributing time to the happy path and to error handling separately.
+    ///
+    /// Available with the `metrics` feature only.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, Outcome};
+    /// use std::time::Duration;
+    ///
+    /// #[iex]
+    /// fn parse(s: &str) -> Result<i32, String> {
+    ///     s.parse().map_err(|_| format!("Invalid number: {s}"))
+    /// }
+    ///
+    /// #[iex]
+    /// fn timed_parse(s: &str, timings: &mut Vec<(Duration, bool)>) -> Result<i32, String> {
+    ///     parse(s).inspect_timing(|duration, succeeded| timings.push((duration, succeeded)))
+    /// }
+    ///
+    /// let mut timings = Vec::new();
+    /// assert_eq!(timed_parse("1", &mut timings).into_result(), Ok(1));
+    /// assert!(timed_parse("x", &mut timings).into_result().is_err());
+    /// let succeeded: Vec<bool> = timings.iter().map(|&(_, succeeded)| succeeded).collect();
+    /// assert_eq!(succeeded, [true, false]);
+    /// ```
+    #[cfg(feature = "metrics")]
+    #[iex]
+    fn inspect_timing<F>(self, f: F) -> Result<Self::Output, Self::Error>
+    where
+        Self: Sized,
+        F: FnOnce(std::time::Duration, bool),
+    {
+        let start = std::time::Instant::now();
+        let mut f = Some(f);
+        let value = self.inspect_err(|_| {
+            if let Some(f) = f.take() {
+                f(start.elapsed(), false);
+            }
+        })?;
+        if let Some(f) = f {
+            f(start.elapsed(), true);
+        }
+        Ok(value)
+    }
+
+    /// Wrap the outcome in a [`PollableOutcome`], which can be resolved through `&mut`.
+    fn into_pollable(self) -> PollableOutcome<Self>
+    where
+        Self: Sized,
+    {
+        PollableOutcome::new(self)
+    }
+
+    /// Cast a generic result to a [`Future`](std::future::Future) resolving to a [`Result`].
+    ///
+    /// The outcome is evaluated immediately, just like with [`into_result`](Self::into_result),
+    /// and the returned future is always ready. This doesn't depend on any async runtime and is
+    /// only meant to remove friction when calling synchronous `#[iex]` code from `async` code.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, Outcome};
+    ///
+    /// #[iex]
+    /// fn checked_divide(a: u32, b: u32) -> Result<u32, &'static str> {
+    ///     a.checked_div(b).ok_or("Cannot divide by zero")
+    /// }
+    ///
+    /// async fn divide_async(a: u32, b: u32) -> Result<u32, &'static str> {
+    ///     checked_divide(a, b).into_future().await
+    /// }
+    /// ```
+    #[cfg(feature = "future")]
+    fn into_future(self) -> std::future::Ready<Result<Self::Output, Self::Error>>
+    where
+        Self: Sized,
+    {
+        std::future::ready(self.into_result())
+    }
+
+    /// Returns the error, converting an unexpected success into an error via `f`.
+    ///
+    /// This is the mirror image of recovery: the error is the expected result, and the output is
+    /// the exceptional case. This is useful for "this should have failed" checks and for
+    /// accumulating errors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, Outcome};
+    ///
+    /// #[iex]
+    /// fn parse(s: &str) -> Result<i32, String> {
+    ///     s.parse().map_err(|_| format!("Invalid number: {s}"))
+    /// }
+    ///
+    /// let errors: Vec<String> = ["1", "x", "y"]
+    ///     .into_iter()
+    ///     .map(|s| parse(s).err_or_else(|n| format!("Unexpectedly parsed {n}")))
+    ///     .collect();
+    /// assert_eq!(errors, ["Unexpectedly parsed 1", "Invalid number: x", "Invalid number: y"]);
+    /// ```
+    fn err_or_else<F>(self, f: F) -> Self::Error
+    where
+        Self: Sized,
+        F: FnOnce(Self::Output) -> Self::Error,
+    {
+        self.into_result().map_or_else(|err| err, f)
+    }
+
+    /// Cast a generic result to a [`Result`], catching genuine panics too.
+    ///
+    /// Distinguishes three cases: success (`Ok(Ok(value))`), an error propagated by `#[iex]`
+    /// (`Ok(Err(error))`), and a genuine panic (`Err(payload)`). The panic payload is the same as
+    /// would be returned by [`catch_unwind`](std::panic::catch_unwind), so it can be re-raised
+    /// with [`resume_unwind`](std::panic::resume_unwind). Unlike a manual `catch_unwind` around
+    /// [`into_result`](Self::into_result), this is not tied to the internal unwinding mechanism,
+    /// and is the complete way to catch everything an outcome may throw, e.g. in a task runner
+    /// that logs bugs separately from expected errors.
+    ///
+    /// Like with `catch_unwind`, the panic hook is still invoked for genuine panics, and aborting
+    /// panics cannot be caught. As with [`AssertUnwindSafe`](std::panic::AssertUnwindSafe), it is
+    /// up to the caller not to observe broken invariants after a panic.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, Outcome};
+    ///
+    /// #[iex]
+    /// fn run(task: i32) -> Result<i32, String> {
+    ///     match task {
+    ///         0 => Err("Expected failure".to_string()),
+    ///         1 => panic!("Bug"),
+    ///         _ => Ok(task),
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(run(2).into_result_or_panic().ok(), Some(Ok(2)));
+    /// assert_eq!(run(0).into_result_or_panic().ok(), Some(Err("Expected failure".to_string())));
+    /// let payload = run(1).into_result_or_panic().unwrap_err();
+    /// assert_eq!(payload.downcast_ref::<&str>(), Some(&"Bug"));
+    /// ```
+    fn into_result_or_panic(
+        self,
+    ) -> Result<Result<Self::Output, Self::Error>, Box<dyn std::any::Any + Send>>
+    where
+        Self: Sized,
+    {
+        // into_result catches errors and resumes genuine panics, which are caught here
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| self.into_result()))
+    }
+
+    /// Returns the output, converting an error into an ordinary panic.
+    ///
+    /// The panic payload is the formatted error as a [`String`], like with `panic!("{error}")`,
+    /// so [`catch_unwind`](std::panic::catch_unwind) and panic hooks see a regular panic rather
+    /// than the internal unwinding mechanism of `#[iex]`. This is useful at the top level of a
+    /// task that is supervised by something that catches panics.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the outcome fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, Outcome};
+    ///
+    /// #[iex]
+    /// fn checked_divide(a: u32, b: u32) -> Result<u32, &'static str> {
+    ///     a.checked_div(b).ok_or("Cannot divide by zero")
+    /// }
+    ///
+    /// let payload = std::panic::catch_unwind(|| checked_divide(1, 0).panic_on_err()).unwrap_err();
+    /// assert_eq!(payload.downcast_ref::<String>().unwrap(), "Cannot divide by zero");
+    /// ```
+    #[doc(alias = "unwrap")]
+    #[doc(alias = "expect")]
+    #[track_caller]
+    fn panic_on_err(self) -> Self::Output
+    where
+        Self: Sized,
+        Self::Error: std::fmt::Display,
+    {
+        match self.into_result() {
+            Ok(value) => value,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Returns the output, or logs the error and returns `default`.
+    ///
+    /// With the `log` feature, the error is logged at the error level via
+    /// [`log::error!`](https://docs.rs/log/latest/log/macro.error.html). This is the usual way to
+    /// handle non-fatal errors that should still be noticed, e.g. per-request errors in a server.
+    /// Without the feature, the error is silently dropped, as with [`Result::unwrap_or`].
+    ///
+    /// Nothing is logged or formatted on the success path.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, Outcome};
+    ///
+    /// #[iex]
+    /// fn parse(s: &str) -> Result<i32, String> {
+    ///     s.parse().map_err(|_| format!("Invalid number: {s}"))
+    /// }
+    ///
+    /// assert_eq!(parse("1").unwrap_or_log(0), 1);
+    /// // Logs "Invalid number: x"
+    /// assert_eq!(parse("x").unwrap_or_log(0), 0);
+    /// ```
+    #[doc(alias = "unwrap_or")]
+    fn unwrap_or_log(self, default: Self::Output) -> Self::Output
+    where
+        Self: Sized,
+        Self::Error: std::fmt::Display,
+    {
+        match self.into_result() {
+            Ok(value) => value,
+            #[allow(unused_variables)]
+            Err(err) => {
+                #[cfg(feature = "log")]
+                log::error!("{err}");
+                default
+            }
+        }
+    }
+
+    /// Returns the output, or discards the error and returns a default produced by `f`.
+    ///
+    /// Unlike [`Result::unwrap_or_else`], `f` doesn't receive the error, so it can be a plain
+    /// constructor like `Vec::new`. The default is only produced if the outcome fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, Outcome};
+    ///
+    /// #[iex]
+    /// fn parse_list(s: &str) -> Result<Vec<i32>, String> {
+    ///     s.split(',')
+    ///         .map(|x| x.parse().map_err(|_| format!("Invalid number: {x}")))
+    ///         .collect::<Result<_, _>>()
+    /// }
+    ///
+    /// assert_eq!(parse_list("1,2").unwrap_or_default_with(Vec::new), [1, 2]);
+    /// assert_eq!(parse_list("1,x").unwrap_or_default_with(|| vec![0]), [0]);
+    /// ```
+    #[doc(alias = "unwrap_or_else")]
+    #[doc(alias = "unwrap_or_default")]
+    fn unwrap_or_default_with<F>(self, f: F) -> Self::Output
+    where
+        Self: Sized,
+        F: FnOnce() -> Self::Output,
+    {
+        self.into_result().unwrap_or_else(|_| f())
+    }
+
+    /// Returns the output, or moves the error to `sink` and returns `default`.
+    ///
+    /// This is useful for batch processing that should go past errors and report all of them at
+    /// the end. The error is fully handled before being moved to `sink`, so the next outcome can
+    /// run right away. See [`IexIteratorExt::sink_errors`](crate::IexIteratorExt::sink_errors) for
+    /// iterators of outcomes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, Outcome};
+    ///
+    /// #[iex]
+    /// fn parse(s: &str) -> Result<i32, String> {
+    ///     s.parse().map_err(|_| format!("Invalid number: {s}"))
+    /// }
+    ///
+    /// let mut errors = Vec::new();
+    /// let values: Vec<i32> = ["1", "x", "2", "y"]
+    ///     .into_iter()
+    ///     .map(|s| parse(s).sink_err(&mut errors, 0))
+    ///     .collect();
+    /// assert_eq!(values, [1, 0, 2, 0]);
+    /// assert_eq!(errors, ["Invalid number: x", "Invalid number: y"]);
+    /// ```
+    fn sink_err<C>(self, sink: &mut C, default: Self::Output) -> Self::Output
+    where
+        Self: Sized,
+        C: Extend<Self::Error>,
+    {
+        match self.into_result() {
+            Ok(value) => value,
+            Err(err) => {
+                sink.extend(Some(err));
+                default
+            }
+        }
+    }
+
+    /// Returns the output, assuming that the outcome is successful.
+    ///
+    /// In debug builds, this is a checked [`into_result`](Self::into_result) followed by an
+    /// unwrap. In release builds, the check is elided, and the outcome is evaluated without
+    /// setting up a handler for the error at all, which is as cheap as calling the function
+    /// directly.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the message "assume_ok on a failing outcome" if the outcome fails and
+    /// `debug_assertions` are enabled.
+    ///
+    /// # Safety
+    ///
+    /// The outcome must be successful. Calling this method on a failing outcome in release builds
+    /// is *[undefined behavior]*.
+    ///
+    /// [undefined behavior]: https://doc.rust-lang.org/reference/behavior-considered-undefined.html
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, Outcome};
+    ///
+    /// #[iex]
+    /// fn checked_divide(a: u32, b: u32) -> Result<u32, &'static str> {
+    ///     a.checked_div(b).ok_or("Cannot divide by zero")
+    /// }
+    ///
+    /// // SAFETY: the divisor is non-zero
+    /// assert_eq!(unsafe { checked_divide(6, 3).assume_ok() }, 2);
+    /// ```
+    #[doc(alias = "unwrap_unchecked")]
+    #[track_caller]
+    unsafe fn assume_ok(self) -> Self::Output
+    where
+        Self: Sized,
+    {
+        #[cfg(debug_assertions)]
+        {
+            match self.into_result() {
+                Ok(value) => value,
+                Err(_) => panic!("assume_ok on a failing outcome"),
+            }
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            // SAFETY: The caller guarantees that the outcome doesn't fail, so no one ever has to
+            // handle the error.
+            self.get_value_or_panic(unsafe { Marker::new() })
+        }
+    }
+
+    /// Returns the error, assuming that the outcome fails.
+    ///
+    /// This is the counterpart of [`assume_ok`](Self::assume_ok) for code that deliberately
+    /// exercises the error path, e.g. error path benchmarks. In debug builds, this is a checked
+    /// [`into_result`](Self::into_result) followed by an unwrap. In release builds, the check is
+    /// elided, and the error is taken from the catch handler directly. Either way, the error is
+    /// fully handled afterwards, just like with `into_result`.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the message "assume_err on a successful outcome" if the outcome succeeds and
+    /// `debug_assertions` are enabled.
+    ///
+    /// # Safety
+    ///
+    /// The outcome must fail. Calling this method on a successful outcome in release builds is
+    /// *[undefined behavior]*.
+    ///
+    /// [undefined behavior]: https://doc.rust-lang.org/reference/behavior-considered-undefined.html
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, Outcome};
+    ///
+    /// #[iex]
+    /// fn checked_divide(a: u32, b: u32) -> Result<u32, &'static str> {
+    ///     a.checked_div(b).ok_or("Cannot divide by zero")
+    /// }
+    ///
+    /// // SAFETY: the divisor is zero
+    /// assert_eq!(unsafe { checked_divide(6, 0).assume_err() }, "Cannot divide by zero");
+    /// ```
+    #[doc(alias = "unwrap_err_unchecked")]
+    #[track_caller]
+    unsafe fn assume_err(self) -> Self::Error
+    where
+        Self: Sized,
+    {
+        match self.into_result() {
+            Err(err) => err,
+            #[cfg(debug_assertions)]
+            Ok(_) => panic!("assume_err on a successful outcome"),
+            // SAFETY: The caller guarantees that the outcome fails.
+            #[cfg(not(debug_assertions))]
+            Ok(_) => unsafe { std::hint::unreachable_unchecked() },
+        }
+    }
+
+    /// Maps an `Outcome<Output = &T>` to an `Outcome<Output = T>` by cloning the output.
+    ///
+    /// This is a generalized version of [`Result::cloned`].
+    #[iex]
+    fn cloned<'a, T>(self) -> Result<T, Self::Error>
+    where
+        Self: Sized + Outcome<Output = &'a T>,
+        T: 'a + Clone,
+    {
+        Ok(self?.clone())
+    }
+
+    /// Maps an `Outcome<Output = &T>` to an `Outcome<Output = T>` by copying the output.
+    ///
+    /// This is a generalized version of [`Result::copied`].
+    #[iex]
+    fn copied<'a, T>(self) -> Result<T, Self::Error>
+    where
+        Self: Sized + Outcome<Output = &'a T>,
+        T: 'a + Copy,
+    {
+        Ok(*self?)
+    }
+
+    /// Drops the output, keeping the error.
+    ///
+    /// This is [`apply(outcome, drop)`](apply), named for intent: use it when an `#[iex]`
+    /// function is only called for its checks, e.g. `validate(input).discard()?`.
+    #[iex]
+    fn discard(self) -> Result<(), Self::Error>
+    where
+        Self: Sized,
+    {
+        apply(self, drop)
+    }
+
+    /// Converts the output with [`TryFrom`], propagating the conversion error as `Self::Error`.
+    ///
+    /// This is a shorthand for `Ok(U::try_from(outcome?)?)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, Outcome};
+    /// use std::num::TryFromIntError;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum Error {
+    ///     Negative,
+    ///     TooLarge,
+    /// }
+    ///
+    /// impl From<TryFromIntError> for Error {
+    ///     fn from(_: TryFromIntError) -> Self {
+    ///         Self::TooLarge
+    ///     }
+    /// }
+    ///
+    /// #[iex]
+    /// fn non_negative(x: i64) -> Result<i64, Error> {
+    ///     if x < 0 {
+    ///         Err(Error::Negative)
+    ///     } else {
+    ///         Ok(x)
+    ///     }
+    /// }
+    ///
+    /// #[iex]
+    /// fn to_byte(x: i64) -> Result<u8, Error> {
+    ///     non_negative(x).try_into_output()
+    /// }
+    ///
+    /// assert_eq!(to_byte(12).into_result(), Ok(12));
+    /// assert_eq!(to_byte(-1).into_result(), Err(Error::Negative));
+    /// assert_eq!(to_byte(256).into_result(), Err(Error::TooLarge));
+    /// ```
+    #[iex]
+    fn try_into_output<U>(self) -> Result<U, Self::Error>
+    where
+        Self: Sized,
+        U: TryFrom<Self::Output>,
+        Self::Error: From<U::Error>,
+    {
+        Ok(U::try_from(self?)?)
+    }
+
+    /// Apply a fallible function to the output.
+    ///
+    /// If `f` returns `Err`, the error is propagated just like an error of the original outcome.
+    /// This is the most convenient way to validate an output inline.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, Outcome};
+    ///
+    /// #[iex]
+    /// fn parse(s: &str) -> Result<i32, String> {
+    ///     s.parse().map_err(|_| format!("Invalid number: {s}"))
+    /// }
+    ///
+    /// #[iex]
+    /// fn parse_port(s: &str) -> Result<u16, String> {
+    ///     parse(s).try_map(|port| u16::try_from(port).map_err(|_| format!("Invalid port: {port}")))
+    /// }
+    ///
+    /// assert_eq!(parse_port("80").into_result(), Ok(80));
+    /// assert_eq!(parse_port("-1").into_result(), Err("Invalid port: -1".to_string()));
+    /// assert_eq!(parse_port("x").into_result(), Err("Invalid number: x".to_string()));
+    /// ```
+    #[doc(alias = "and_then")]
+    #[iex]
+    fn try_map<U, F>(self, f: F) -> Result<U, Self::Error>
+    where
+        Self: Sized,
+        F: FnOnce(Self::Output) -> Result<U, Self::Error>,
+    {
+        f(self?)
+    }
+
+    /// Pass the output to a function returning a plain [`Result`].
+    ///
+    /// This is [`try_map`](Self::try_map) under the name of the [`Result::and_then`] family, meant
+    /// for calling code that hasn't been migrated to `#[iex]` yet. If `f` returns `Err`, the error
+    /// is propagated like an error of the original outcome.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, Outcome};
+    ///
+    /// #[iex]
+    /// fn read_config(s: &str) -> Result<&str, String> {
+    ///     s.strip_prefix("port=").ok_or_else(|| format!("Unknown option: {s}"))
+    /// }
+    ///
+    /// // Not migrated yet
+    /// fn parse_port(s: &str) -> Result<u16, String> {
+    ///     s.parse().map_err(|_| format!("Invalid port: {s}"))
+    /// }
+    ///
+    /// #[iex]
+    /// fn port(s: &str) -> Result<u16, String> {
+    ///     read_config(s).and_then_result(parse_port)
+    /// }
+    ///
+    /// assert_eq!(port("port=80").into_result(), Ok(80));
+    /// assert_eq!(port("port=x").into_result(), Err("Invalid port: x".to_string()));
+    /// assert_eq!(port("host=a").into_result(), Err("Unknown option: host=a".to_string()));
+    /// ```
+    #[iex]
+    fn and_then_result<U, F>(self, f: F) -> Result<U, Self::Error>
+    where
+        Self: Sized,
+        F: FnOnce(Self::Output) -> Result<U, Self::Error>,
+    {
+        f(self?)
+    }
+
+    /// Combine the outputs of two outcomes with a function.
+    ///
+    /// `self` is driven first. If it fails, `other` is not driven at all. If both succeed, `f` is
+    /// called with both outputs; otherwise, the first error is propagated and `f` is not called.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, Outcome};
+    ///
+    /// #[iex]
+    /// fn parse(s: &str) -> Result<i32, String> {
+    ///     s.parse().map_err(|_| format!("Invalid number: {s}"))
+    /// }
+    ///
+    /// struct Range {
+    ///     start: i32,
+    ///     end: i32,
+    /// }
+    ///
+    /// #[iex]
+    /// fn parse_range(s: &str) -> Result<Range, String> {
+    ///     let (start, end) = s.split_once("..").ok_or("Missing ..".to_string())?;
+    ///     parse(start).zip_with(parse(end), |start, end| Range { start, end })
+    /// }
+    ///
+    /// let range = parse_range("1..5").into_result().unwrap();
+    /// assert_eq!((range.start, range.end), (1, 5));
+    /// assert_eq!(parse_range("x..y").into_result().err(), Some("Invalid number: x".to_string()));
+    /// ```
+    #[iex]
+    fn zip_with<O, U, F>(self, other: O, f: F) -> Result<U, Self::Error>
+    where
+        Self: Sized,
+        O: Outcome<Error = Self::Error>,
+        F: FnOnce(Self::Output, O::Output) -> U,
+    {
+        let output = self?;
+        Ok(f(output, other?))
+    }
+
+    /// Unwraps an `Outcome<Output = Option<T>>`, treating `None` as the error `err`.
+    ///
+    /// Arguments passed to `unwrap_option_or` are eagerly evaluated; if you are passing the result
+    /// of a function call, it is recommended to use
+    /// [`unwrap_option_or_else`](Self::unwrap_option_or_else), which is lazily evaluated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, Outcome};
+    ///
+    /// #[iex]
+    /// fn lookup(key: &str) -> Result<Option<i32>, String> {
+    ///     Ok((key == "answer").then_some(42))
+    /// }
+    ///
+    /// #[iex]
+    /// fn get(key: &str) -> Result<i32, String> {
+    ///     lookup(key).unwrap_option_or("Missing key".to_string())
+    /// }
+    ///
+    /// assert_eq!(get("answer").into_result(), Ok(42));
+    /// assert_eq!(get("question").into_result(), Err("Missing key".to_string()));
+    /// ```
+    #[doc(alias = "ok_or")]
+    #[iex]
+    fn unwrap_option_or<T>(self, err: Self::Error) -> Result<T, Self::Error>
+    where
+        Self: Sized + Outcome<Output = Option<T>>,
+    {
+        self?.ok_or(err)
+    }
+
+    /// Unwraps an `Outcome<Output = Option<T>>`, treating `None` as the error returned by `f`.
+    ///
+    /// `f` is only called if the output is `None`.
+    #[doc(alias = "ok_or_else")]
+    #[iex]
+    fn unwrap_option_or_else<T, F>(self, f: F) -> Result<T, Self::Error>
+    where
+        Self: Sized + Outcome<Output = Option<T>>,
+        F: FnOnce() -> Self::Error,
+    {
+        self?.ok_or_else(f)
+    }
+
+    /// Unwraps an `Outcome<Output = Result<T, E>>`, propagating the inner error.
+    ///
+    /// The inner error is converted via [`From`], just like with `?`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, Outcome};
+    ///
+    /// #[iex]
+    /// fn read_config(s: &str) -> Result<Result<i32, String>, String> {
+    ///     if s.is_empty() {
+    ///         return Err("Empty config".to_string());
+    ///     }
+    ///     Ok(s.parse().map_err(|_| format!("Invalid number: {s}")))
+    /// }
+    ///
+    /// #[iex]
+    /// fn get(s: &str) -> Result<i32, String> {
+    ///     read_config(s).flatten_result()
+    /// }
+    ///
+    /// assert_eq!(get("1").into_result(), Ok(1));
+    /// assert_eq!(get("x").into_result(), Err("Invalid number: x".to_string()));
+    /// assert_eq!(get("").into_result(), Err("Empty config".to_string()));
+    /// ```
+    #[doc(alias = "flatten")]
+    #[iex]
+    fn flatten_result<T, E>(self) -> Result<T, Self::Error>
+    where
+        Self: Sized + Outcome<Output = Result<T, E>>,
+        Self::Error: From<E>,
+    {
+        Ok(self??)
+    }
+
+    /// Recover from some errors and convert the rest to another error type.
+    ///
+    /// If `Err`, calls `recover` with a reference to the error. If `recover` returns `Some`, the
+    /// value is used as the output; otherwise, the original error is converted via [`From`] and
+    /// propagated.
+    ///
+    /// This is useful at subsystem boundaries, where a subset of errors is retriable and the rest
+    /// is reported in terms of the boundary's error type.
+    ///
+    /// Recovering means stopping the propagation, so unlike most adapters, this method catches the
+    /// error the way [`into_result`](Self::into_result) does. The value is still returned without
+    /// going through a `Result`, and `recover` only runs on the error path.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, Outcome};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum LowLevelError {
+    ///     WouldBlock,
+    ///     Broken,
+    /// }
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct BoundaryError(LowLevelError);
+    ///
+    /// impl From<LowLevelError> for BoundaryError {
+    ///     fn from(err: LowLevelError) -> Self {
+    ///         Self(err)
+    ///     }
+    /// }
+    ///
+    /// #[iex]
+    /// fn read(err: LowLevelError) -> Result<u8, LowLevelError> {
+    ///     Err(err)
+    /// }
+    ///
+    /// #[iex]
+    /// fn boundary(err: LowLevelError) -> Result<u8, BoundaryError> {
+    ///     read(err).recover_or_rethrow(|err| (*err == LowLevelError::WouldBlock).then_some(0))
+    /// }
+    ///
+    /// assert_eq!(boundary(LowLevelError::WouldBlock).into_result(), Ok(0));
+    /// assert_eq!(
+    ///     boundary(LowLevelError::Broken).into_result(),
+    ///     Err(BoundaryError(LowLevelError::Broken)),
+    /// );
+    /// ```
+    #[iex]
+    fn recover_or_rethrow<E2, F>(self, recover: F) -> Result<Self::Output, E2>
+    where
+        Self: Sized,
+        E2: From<Self::Error>,
+        F: FnOnce(&Self::Error) -> Option<Self::Output>,
+    {
+        // Recovery has to stop the unwinding, so this catches like `into_result` does. But the
+        // value is returned straight out of the catch, and reading, recovering and rethrowing the
+        // error all happen in the handler, so no `Result` is built on the happy path.
+        crate::imp::IexResult(
+            move |marker| {
+                catch_or_else(
+                    |in_marker| self.get_value_or_panic(in_marker),
+                    #[cold]
+                    |err| match recover(&err) {
+                        Some(value) => value,
+                        None => Err(E2::from(err)).get_value_or_panic(marker),
+                    },
+                )
+            },
+            PhantomData,
+        )
+    }
+
+    /// Recover from an error, or wrap it with additional context.
+    ///
+    /// If `Err`, calls `recover` with a reference to the error. If `recover` returns `Some`, the
+    /// value is used as the output; otherwise, the error is wrapped with `context` like
+    /// [`Context::context`](crate::Context::context) does and propagated. This is
+    /// [`recover_or_rethrow`](Self::recover_or_rethrow) for subsystem boundaries reporting errors
+    /// via [`anyhow`](https://docs.rs/anyhow).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, Outcome};
+    /// use std::io::{Error, ErrorKind};
+    ///
+    /// #[iex]
+    /// fn read(kind: ErrorKind) -> Result<u8, Error> {
+    ///     Err(Error::from(kind))
+    /// }
+    ///
+    /// #[iex]
+    /// fn handle(kind: ErrorKind) -> anyhow::Result<u8> {
+    ///     read(kind).context_or_recover("While reading the request", |err| {
+    ///         (err.kind() == ErrorKind::WouldBlock).then_some(0)
+    ///     })
+    /// }
+    ///
+    /// assert_eq!(handle(ErrorKind::WouldBlock).into_result().unwrap(), 0);
+    /// assert_eq!(
+    ///     format!("{:#}", handle(ErrorKind::NotFound).into_result().unwrap_err()),
+    ///     "While reading the request: entity not found",
+    /// );
+    /// ```
+    #[cfg(feature = "anyhow")]
+    #[iex]
+    fn context_or_recover<C, F>(self, context: C, recover: F) -> Result<Self::Output, anyhow::Error>
+    where
+        Self: Sized,
+        Result<(), Self::Error>: anyhow::Context<(), Self::Error>,
+        C: std::fmt::Display + Send + Sync + 'static,
+        F: FnOnce(&Self::Error) -> Option<Self::Output>,
+    {
+        // Catches the same way as `recover_or_rethrow`
+        crate::imp::IexResult(
+            move |marker| {
+                catch_or_else(
+                    |in_marker| self.get_value_or_panic(in_marker),
+                    #[cold]
+                    |err| match recover(&err) {
+                        Some(value) => value,
+                        None => {
+                            Err(anyhow::Context::context(Err::<(), _>(err), context).unwrap_err())
+                                .get_value_or_panic(marker)
+                        }
+                    },
+                )
+            },
+            PhantomData,
+        )
+    }
+
+    /// Recover from or convert an error by inspecting it by value.
+    ///
+    /// If `Err`, calls `f` with the error. If `f` returns `Ok`, the value is used as the output;
+    /// if it returns `Err`, the new error is propagated instead. This is [`Result::or_else`] that
+    /// can also change the error type, and subsumes both [`map_err`](Self::map_err) and
+    /// [`recover_or_rethrow`](Self::recover_or_rethrow).
+    ///
+    /// # Exception state
+    ///
+    /// Like [`map_err`](Self::map_err), `map_err_kind` rewrites the in-flight exception while it
+    /// unwinds: the original error is moved out of the exception slot and passed to `f`, and the
+    /// `Result` returned by `f` is written back to the slot in its place, whether it's `Ok` or
+    /// `Err`. To be able to recover, the unwinding is then caught at the `map_err_kind` call, and
+    /// that `Result` is moved out of the slot again. An `Ok` value is returned as the output and
+    /// leaves the slot empty. An `Err` is written back to the slot as the new error when the
+    /// returned outcome is propagated with `?` or resolved with [`into_result`](Self::into_result).
+    ///
+    /// `f` may call other [`#[iex]`](macro@crate::iex) functions: like any code running while an
+    /// error unwinds, they set the in-flight exception aside and restore it when they return.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, Outcome};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum ParseError {
+    ///     Empty,
+    ///     Invalid(char),
+    /// }
+    ///
+    /// #[iex]
+    /// fn parse_digit(s: &str) -> Result<u32, ParseError> {
+    ///     let c = s.chars().next().ok_or(ParseError::Empty)?;
+    ///     c.to_digit(10).ok_or(ParseError::Invalid(c))
+    /// }
+    ///
+    /// #[iex]
+    /// fn parse_digit_or_zero(s: &str) -> Result<u32, String> {
+    ///     parse_digit(s).map_err_kind(|err| match err {
+    ///         ParseError::Empty => Ok(0),
+    ///         ParseError::Invalid(c) => Err(format!("invalid digit {c:?}")),
+    ///     })
+    /// }
+    ///
+    /// assert_eq!(parse_digit_or_zero("7").into_result(), Ok(7));
+    /// assert_eq!(parse_digit_or_zero("").into_result(), Ok(0));
+    /// assert_eq!(
+    ///     parse_digit_or_zero("x").into_result(),
+    ///     Err("invalid digit 'x'".to_string()),
+    /// );
+    /// ```
+    #[doc(alias = "or_else")]
+    #[iex]
+    fn map_err_kind<E2, F>(self, f: F) -> Result<Self::Output, E2>
+    where
+        Self: Sized,
+        F: FnOnce(Self::Error) -> Result<Self::Output, E2>,
+    {
+        // `f` runs in the mapper while the error unwinds, and its result is carried up to the catch
+        // in place of the error
+        crate::imp::IexResult(
+            move |marker| {
+                catch_or_else(
+                    move |in_marker| {
+                        let exception_mapper =
+                            ExceptionMapper::new(in_marker, (), move |(), err| f(err));
+                        let output = self.get_value_or_panic(exception_mapper.get_in_marker());
+                        exception_mapper.swallow();
+                        output
+                    },
+                    #[cold]
+                    |mapped: Result<Self::Output, E2>| mapped.get_value_or_panic(marker),
+                )
+            },
+            PhantomData,
+        )
+    }
+
+    /// Fail with [`Cancelled`] if the token is cancelled before or after driving the outcome.
+    ///
+    /// This adds two checkpoints to a computation that doesn't check the token itself: if the
+    /// token is already cancelled, the outcome is not driven at all, and if it gets cancelled
+    /// while the outcome is being driven, the output is dropped and `Cancelled` is propagated
+    /// instead. Errors of the outcome itself are propagated as is. For computations that run for
+    /// a long time, call [`CancellationToken::check`] inside the loop as well; cancellation is
+    /// cooperative and never interrupts the outcome in the middle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, CancellationToken, Cancelled, Outcome};
+    ///
+    /// #[iex]
+    /// fn step(token: &CancellationToken, cancel: bool) -> Result<i32, Cancelled> {
+    ///     if cancel {
+    ///         token.cancel();
+    ///     }
+    ///     Ok(1)
+    /// }
+    ///
+    /// #[iex]
+    /// fn run(token: &CancellationToken, cancel: bool) -> Result<i32, Cancelled> {
+    ///     step(token, cancel).with_cancellation(token)
+    /// }
+    ///
+    /// let token = CancellationToken::new();
+    /// assert_eq!(run(&token, false).into_result(), Ok(1));
+    /// assert_eq!(run(&token, true).into_result(), Err(Cancelled));
+    /// ```
+    #[iex]
+    fn with_cancellation(self, token: &CancellationToken) -> Result<Self::Output, Self::Error>
+    where
+        Self: Sized,
+        Self::Error: From<Cancelled>,
+    {
+        token.check()?;
+        let output = self?;
+        token.check()?;
+        Ok(output)
+    }
+
+    /// Recover from an error with a fallback computation that can fail itself.
+    ///
+    /// If `Err`, calls `f` with the error and propagates the outcome it returns, which may have a
+    /// different error type. Unlike [`map_err_kind`](Self::map_err_kind), the fallback is an
+    /// outcome, so it can be an `#[iex]` function call, and its error is propagated without being
+    /// materialized as a [`Result`]. `f` is not called if the outcome succeeds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use iex::{iex, Outcome};
+    ///
+    /// #[iex]
+    /// fn read_cache(key: &str) -> Result<String, ()> {
+    ///     Err(())
+    /// }
+    ///
+    /// #[iex]
+    /// fn fetch(key: &str) -> Result<String, String> {
+    ///     if key.is_empty() {
+    ///         Err("Empty key".to_string())
+    ///     } else {
+    ///         Ok(format!("value of {key}"))
+    ///     }
+    /// }
+    ///
+    /// #[iex]
+    /// fn get(key: &str) -> Result<String, String> {
+    ///     read_cache(key).or_else_value(|()| fetch(key))
+    /// }
+    ///
+    /// assert_eq!(get("a").into_result(), Ok("value of a".to_string()));
+    /// assert_eq!(get("").into_result(), Err("Empty key".to_string()));
+    /// ```
+    #[doc(alias = "or_else")]
+    #[iex]
+    fn or_else_value<E2, O, F>(self, f: F) -> Result<Self::Output, E2>
+    where
+        Self: Sized,
+        O: Outcome<Output = Self::Output, Error = E2>,
+        F: FnOnce(Self::Error) -> O,
+    {
+        // As in `map_err_kind`, recovery has to stop the unwinding.
+        match self.into_result() {
+            Ok(value) => Ok(value),
+            Err(err) => Ok(f(err)?),
+        }
+    }
+}
+
+/// Create an outcome from a closure returning a [`Result`].
+///
+/// The closure is invoked lazily, when the outcome is propagated or cast to a [`Result`]. This is
+/// a stable alternative to `#[iex]` closures when the body doesn't need to call `#[iex]` functions
+/// with `?`.
+///
+/// Unlike the outcomes returned by `#[iex]` functions, the returned outcome has a nameable type,
+/// [`IexResult`].
+///
+/// # Example
+///
+/// ```
+/// use iex::{iex, Outcome};
+///
+/// #[iex]
+/// fn parse(s: &str) -> Result<i32, std::num::ParseIntError> {
+///     iex::from_fn(|| s.parse())
+/// }
+///
+/// assert_eq!(parse("123").into_result(), Ok(123));
+/// assert!(parse("abc").into_result().is_err());
+/// ```
+pub fn from_fn<T, E, F>(f: F) -> IexResult<T, E, F>
+where
+    F: FnOnce() -> Result<T, E>,
+{
+    IexResult::new(f)
+}
+
+/// Apply a function to the output of an outcome.
+///
+/// This is [`Result::map`] for outcomes. It is most useful in code that is generic over outcomes.
+///
+/// # Generic code
+///
+/// Functions can accept any outcome via a generic parameter `O: Outcome` and consume it with `?`
+/// inside [`#[iex]`](macro@crate::iex), just like a concrete outcome. Use `O::Error` (or a type
+/// that `O::Error` converts to) as the error type of such functions. When the function doesn't
+/// need to be `#[iex]` itself, `apply` transforms the outcome without resolving it, so the result
+/// can be propagated further by the caller.
+///
+/// # Example
+///
+/// ```
+/// use iex::{apply, iex, Outcome};
+/// use std::fmt::Display;
+///
+/// #[iex]
+/// fn parse(s: &str) -> Result<i32, String> {
+///     s.parse().map_err(|_| format!("Invalid number: {s}"))
+/// }
+///
+/// // Works with both `#[iex] Result` and `Result`
+/// fn describe<O>(outcome: O) -> impl Outcome<Output = String, Error = O::Error>
+/// where
+///     O: Outcome,
+///     O::Output: Display,
+/// {
+///     apply(outcome, |value| format!("got {value}"))
+/// }
+///
+/// #[iex]
+/// fn sum<O: Outcome<Output = i32>>(a: O, b: O) -> Result<i32, O::Error> {
+///     Ok(a? + b?)
+/// }
+///
+/// assert_eq!(describe(parse("1")).into_result(), Ok("got 1".to_string()));
+/// assert_eq!(describe(Ok::<_, ()>(2)).into_result(), Ok("got 2".to_string()));
+/// assert_eq!(sum(parse("1"), parse("2")).into_result(), Ok(3));
+/// assert_eq!(
+///     sum(parse("1"), parse("x")).into_result(),
+///     Err("Invalid number: x".to_string()),
+/// );
+/// ```
+#[doc(alias = "map")]
+#[iex]
+pub fn apply<O, R, F>(outcome: O, f: F) -> Result<R, O::Error>
+where
+    O: Outcome,
+    F: FnOnce(O::Output) -> R,
+{
+    Ok(f(outcome?))
+}
+
+/// Drive all checks, collecting the errors of the failed ones.
+///
+/// Unlike `?`, which stops at the first error, this runs every check and returns `Err` with the
+/// errors of all failed checks, in order, if there are any. This is useful for validation, where
+/// reporting all problems at once is friendlier than reporting them one by one.
+///
+/// All checks must have the same type, e.g. be calls to one function. Use
+/// [`try_join_all!`](crate::try_join_all) to run a fixed number of checks of different types.
+///
+/// # Example
+///
+/// ```
+/// use iex::{iex, validate_all};
+///
+/// #[iex]
+/// fn not_empty(field: &str, value: &str) -> Result<(), String> {
+///     if value.is_empty() {
+///         return Err(format!("{field} must not be empty"));
+///     }
+/// }
+///
+/// let form = [("name", "Alice"), ("email", ""), ("city", "")];
+/// assert_eq!(
+///     validate_all(form.iter().map(|(field, value)| not_empty(field, value))),
+///     Err(vec!["email must not be empty".to_string(), "city must not be empty".to_string()]),
+/// );
+/// assert_eq!(validate_all(form[..1].iter().map(|(field, value)| not_empty(field, value))), Ok(()));
+/// ```
+pub fn validate_all<E, I>(checks: I) -> Result<(), Vec<E>>
+where
+    I: IntoIterator,
+    I::Item: Outcome<Output = (), Error = E>,
+{
+    let errors: Vec<E> = checks
+        .into_iter()
+        .filter_map(|check| check.into_result().err())
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Drive all outcomes, separating the values of the successful ones from the errors of the failed
+/// ones.
+///
+/// This is [`validate_all`] for outcomes that produce values: every outcome is driven, and both
+/// the values and the errors are returned in input order. Use it for batch jobs that need to report
+/// which items succeeded and which failed, rather than stopping at the first error.
+///
+/// # Example
+///
+/// ```
+/// use iex::{iex, partition_iex};
+///
+/// #[iex]
+/// fn parse(record: &str) -> Result<u32, String> {
+///     record.parse().map_err(|_| format!("invalid record: {record}"))
+/// }
+///
+/// let records = ["1", "x", "3", "y"];
+/// let (values, errors) = partition_iex(records.iter().map(|record| parse(record)));
+/// assert_eq!(values, [1, 3]);
+/// assert_eq!(errors, ["invalid record: x", "invalid record: y"]);
+/// ```
+#[doc(alias = "collect_errors")]
+pub fn partition_iex<T, E, I>(outcomes: I) -> (Vec<T>, Vec<E>)
+where
+    I: IntoIterator,
+    I::Item: Outcome<Output = T, Error = E>,
+{
+    let mut values = Vec::new();
+    let mut errors = Vec::new();
+    for outcome in outcomes {
+        match outcome.into_result() {
+            Ok(value) => values.push(value),
+            Err(error) => errors.push(error),
+        }
+    }
+    (values, errors)
+}
+
+/// Drive outcomes in order until one succeeds.
+///
+/// Returns the output of the first successful outcome, without driving the following ones. If all
+/// outcomes fail, throws the errors of all of them, in order. This is useful for redundant
+/// sources, e.g. querying several mirrors until one responds. The outcomes are driven sequentially,
+/// not concurrently.
+///
+/// If there are no outcomes, an empty list of errors is thrown.
+///
+/// # Example
+///
+/// ```
+/// use iex::{iex, race, Outcome};
+///
+/// #[iex]
+/// fn fetch(mirror: &str) -> Result<String, String> {
+///     if mirror.starts_with("down.") {
+///         return Err(format!("{mirror} is unreachable"));
+///     }
+///     Ok(format!("data from {mirror}"))
+/// }
+///
+/// let mirrors = ["down.example.com", "up.example.com", "up.example.org"];
+/// assert_eq!(
+///     race(mirrors.iter().map(|mirror| fetch(mirror))).into_result(),
+///     Ok("data from up.example.com".to_string()),
+/// );
+/// assert_eq!(
+///     race(mirrors[..1].iter().map(|mirror| fetch(mirror))).into_result(),
+///     Err(vec!["down.example.com is unreachable".to_string()]),
+/// );
+/// ```
+#[iex]
+pub fn race<T, E, I>(outcomes: I) -> Result<T, Vec<E>>
+where
+    I: IntoIterator,
+    I::Item: Outcome<Output = T, Error = E>,
+{
+    let mut errors = Vec::new();
+    for outcome in outcomes {
+        match outcome.into_result() {
+            Ok(output) => return Ok(output),
+            Err(error) => errors.push(error),
+        }
+    }
+    Err(errors)
 }
@@ -0,0 +1,50 @@
+use crate::Outcome;
+
+/// An outcome that can be stored and resolved later through a mutable reference.
+///
+/// Outcomes are consumed when resolved, which is inconvenient in state machines and other code
+/// that only has `&mut` access to the outcome. `PollableOutcome` resolves the wrapped outcome on
+/// the first call to [`poll_once`](Self::poll_once) and returns `None` on all subsequent calls.
+///
+/// # Example
+///
+/// ```
+/// use iex::{iex, Outcome, PollableOutcome};
+///
+/// #[iex]
+/// fn compute(x: i32) -> Result<i32, String> {
+///     Ok(x * 2)
+/// }
+///
+/// struct Task<R: Outcome> {
+///     outcome: PollableOutcome<R>,
+/// }
+///
+/// let mut task = Task {
+///     outcome: compute(21).into_pollable(),
+/// };
+/// assert_eq!(task.outcome.poll_once(), Some(Ok(42)));
+/// assert_eq!(task.outcome.poll_once(), None);
+/// ```
+#[must_use]
+pub struct PollableOutcome<R>(Option<R>);
+
+impl<R: Outcome> PollableOutcome<R> {
+    /// Wrap an outcome.
+    pub fn new(outcome: R) -> Self {
+        Self(Some(outcome))
+    }
+
+    /// Resolve the outcome if it hasn't been resolved yet.
+    ///
+    /// Returns `Some` on the first call and `None` afterwards.
+    pub fn poll_once(&mut self) -> Option<Result<R::Output, R::Error>> {
+        self.0.take().map(Outcome::into_result)
+    }
+
+    /// Returns `true` if the outcome has already been resolved by
+    /// [`poll_once`](Self::poll_once).
+    pub fn is_resolved(&self) -> bool {
+        self.0.is_none()
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{iex, Outcome};
+use rayon::iter::{FromParallelIterator, ParallelIterator};
+
+/// [`rayon`](https://docs.rs/rayon/latest/rayon/) compatibility layer.
+///
+/// Exceptions cannot cross thread boundaries, so an `#[iex] Result` computed on a rayon worker
+/// must be resolved on that worker. This trait does just that: each outcome is cast to a
+/// [`Result`] on the thread that computes it, and the collected [`Result`] is propagated as an
+/// exception on the calling thread.
+///
+/// # Example
+///
+/// ```rust
+/// use iex::{iex, IexParallelIteratorExt, Outcome};
+/// use rayon::iter::{IntoParallelIterator, ParallelIterator};
+///
+/// #[iex]
+/// fn validate(x: u32) -> Result<u32, String> {
+///     if x % 7 == 6 {
+///         Err(format!("{x} is invalid"))
+///     } else {
+///         Ok(x * 2)
+///     }
+/// }
+///
+/// #[iex]
+/// fn validate_all(n: u32) -> Result<Vec<u32>, String> {
+///     (0..n).into_par_iter().map(validate).try_collect_iex()
+/// }
+///
+/// assert_eq!(validate_all(3).into_result(), Ok(vec![0, 2, 4]));
+/// assert!(validate_all(100).into_result().is_err());
+/// ```
+pub trait IexParallelIteratorExt: ParallelIterator {
+    /// Collect the outputs of a parallel iterator over outcomes, propagating an error if any
+    /// outcome fails.
+    ///
+    /// Like when collecting a parallel iterator into a [`Result`], the propagated error is the
+    /// first one *encountered*, which is not necessarily the one with the smallest index. Once an
+    /// error is encountered, the remaining items are skipped on a best-effort basis.
+    #[iex]
+    fn try_collect_iex<C>(self) -> Result<C, <Self::Item as Outcome>::Error>
+    where
+        Self: Sized,
+        Self::Item: Outcome,
+        <Self::Item as Outcome>::Output: Send,
+        <Self::Item as Outcome>::Error: Send,
+        C: FromParallelIterator<<Self::Item as Outcome>::Output>,
+    {
+        self.map(Outcome::into_result).collect::<Result<C, _>>()
+    }
+}
+
+impl<I: ParallelIterator> IexParallelIteratorExt for I {}
@@ -2,6 +2,10 @@ use crate::{imp::Marker, outcome::Sealed, IexPanic, Outcome, EXCEPTION};
 
 impl<T, E> Sealed for Result<T, E> {}
 
+/// The reference implementation of [`Outcome`].
+///
+/// `Ok(value)` propagates as `value`. `Err(error)` moves `error` to the exception slot and unwinds.
+/// See [`Outcome`](Outcome#result) for details.
 impl<T, E> Outcome for Result<T, E> {
     type Output = T;
 
@@ -51,3 +55,35 @@ impl<T, E> Outcome for Result<T, E> {
         self
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    fn slot_is_clear() -> bool {
+        EXCEPTION.with(|exception| unsafe { &*exception.get() }.is_clear())
+    }
+
+    #[test]
+    fn ok_returns_value() {
+        assert_eq!(
+            Ok::<_, String>(1).get_value_or_panic(unsafe { Marker::new() }),
+            1
+        );
+        assert!(slot_is_clear());
+    }
+
+    #[test]
+    fn err_writes_slot_and_unwinds() {
+        let payload = catch_unwind(AssertUnwindSafe(|| {
+            Err::<(), _>(String::from("error")).get_value_or_panic(unsafe { Marker::new() })
+        }))
+        .unwrap_err();
+        assert!(payload.is::<IexPanic>());
+        let error =
+            EXCEPTION.with(|exception| unsafe { (*exception.get()).take().read::<String>() });
+        assert_eq!(error.as_deref(), Some("error"));
+        assert!(slot_is_clear());
+    }
+}
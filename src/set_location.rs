@@ -0,0 +1,15 @@
+use std::panic::Location;
+
+/// An error that can record where it was propagated.
+///
+/// This is used by [`Outcome::with_location`](crate::Outcome::with_location), which stamps the
+/// location of its caller onto the error. Unlike the `location` feature, which tracks the
+/// location of every error, this is opt-in per error type and per call site, and the location is
+/// stored in the error itself.
+pub trait SetLocation {
+    /// Record the location of a call site that propagated this error.
+    ///
+    /// Called once by each [`with_location`](crate::Outcome::with_location) the error passes
+    /// through, innermost first.
+    fn set_location(&mut self, location: &'static Location<'static>);
+}
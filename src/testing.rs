@@ -0,0 +1,217 @@
+//! Test-only helpers for checking the exception state.
+//!
+//! Available with the `testing` feature only. Enable it in `[dev-dependencies]` rather than in
+//! `[dependencies]`, since these functions are only meaningful as assertions.
+//!
+//! An error thrown by an `#[iex]` function is stored in a thread-local slot until it is caught by
+//! [`into_result`](crate::Outcome::into_result) or a similar method. If the slot is still occupied
+//! once all outcomes have been resolved, an error was thrown but never read, e.g. because the
+//! unwinding was intercepted by something other than `iex`.
+//!
+//! # Example
+//!
+//! ```
+//! use iex::{iex, testing, Outcome};
+//!
+//! #[iex]
+//! fn fails() -> Result<(), i32> {
+//!     Err(123)
+//! }
+//!
+//! assert_eq!(fails().into_result(), Err(123));
+//! // Teardown: the error has been consumed
+//! assert!(testing::exception_is_clear());
+//! ```
+//!
+//! # Random call trees
+//!
+//! [`random_tree_strategy`] generates trees of nested `#[iex]` calls with errors at random points,
+//! mixing propagation via `?`, error mapping that runs other outcomes while an error is in flight,
+//! and nested recovery. [`CallTree::check`] runs a tree and compares the result to the one
+//! computed with plain [`Result`]s. This validates the core invariant of the crate: an outcome
+//! fails with exactly the error it would have failed with had it been a [`Result`], and leaves no
+//! exception behind.
+//!
+//! Downstream crates can run the check in the environment their own `#[iex]` code runs in, e.g.
+//! with the `exception_store` feature enabled, inside a custom panic hook, or from a thread pool:
+//!
+//! ```
+//! use iex::testing;
+//!
+//! for tree in testing::random_tree_strategy().take(100) {
+//!     tree.check();
+//! }
+//! ```
+//!
+//! The trees are generated from consecutive seeds, so the sequence is the same on every run. To
+//! plug the generator into a property testing framework, map an arbitrary `u64` through
+//! [`CallTree::random`], e.g. `any::<u64>().prop_map(CallTree::random)` with `proptest`.
+
+use crate::{iex, Outcome, EXCEPTION};
+
+/// Returns `true` if no error is stored in the current thread's exception slot.
+pub fn exception_is_clear() -> bool {
+    EXCEPTION.with(|exception| unsafe { &*exception.get() }.is_clear())
+}
+
+/// Resets the current thread's exception slot.
+///
+/// The stored error, if any, is leaked rather than dropped, because its type is not known.
+pub fn clear() {
+    EXCEPTION.with(|exception| unsafe { &mut *exception.get() }.clear())
+}
+
+/// A tree of nested `#[iex]` calls.
+///
+/// See the [module documentation](self) for more information.
+#[derive(Clone, Debug)]
+pub struct CallTree(Node);
+
+#[derive(Clone, Debug)]
+enum Node {
+    // Returns a fixed result
+    Leaf(Result<u32, u32>),
+    // Sums the outputs of the children, propagating the first error with `?`
+    Sum(Vec<CallTree>),
+    // Maps the error of the first tree with the output or the error of the second one
+    MapErr(Box<CallTree>, Box<CallTree>),
+    // Turns an error into an output
+    Catch(Box<CallTree>),
+}
+
+const MAX_DEPTH: u32 = 6;
+const MAX_WIDTH: u64 = 4;
+
+// SplitMix64, which is good enough for generating test cases and needs no dependencies
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+}
+
+impl CallTree {
+    /// Generate a tree from a seed.
+    ///
+    /// The same seed always produces the same tree.
+    pub fn random(seed: u64) -> Self {
+        Self::generate(&mut Rng(seed), MAX_DEPTH)
+    }
+
+    fn generate(rng: &mut Rng, depth: u32) -> Self {
+        let kind = if depth == 0 { 0 } else { rng.below(5) };
+        Self(match kind {
+            0 | 1 => {
+                let value = rng.below(1000) as u32;
+                if rng.below(3) == 0 {
+                    Node::Leaf(Err(value))
+                } else {
+                    Node::Leaf(Ok(value))
+                }
+            }
+            2 => Node::Sum(
+                (0..=rng.below(MAX_WIDTH))
+                    .map(|_| Self::generate(rng, depth - 1))
+                    .collect(),
+            ),
+            3 => Node::MapErr(
+                Box::new(Self::generate(rng, depth - 1)),
+                Box::new(Self::generate(rng, depth - 1)),
+            ),
+            _ => Node::Catch(Box::new(Self::generate(rng, depth - 1))),
+        })
+    }
+
+    /// Run the tree as `#[iex]` functions.
+    #[iex]
+    pub fn run(&self) -> Result<u32, u32> {
+        match &self.0 {
+            &Node::Leaf(result) => Ok(result?),
+            Node::Sum(children) => {
+                let mut sum = 0u32;
+                for child in children {
+                    sum = sum.wrapping_add(child.run()?);
+                }
+                Ok(sum)
+            }
+            Node::MapErr(inner, handler) => Ok(inner.run().map_err(|err| {
+                let (Ok(value) | Err(value)) = handler.run().into_result();
+                err.wrapping_add(value)
+            })?),
+            Node::Catch(inner) => Ok(match inner.run().into_result() {
+                Ok(value) => value,
+                Err(err) => err.wrapping_mul(3),
+            }),
+        }
+    }
+
+    /// Compute the result of the tree with plain [`Result`]s.
+    pub fn expected(&self) -> Result<u32, u32> {
+        match &self.0 {
+            Node::Leaf(result) => *result,
+            Node::Sum(children) => children
+                .iter()
+                .try_fold(0u32, |sum, child| Ok(sum.wrapping_add(child.expected()?))),
+            Node::MapErr(inner, handler) => inner.expected().map_err(|err| {
+                let (Ok(value) | Err(value)) = handler.expected();
+                err.wrapping_add(value)
+            }),
+            Node::Catch(inner) => Ok(match inner.expected() {
+                Ok(value) => value,
+                Err(err) => err.wrapping_mul(3),
+            }),
+        }
+    }
+
+    /// Check that [`run`](Self::run) produces the [`expected`](Self::expected) result and leaves
+    /// no exception behind.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the tree in the message if the check fails.
+    pub fn check(&self) {
+        assert_eq!(self.run().into_result(), self.expected(), "in {self:?}");
+        assert!(exception_is_clear(), "exception left behind by {self:?}");
+    }
+}
+
+/// Generate random call trees.
+///
+/// This is an infinite iterator over [`CallTree::random`] with seeds `0, 1, 2, ...`. See the
+/// [module documentation](self) for more information.
+pub fn random_tree_strategy() -> impl Iterator<Item = CallTree> {
+    (0..).map(CallTree::random)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{imp::Marker, Outcome};
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    #[test]
+    fn detects_leak() {
+        assert!(exception_is_clear());
+        assert_eq!(Err::<(), _>(1).into_result(), Err(1));
+        assert!(exception_is_clear());
+
+        // Throw without anyone reading the error
+        let payload = catch_unwind(AssertUnwindSafe(|| {
+            Err::<(), _>(String::from("leaked")).get_value_or_panic(unsafe { Marker::new() })
+        }));
+        assert!(payload.is_err());
+        assert!(!exception_is_clear());
+
+        clear();
+        assert!(exception_is_clear());
+    }
+}
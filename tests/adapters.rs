@@ -0,0 +1,351 @@
+use iex::{iex, OptionIexExt, Outcome};
+use std::cell::{Cell, RefCell};
+
+#[iex]
+fn first<'a>(items: &'a [String]) -> Result<&'a String, &'static str> {
+    items.first().ok_or("Empty")
+}
+
+#[iex]
+fn first_number<'a>(items: &'a [i32]) -> Result<&'a i32, &'static str> {
+    items.first().ok_or("Empty")
+}
+
+#[iex]
+fn first_owned(items: &[String]) -> Result<String, &'static str> {
+    first(items).cloned()
+}
+
+#[test]
+fn cloned() {
+    assert_eq!(
+        first_owned(&["a".to_string()]).into_result(),
+        Ok("a".to_string())
+    );
+    assert_eq!(first_owned(&[]).into_result(), Err("Empty"));
+    assert_eq!(Ok::<_, ()>(&1).cloned().into_result(), Ok(1));
+}
+
+#[test]
+fn copied() {
+    assert_eq!(first_number(&[1, 2]).copied().into_result(), Ok(1));
+    assert_eq!(first_number(&[]).copied().into_result(), Err("Empty"));
+}
+
+#[iex]
+fn maybe(value: Option<i32>) -> Result<Option<i32>, &'static str> {
+    Ok(value)
+}
+
+#[test]
+fn unwrap_option_or() {
+    assert_eq!(maybe(Some(1)).unwrap_option_or("None").into_result(), Ok(1));
+    assert_eq!(
+        maybe(None).unwrap_option_or("None").into_result(),
+        Err("None")
+    );
+    assert_eq!(
+        Err::<Option<i32>, _>("Inner")
+            .unwrap_option_or("None")
+            .into_result(),
+        Err("Inner")
+    );
+}
+
+#[test]
+fn unwrap_option_or_else() {
+    assert_eq!(
+        maybe(Some(1))
+            .unwrap_option_or_else(|| unreachable!())
+            .into_result(),
+        Ok(1)
+    );
+    assert_eq!(
+        maybe(None).unwrap_option_or_else(|| "None").into_result(),
+        Err("None")
+    );
+}
+
+#[derive(Debug, PartialEq)]
+enum LookupError {
+    Missing(&'static str),
+    Negative(i32),
+}
+
+#[iex]
+fn lookup(values: &[(&'static str, i32)], key: &'static str) -> Result<i32, LookupError> {
+    let value = values
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, v)| *v)
+        .ok_or_iex(LookupError::Missing(key))?;
+    if value < 0 {
+        return Err(LookupError::Negative(value));
+    }
+    Ok(value)
+}
+
+#[test]
+fn ok_or_iex() {
+    let values = [("a", 1), ("b", -1)];
+    assert_eq!(lookup(&values, "a").into_result(), Ok(1));
+    assert_eq!(
+        lookup(&values, "b").into_result(),
+        Err(LookupError::Negative(-1))
+    );
+    assert_eq!(
+        lookup(&values, "c").into_result(),
+        Err(LookupError::Missing("c"))
+    );
+    assert_eq!(Some(1).ok_or_iex("None").into_result(), Ok(1));
+    assert_eq!(None::<i32>.ok_or_iex("None").into_result(), Err("None"));
+}
+
+#[iex]
+fn missing(key: &'static str) -> Result<LookupError, String> {
+    Ok(LookupError::Missing(key))
+}
+
+#[test]
+fn ok_or_else_iex() {
+    let called = Cell::new(false);
+    let lazy = |value: Option<i32>| {
+        value
+            .ok_or_else_iex(|| {
+                called.set(true);
+                "None"
+            })
+            .into_result()
+    };
+    assert_eq!(lazy(Some(1)), Ok(1));
+    assert!(!called.get());
+    assert_eq!(lazy(None), Err("None"));
+    assert!(called.get());
+
+    // The closure can resolve #[iex] functions
+    assert_eq!(
+        None::<i32>
+            .ok_or_else_iex(|| missing("key").into_result().unwrap())
+            .into_result(),
+        Err(LookupError::Missing("key"))
+    );
+}
+
+#[derive(Debug, PartialEq)]
+enum ConversionError {
+    Inner(&'static str),
+    OutOfRange,
+}
+
+impl From<std::num::TryFromIntError> for ConversionError {
+    fn from(_: std::num::TryFromIntError) -> Self {
+        Self::OutOfRange
+    }
+}
+
+impl From<std::convert::Infallible> for ConversionError {
+    fn from(err: std::convert::Infallible) -> Self {
+        match err {}
+    }
+}
+
+#[iex]
+fn first_wide(items: &[i64]) -> Result<i64, ConversionError> {
+    items
+        .first()
+        .copied()
+        .ok_or(ConversionError::Inner("Empty"))
+}
+
+#[test]
+fn try_into_output() {
+    assert_eq!(
+        first_wide(&[7]).try_into_output::<u8>().into_result(),
+        Ok(7)
+    );
+    assert_eq!(
+        first_wide(&[-7]).try_into_output::<u8>().into_result(),
+        Err(ConversionError::OutOfRange)
+    );
+    assert_eq!(
+        first_wide(&[]).try_into_output::<u8>().into_result(),
+        Err(ConversionError::Inner("Empty"))
+    );
+    // Infallible conversions work too
+    assert_eq!(
+        Ok::<u8, ConversionError>(1)
+            .try_into_output::<i64>()
+            .into_result(),
+        Ok(1)
+    );
+}
+
+#[iex]
+fn first_byte(items: &[i64]) -> Result<Result<u8, std::num::TryFromIntError>, ConversionError> {
+    Ok(u8::try_from(first_wide(items)?))
+}
+
+#[test]
+fn flatten_result() {
+    assert_eq!(first_byte(&[7]).flatten_result().into_result(), Ok(7));
+    assert_eq!(
+        first_byte(&[-7]).flatten_result().into_result(),
+        Err(ConversionError::OutOfRange)
+    );
+    assert_eq!(
+        first_byte(&[]).flatten_result().into_result(),
+        Err(ConversionError::Inner("Empty"))
+    );
+}
+
+#[iex]
+fn double<O: Outcome<Output = i32>>(outcome: O) -> Result<i32, O::Error> {
+    Ok(iex::apply(outcome, |value| value * 2)?)
+}
+
+#[test]
+fn apply() {
+    assert_eq!(double(first_number(&[3]).copied()).into_result(), Ok(6));
+    assert_eq!(double(first_number(&[]).copied()).into_result(), Err("Empty"));
+    assert_eq!(double(Ok::<_, ()>(4)).into_result(), Ok(8));
+    assert_eq!(
+        iex::apply(first_owned(&[]), |s| s.len()).into_result(),
+        Err("Empty")
+    );
+}
+
+struct DropCounter<'a>(&'a Cell<u32>);
+
+impl Drop for DropCounter<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+#[iex]
+fn validate<'a>(drops: &'a Cell<u32>, valid: bool) -> Result<DropCounter<'a>, &'static str> {
+    if valid {
+        Ok(DropCounter(drops))
+    } else {
+        Err("Invalid")
+    }
+}
+
+#[iex]
+fn validates(drops: &Cell<u32>, valid: bool) -> Result<u32, &'static str> {
+    validate(drops, valid).discard()?;
+    Ok(drops.get())
+}
+
+#[test]
+fn discard() {
+    let drops = Cell::new(0);
+    assert_eq!(validates(&drops, true).into_result(), Ok(1));
+    assert_eq!(validates(&drops, false).into_result(), Err("Invalid"));
+    assert_eq!(drops.get(), 1);
+}
+
+#[iex]
+fn validated(numbers: &[i32]) -> Result<i32, &'static str> {
+    first_number(numbers).try_map(|&x| if x > 0 { Ok(x) } else { Err("Non-positive") })
+}
+
+#[test]
+fn try_map() {
+    assert_eq!(validated(&[1]).into_result(), Ok(1));
+    assert_eq!(validated(&[0]).into_result(), Err("Non-positive"));
+    assert_eq!(validated(&[]).into_result(), Err("Empty"));
+}
+
+// A function that hasn't been migrated to #[iex]
+fn positive(x: &i32) -> Result<i32, &'static str> {
+    if *x > 0 {
+        Ok(*x)
+    } else {
+        Err("Non-positive")
+    }
+}
+
+#[iex]
+fn doubled(numbers: &[i32]) -> Result<i32, &'static str> {
+    let x = first_number(numbers).and_then_result(positive)?;
+    Ok(x * 2)
+}
+
+#[test]
+fn and_then_result() {
+    assert_eq!(doubled(&[1]).into_result(), Ok(2));
+    assert_eq!(doubled(&[0]).into_result(), Err("Non-positive"));
+    assert_eq!(doubled(&[]).into_result(), Err("Empty"));
+    assert_eq!(Ok(&3).and_then_result(positive).into_result(), Ok(3));
+    assert_eq!(
+        Err("Empty").and_then_result(positive).into_result(),
+        Err("Empty")
+    );
+}
+
+#[test]
+fn inspect() {
+    let mut seen = Vec::new();
+    assert_eq!(
+        first_number(&[1, 2])
+            .inspect(|&&x| seen.push(x))
+            .into_result(),
+        Ok(&1)
+    );
+    assert_eq!(
+        first_number(&[]).inspect(|_| unreachable!()).into_result(),
+        Err("Empty")
+    );
+    assert_eq!(seen, [1]);
+}
+
+#[iex]
+fn traced<'a>(
+    log: &'a RefCell<Vec<&'static str>>,
+    name: &'static str,
+    ok: bool,
+) -> Result<i32, &'static str> {
+    log.borrow_mut().push(name);
+    if ok {
+        Ok(name.len() as i32)
+    } else {
+        Err(name)
+    }
+}
+
+#[test]
+fn zip_with() {
+    let log = RefCell::new(Vec::new());
+    let combine = |a, b| {
+        log.borrow_mut().push("combine");
+        a * 10 + b
+    };
+
+    let result = traced(&log, "a", true).zip_with(traced(&log, "bc", true), combine);
+    assert_eq!(result.into_result(), Ok(12));
+    assert_eq!(log.take(), ["a", "bc", "combine"]);
+
+    let result = traced(&log, "a", false).zip_with(traced(&log, "b", true), combine);
+    assert_eq!(result.into_result(), Err("a"));
+    assert_eq!(log.take(), ["a"]);
+
+    let result = traced(&log, "a", true).zip_with(traced(&log, "b", false), combine);
+    assert_eq!(result.into_result(), Err("b"));
+    assert_eq!(log.take(), ["a", "b"]);
+}
+
+#[test]
+fn unwrap_or_default_with() {
+    let calls = Cell::new(0);
+    let fresh = || {
+        calls.set(calls.get() + 1);
+        &-1
+    };
+    assert_eq!(first_number(&[1]).unwrap_or_default_with(fresh), &1);
+    assert_eq!(calls.get(), 0);
+    assert_eq!(first_number(&[]).unwrap_or_default_with(fresh), &-1);
+    assert_eq!(calls.get(), 1);
+    // The error is handled, so the next outcome starts from a clean state
+    assert_eq!(first_number(&[2]).into_result(), Ok(&2));
+}
@@ -0,0 +1,84 @@
+use iex::{iex, Outcome};
+
+#[derive(Debug, PartialEq)]
+struct MyError(&'static str);
+
+type MyResult<T> = Result<T, MyError>;
+
+type DefaultedResult<T, E = MyError> = Result<T, E>;
+
+mod errors {
+    pub type Result<T> = std::result::Result<T, super::MyError>;
+}
+
+#[iex]
+fn plain(fail: bool) -> MyResult<u8> {
+    if fail {
+        Err(MyError("plain"))
+    } else {
+        Ok(1)
+    }
+}
+
+#[iex]
+fn defaulted(fail: bool) -> DefaultedResult<u8> {
+    if fail {
+        Err(MyError("defaulted"))
+    } else {
+        Ok(plain(false)? + 1)
+    }
+}
+
+#[iex]
+fn overridden(fail: bool) -> DefaultedResult<u8, String> {
+    defaulted(fail).map_err(|err| err.0.to_string())
+}
+
+#[iex]
+fn shadowing(fail: bool) -> errors::Result<u8> {
+    Ok(plain(fail)? + defaulted(false)?)
+}
+
+#[iex]
+fn generic<T: Default>() -> MyResult<T> {
+    Ok(T::default())
+}
+
+#[iex]
+fn borrowing<'a>(s: &'a str) -> MyResult<&'a str> {
+    Ok(s)
+}
+
+#[test]
+fn aliases() {
+    assert_eq!(plain(false).into_result(), Ok(1));
+    assert_eq!(plain(true).into_result(), Err(MyError("plain")));
+    assert_eq!(defaulted(false).into_result(), Ok(2));
+    assert_eq!(defaulted(true).into_result(), Err(MyError("defaulted")));
+    assert_eq!(overridden(true).into_result(), Err("defaulted".to_string()));
+    assert_eq!(shadowing(false).into_result(), Ok(3));
+    assert_eq!(shadowing(true).into_result(), Err(MyError("plain")));
+    assert_eq!(generic::<i32>().into_result(), Ok(0));
+    assert_eq!(borrowing("a").into_result(), Ok("a"));
+}
+
+trait Parser {
+    type Error;
+
+    #[iex]
+    fn parse(&self) -> DefaultedResult<u8, Self::Error>;
+}
+
+impl Parser for () {
+    type Error = MyError;
+
+    #[iex]
+    fn parse(&self) -> MyResult<u8> {
+        Err(MyError("trait"))
+    }
+}
+
+#[test]
+fn trait_aliases() {
+    assert_eq!(().parse().into_result(), Err(MyError("trait")));
+}
@@ -0,0 +1,93 @@
+use iex::{as_result, iex, Outcome};
+
+#[iex(also_result)]
+fn checked_divide(a: u32, b: u32) -> Result<u32, &'static str> {
+    a.checked_div(b).ok_or("Cannot divide by zero")
+}
+
+#[iex(also_result)]
+fn divide_all(a: u32, bs: &[u32]) -> Result<Vec<u32>, String> {
+    let mut results = Vec::new();
+    for &b in bs {
+        results.push(checked_divide(a, b)?);
+    }
+    let s = "Shared".to_string();
+    checked_divide(a, 1).map_err(
+        #[iex(shares = s)]
+        |err| {
+            let _s: String = s;
+            err
+        },
+    )?;
+    Ok(results)
+}
+
+#[test]
+fn twin_matches() {
+    for bs in [&[1, 2][..], &[1, 0, 2]] {
+        assert_eq!(divide_all(6, bs).into_result(), divide_all_as_result(6, bs));
+    }
+    assert_eq!(checked_divide_as_result(6, 0), Err("Cannot divide by zero"));
+}
+
+trait Parser {
+    #[iex(also_result)]
+    fn parse(&self, s: &'static str) -> Result<u32, String>;
+
+    #[iex(also_result)]
+    fn parse_twice(&self, s: &'static str) -> Result<u32, String> {
+        Ok(self.parse(s)? + self.parse(s)?)
+    }
+}
+
+struct Decimal;
+
+impl Parser for Decimal {
+    #[iex(also_result)]
+    fn parse(&self, s: &'static str) -> Result<u32, String> {
+        s.parse().map_err(|_| format!("Invalid number: {s}"))
+    }
+}
+
+#[test]
+fn trait_twin() {
+    assert_eq!(Decimal.parse_twice_as_result("2"), Ok(4));
+    assert_eq!(
+        Decimal.parse_twice_as_result("x"),
+        Decimal.parse_twice("x").into_result()
+    );
+}
+
+mod math {
+    use iex::iex;
+
+    #[iex(also_result)]
+    pub fn first<T: Copy>(items: &[T]) -> Result<T, &'static str> {
+        items.first().copied().ok_or("Empty")
+    }
+}
+
+#[iex]
+fn first_quotient(a: u32, bs: &[u32]) -> Result<u32, &'static str> {
+    let b = as_result!(math::first::<u32>(bs))?;
+    as_result!(checked_divide(a, b))
+}
+
+#[test]
+fn call_site() {
+    assert_eq!(as_result!(checked_divide(6, 2)), Ok(3));
+    assert_eq!(
+        as_result!(math::first(&[1, 2])),
+        math::first(&[1, 2]).into_result()
+    );
+    assert_eq!(
+        as_result!(Decimal.parse_twice("x")),
+        Err("Invalid number: x".to_string())
+    );
+    assert_eq!(first_quotient(6, &[3]).into_result(), Ok(2));
+    assert_eq!(
+        first_quotient(6, &[0]).into_result(),
+        Err("Cannot divide by zero")
+    );
+    assert_eq!(first_quotient(6, &[]).into_result(), Err("Empty"));
+}
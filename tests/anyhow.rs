@@ -1,5 +1,5 @@
 use anyhow::{anyhow, bail, Result};
-use iex::{iex, Context, Outcome};
+use iex::{ctx, iex, try_block, Context, Outcome};
 
 #[iex]
 fn returns_anyhow_error() -> Result<()> {
@@ -26,3 +26,210 @@ fn iex_matches_result() {
 fn option_works() {
     let _: Result<()> = None.context("Meow");
 }
+
+#[iex]
+fn returns_ok() -> Result<i32> {
+    Ok(1)
+}
+
+#[iex]
+fn with_context_is_lazy() -> Result<i32> {
+    returns_ok().with_context(|| -> String { panic!("context evaluated on the happy path") })
+}
+
+#[test]
+fn with_context_lazy() {
+    assert_eq!(with_context_is_lazy().into_result().unwrap(), 1);
+    assert_eq!(
+        Ok::<_, anyhow::Error>(1)
+            .with_context(|| -> String { panic!("context evaluated on the happy path") })
+            .into_result()
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        format!(
+            "{:#}",
+            returns_anyhow_error()
+                .with_context(|| format!("In {}", "with_context_lazy()"))
+                .into_result()
+                .unwrap_err()
+        ),
+        r"In with_context_lazy(): ¯\_(ツ)_/¯",
+    );
+}
+
+#[iex(also_result)]
+fn uses_ctx() -> Result<()> {
+    ctx!(returns_anyhow_error(), "In uses_ctx()");
+    Ok(())
+}
+
+#[iex]
+fn uses_ctx_in_expression(fail: bool) -> Result<i32> {
+    let value = iex::ctx!(Ok::<_, anyhow::Error>(returns_ok()? + 1), "Unreachable");
+    if fail {
+        iex::ctx!(returns_anyhow_error(), format!("After computing {value}"));
+    }
+    try_block! {
+        ctx!(returns_ok(), "Unreachable") + value
+    }
+    .into_result()
+}
+
+#[test]
+fn ctx() {
+    assert_eq!(
+        format!("{:#}", uses_ctx().into_result().unwrap_err()),
+        r"In uses_ctx(): ¯\_(ツ)_/¯",
+    );
+    assert_eq!(
+        format!("{:#}", uses_ctx_as_result().unwrap_err()),
+        r"In uses_ctx(): ¯\_(ツ)_/¯",
+    );
+    assert_eq!(uses_ctx_in_expression(false).into_result().unwrap(), 3);
+    assert_eq!(
+        format!(
+            "{:#}",
+            uses_ctx_in_expression(true).into_result().unwrap_err()
+        ),
+        r"After computing 2: ¯\_(ツ)_/¯",
+    );
+}
+
+// The return types below are spelled out as `anyhow::Result` on purpose, without the import.
+
+#[iex]
+fn parse_std(s: &str) -> std::result::Result<i32, std::num::ParseIntError> {
+    s.parse()
+}
+
+#[iex]
+fn parse_generic<T: std::str::FromStr>(s: &str) -> anyhow::Result<T>
+where
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    Ok(s.parse::<T>()?)
+}
+
+#[iex]
+fn sum_all(a: &str, b: &str, c: &str) -> anyhow::Result<i32> {
+    // A plain Result with a std error. The turbofish is required, see the docs of #[iex]
+    let a = a.parse::<i32>()?;
+    // An #[iex] Result with a std error
+    let b = parse_std(b)?;
+    // An #[iex] Result with an anyhow error
+    let c = parse_generic::<i32>(c)?;
+    Ok(a + b + c)
+}
+
+#[iex]
+fn forwards(s: &str) -> anyhow::Result<i32, anyhow::Error> {
+    parse_generic(s)
+}
+
+trait Source {
+    #[iex]
+    fn read(&self) -> anyhow::Result<String>;
+}
+
+impl Source for &str {
+    #[iex]
+    fn read(&self) -> anyhow::Result<String> {
+        if self.is_empty() {
+            bail!("Empty source");
+        }
+        Ok(self.to_string())
+    }
+}
+
+#[test]
+fn anyhow_result_path() {
+    assert_eq!(sum_all("1", "2", "3").into_result().unwrap(), 6);
+    for (a, b, c) in [("x", "2", "3"), ("1", "x", "3"), ("1", "2", "x")] {
+        assert_eq!(
+            sum_all(a, b, c).into_result().unwrap_err().to_string(),
+            "invalid digit found in string"
+        );
+    }
+    assert_eq!(parse_generic::<u8>("7").into_result().unwrap(), 7);
+    assert!(parse_generic::<u8>("300").into_result().is_err());
+    assert_eq!(forwards("5").into_result().unwrap(), 5);
+    assert_eq!("abc".read().into_result().unwrap(), "abc");
+    assert_eq!(
+        "".read().into_result().unwrap_err().to_string(),
+        "Empty source"
+    );
+}
+
+#[derive(Debug, PartialEq)]
+enum Fetch {
+    Missing,
+    Broken,
+}
+
+impl std::fmt::Display for Fetch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl std::error::Error for Fetch {}
+
+#[iex]
+fn fetch(result: std::result::Result<i32, Fetch>) -> std::result::Result<i32, Fetch> {
+    result
+}
+
+#[iex]
+fn fetch_or_default(result: std::result::Result<i32, Fetch>) -> Result<i32> {
+    Ok(
+        fetch(result).context_or_recover("In fetch_or_default()", |err| {
+            (*err == Fetch::Missing).then_some(0)
+        })? + 1,
+    )
+}
+
+#[test]
+fn context_or_recover() {
+    assert_eq!(fetch_or_default(Ok(2)).into_result().unwrap(), 3);
+    assert_eq!(
+        fetch_or_default(Err(Fetch::Missing)).into_result().unwrap(),
+        1
+    );
+    let err = fetch_or_default(Err(Fetch::Broken))
+        .into_result()
+        .unwrap_err();
+    assert_eq!(format!("{err:#}"), "In fetch_or_default(): Broken");
+    assert_eq!(err.downcast_ref::<Fetch>(), Some(&Fetch::Broken));
+}
+
+// The tail is an outcome, so anyhow's `bail!` and `ensure!` have to throw the error
+#[iex]
+fn checked_forward(s: &str) -> Result<i32> {
+    anyhow::ensure!(!s.is_empty());
+    if s == "-" {
+        bail!("Just a sign");
+    }
+    anyhow::ensure!(s.len() < 5, "{s} is too long");
+    forwards(s)
+}
+
+#[test]
+fn anyhow_bail_and_ensure() {
+    assert_eq!(checked_forward("12").into_result().unwrap(), 12);
+    for (input, message) in [
+        ("", "Condition failed: `!s.is_empty()`"),
+        ("-", "Just a sign"),
+        ("12345", "12345 is too long"),
+        ("x", "invalid digit found in string"),
+    ] {
+        assert_eq!(
+            checked_forward(input)
+                .into_result()
+                .unwrap_err()
+                .to_string(),
+            message
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use iex::{iex, Outcome};
+
+#[iex]
+fn check(x: i32) -> Result<i32, i32> {
+    if x < 0 {
+        Err(x)
+    } else {
+        Ok(x + 1)
+    }
+}
+
+#[iex]
+fn nested(x: i32) -> Result<i32, i32> {
+    Ok(check(x)? * 2)
+}
+
+#[test]
+fn succeeds() {
+    unsafe {
+        assert_eq!(check(1).assume_ok(), 2);
+        assert_eq!(nested(1).assume_ok(), 4);
+        assert_eq!(Ok::<i32, i32>(3).assume_ok(), 3);
+    }
+}
+
+// In release builds, failing outcomes are UB, so only the debug check can be tested
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "assume_ok on a failing outcome")]
+fn fails_in_debug() {
+    let _ = unsafe { nested(-1).assume_ok() };
+}
+
+#[test]
+fn assume_err() {
+    unsafe {
+        assert_eq!(check(-1).assume_err(), -1);
+        assert_eq!(nested(-2).assume_err(), -2);
+        assert_eq!(Err::<i32, i32>(-3).assume_err(), -3);
+    }
+    // The error is fully handled, so it doesn't leak into later calls
+    assert_eq!(nested(1).into_result(), Ok(4));
+    assert_eq!(nested(-4).into_result(), Err(-4));
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "assume_err on a successful outcome")]
+fn assume_err_succeeds_in_debug() {
+    let _ = unsafe { nested(1).assume_err() };
+}
@@ -0,0 +1,54 @@
+#![deny(unused_attributes, unfulfilled_lint_expectations)]
+#![deny(clippy::too_many_arguments, clippy::double_must_use)]
+
+use iex::{iex, Outcome};
+
+// Redundant, since outcomes are #[must_use] anyway, but should still be accepted
+#[allow(clippy::double_must_use)]
+#[must_use]
+#[iex]
+fn must_use() -> Result<i32, ()> {
+    Ok(1)
+}
+
+#[iex]
+#[must_use = "with a message"]
+fn must_use_with_message() -> Result<i32, ()> {
+    Ok(2)
+}
+
+#[test]
+fn must_use_is_forwarded() {
+    assert_eq!(must_use().into_result(), Ok(1));
+    assert_eq!(must_use_with_message().into_result(), Ok(2));
+}
+
+// Fires on the signature
+#[allow(clippy::too_many_arguments)]
+#[iex]
+fn many_arguments(
+    a: i32,
+    b: i32,
+    c: i32,
+    d: i32,
+    e: i32,
+    f: i32,
+    g: i32,
+    h: i32,
+) -> Result<i32, ()> {
+    Ok(a + b + c + d + e + f + g + h)
+}
+
+// Fires in the body
+#[expect(unused_variables)]
+#[iex]
+fn unused_variable(x: i32) -> Result<i32, ()> {
+    let y = x;
+    Ok(x)
+}
+
+#[test]
+fn lint_attributes_are_forwarded() {
+    assert_eq!(many_arguments(1, 2, 3, 4, 5, 6, 7, 8).into_result(), Ok(36));
+    assert_eq!(unused_variable(1).into_result(), Ok(1));
+}
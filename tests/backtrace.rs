@@ -0,0 +1,75 @@
+use iex::{iex, Outcome};
+use std::backtrace::Backtrace;
+
+#[iex]
+fn captures_backtrace() -> Result<String, ()> {
+    Ok(Backtrace::force_capture().to_string())
+}
+
+#[iex]
+fn calls_capture() -> Result<String, ()> {
+    Ok(captures_backtrace()?)
+}
+
+#[test]
+fn frames_are_named_after_function() {
+    let backtrace = calls_capture().into_result().unwrap();
+    // Either `{{closure}}` or `{closure#0}`, depending on the mangling scheme
+    assert!(backtrace.contains("backtrace::captures_backtrace::{"));
+    assert!(backtrace.contains("backtrace::calls_capture::{"));
+}
+
+#[iex(named_body)]
+fn captures_named_backtrace() -> Result<String, ()> {
+    Ok(Backtrace::force_capture().to_string())
+}
+
+#[iex(named_body)]
+fn calls_named_capture() -> Result<String, ()> {
+    Ok(captures_named_backtrace()?)
+}
+
+#[test]
+fn named_body_frames() {
+    let backtrace = calls_named_capture().into_result().unwrap();
+    assert!(backtrace.contains("backtrace::captures_named_backtrace::__iex_body"));
+    assert!(backtrace.contains("backtrace::calls_named_capture::__iex_body"));
+}
+
+#[iex(named_body, fn_ptr)]
+fn divide_pair<T: TryFrom<u32>>((a, b): (u32, u32), mut scale: u32) -> Result<T, String> {
+    scale *= a.checked_div(b).ok_or("Cannot divide by zero")?;
+    T::try_from(scale).map_err(|_| "Out of range".to_string())
+}
+
+#[iex(named_body)]
+fn repeat<'a, const N: usize>(s: impl AsRef<str>, err: &'a str) -> Result<[String; N], &'a str> {
+    if s.as_ref().is_empty() {
+        return Err(err);
+    }
+    Ok(std::array::from_fn(|_| s.as_ref().to_string()))
+}
+
+#[iex(named_body)]
+unsafe fn read(ptr: *const u8) -> Result<u8, ()> {
+    Ok(*ptr)
+}
+
+#[test]
+fn named_body_signatures() {
+    assert_eq!(divide_pair::<u8>((6, 2), 5).into_result(), Ok(15));
+    assert_eq!(
+        divide_pair::<u8>((6, 0), 5).into_result(),
+        Err("Cannot divide by zero".to_string())
+    );
+    assert_eq!(
+        divide_pair_fn_ptr::<u8>((6, 1), 50),
+        Err("Out of range".to_string())
+    );
+    assert_eq!(
+        repeat::<2>("a", "empty").into_result(),
+        Ok(["a".to_string(), "a".to_string()])
+    );
+    assert_eq!(repeat::<2>("", "empty").into_result(), Err("empty"));
+    assert_eq!(unsafe { read(&7) }.into_result(), Ok(7));
+}
@@ -0,0 +1,180 @@
+use iex::{bail, ensure, iex, try_block, Outcome};
+
+#[derive(Debug, PartialEq)]
+enum LowLevelError {
+    Eof,
+    Invalid(u8),
+}
+
+#[derive(Debug, PartialEq)]
+enum HighLevelError {
+    Read(LowLevelError),
+    TooLong(usize),
+}
+
+impl From<LowLevelError> for HighLevelError {
+    fn from(err: LowLevelError) -> Self {
+        Self::Read(err)
+    }
+}
+
+#[iex]
+fn read_byte(input: &[u8], pos: usize) -> Result<u8, LowLevelError> {
+    let Some(&byte) = input.get(pos) else {
+        bail!(LowLevelError::Eof);
+    };
+    ensure!(byte.is_ascii_digit(), LowLevelError::Invalid(byte));
+    Ok(byte - b'0')
+}
+
+#[iex(also_result)]
+fn read_number(input: &[u8]) -> Result<u32, HighLevelError> {
+    // Converted via From
+    ensure!(!input.is_empty(), LowLevelError::Eof);
+    ensure!(input.len() <= 9, HighLevelError::TooLong(input.len()));
+    let mut number = 0;
+    for pos in 0..input.len() {
+        number = number * 10 + read_byte(input, pos)? as u32;
+    }
+    Ok(number)
+}
+
+// The tail is an outcome, so `return Err(..)` wouldn't compile here
+#[iex]
+fn read_first(input: &[u8]) -> Result<u8, HighLevelError> {
+    if input.len() > 9 {
+        bail!(HighLevelError::TooLong(input.len()));
+    }
+    ensure!(input.len() != 1, LowLevelError::Invalid(input[0]));
+    read_byte(input, 0).map_err(HighLevelError::Read)
+}
+
+#[iex]
+fn always_fails(input: &[u8]) -> Result<u8, HighLevelError> {
+    let _ = input;
+    bail!(LowLevelError::Eof)
+}
+
+#[test]
+fn conversions() {
+    assert_eq!(read_number(b"123").into_result(), Ok(123));
+    assert_eq!(
+        read_number(b"").into_result(),
+        Err(HighLevelError::Read(LowLevelError::Eof))
+    );
+    assert_eq!(
+        read_number(b"1234567890").into_result(),
+        Err(HighLevelError::TooLong(10))
+    );
+    assert_eq!(
+        read_number(b"12x").into_result(),
+        Err(HighLevelError::Read(LowLevelError::Invalid(b'x')))
+    );
+    assert_eq!(
+        read_number_as_result(b"12x"),
+        read_number(b"12x").into_result()
+    );
+    assert_eq!(
+        always_fails(b"").into_result(),
+        Err(HighLevelError::Read(LowLevelError::Eof))
+    );
+}
+
+#[test]
+fn outcome_tail() {
+    assert_eq!(read_first(b"42").into_result(), Ok(4));
+    assert_eq!(
+        read_first(b"1234567890").into_result(),
+        Err(HighLevelError::TooLong(10))
+    );
+    assert_eq!(
+        read_first(b"x").into_result(),
+        Err(HighLevelError::Read(LowLevelError::Invalid(b'x')))
+    );
+    assert_eq!(
+        read_first(b"").into_result(),
+        Err(HighLevelError::Read(LowLevelError::Eof))
+    );
+}
+
+#[test]
+fn plain_functions() {
+    fn check(x: i32) -> Result<i32, String> {
+        ensure!(x >= 0, "negative");
+        if x > 100 {
+            bail!(format!("{x} is too large"));
+        }
+        Ok(x)
+    }
+
+    assert_eq!(check(1), Ok(1));
+    assert_eq!(check(-1), Err("negative".to_string()));
+    assert_eq!(check(101), Err("101 is too large".to_string()));
+}
+
+#[test]
+fn in_try_block() {
+    let pair = |input: &'static [u8]| {
+        try_block! {
+            ensure!(input.len() == 2, HighLevelError::TooLong(input.len()));
+            read_first(input)?
+        }
+        .into_result()
+    };
+    assert_eq!(pair(b"78"), Ok(7));
+    assert_eq!(pair(b"789"), Err(HighLevelError::TooLong(3)));
+    assert_eq!(
+        pair(b"x8"),
+        Err(HighLevelError::Read(LowLevelError::Invalid(b'x')))
+    );
+}
+
+struct Shared {
+    v: Vec<u8>,
+}
+
+#[iex]
+fn ensure_then_reuse(s: Shared) -> Result<usize, Vec<u8>> {
+    // `s.v` is only moved if the condition fails
+    iex::ensure!(!s.v.is_empty(), s.v);
+    println!("{:?}", s.v);
+    Ok(s.v.len())
+}
+
+#[test]
+fn ensure_moves_only_on_failure() {
+    assert_eq!(
+        ensure_then_reuse(Shared { v: vec![1, 2] }).into_result(),
+        Ok(2)
+    );
+    assert_eq!(
+        ensure_then_reuse(Shared { v: vec![] }).into_result(),
+        Err(vec![])
+    );
+}
+
+mod other {
+    // Not ours, so `#[iex]` must leave it alone
+    macro_rules! bail {
+        ($value:expr) => {
+            $value + 1
+        };
+    }
+    pub(crate) use bail;
+}
+
+#[iex]
+fn other_bail(x: i32) -> Result<i32, String> {
+    let y = other::bail!(x);
+    ensure!(y > 0, format!("{y} is not positive"));
+    Ok(y)
+}
+
+#[test]
+fn other_macros_named_bail() {
+    assert_eq!(other_bail(1).into_result(), Ok(2));
+    assert_eq!(
+        other_bail(-1).into_result(),
+        Err("0 is not positive".to_string())
+    );
+}
@@ -0,0 +1,64 @@
+use iex::{iex, CachedOutcome, Outcome};
+use std::cell::Cell;
+
+#[iex]
+fn compute(x: i32, calls: &Cell<u32>) -> Result<i32, String> {
+    calls.set(calls.get() + 1);
+    if x < 0 {
+        Err(format!("{x} is negative"))
+    } else {
+        Ok(x * 2)
+    }
+}
+
+#[iex]
+fn uses_cached<F>(cached: &CachedOutcome<i32, String, F>) -> Result<i32, String>
+where
+    F: FnOnce() -> Result<i32, String>,
+{
+    Ok(cached.get()? + cached.get()?)
+}
+
+#[test]
+fn computes_once() {
+    let calls = Cell::new(0);
+    let cached = CachedOutcome::new(|| compute(1, &calls));
+    assert_eq!(calls.get(), 0);
+    assert_eq!(cached.peek(), None);
+    assert_eq!(cached.get().into_result(), Ok(2));
+    assert_eq!(cached.get().into_result(), Ok(2));
+    assert_eq!(cached.peek(), Some(&Ok(2)));
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn caches_errors() {
+    let calls = Cell::new(0);
+    let cached = CachedOutcome::new(|| compute(-1, &calls));
+    assert_eq!(
+        cached.get().into_result(),
+        Err("-1 is negative".to_string())
+    );
+    assert_eq!(
+        cached.get().into_result(),
+        Err("-1 is negative".to_string())
+    );
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn propagates_through_iex() {
+    let cached = CachedOutcome::new(|| Ok(3));
+    assert_eq!(uses_cached(&cached).into_result(), Ok(6));
+    let cached = CachedOutcome::new(|| Err("Nope".to_string()));
+    assert_eq!(uses_cached(&cached).into_result(), Err("Nope".to_string()));
+}
+
+#[test]
+fn send() {
+    let cached = CachedOutcome::new(|| Ok::<_, ()>(1));
+    let result = std::thread::spawn(move || cached.get().into_result())
+        .join()
+        .unwrap();
+    assert_eq!(result, Ok(1));
+}
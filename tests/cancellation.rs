@@ -0,0 +1,135 @@
+use iex::{iex, CancellationToken, Cancelled, Outcome};
+use std::sync::mpsc;
+
+#[derive(Debug, PartialEq)]
+enum EvalError {
+    Negative(i64),
+    Cancelled,
+}
+
+impl From<Cancelled> for EvalError {
+    fn from(_: Cancelled) -> Self {
+        Self::Cancelled
+    }
+}
+
+#[iex]
+fn eval(
+    values: &[i64],
+    token: &CancellationToken,
+    cancel_at: Option<usize>,
+) -> Result<i64, EvalError> {
+    let mut sum = 0;
+    for (i, &value) in values.iter().enumerate() {
+        if cancel_at == Some(i) {
+            token.cancel();
+        }
+        token.check()?;
+        if value < 0 {
+            return Err(EvalError::Negative(value));
+        }
+        sum += value;
+    }
+    Ok(sum)
+}
+
+#[test]
+fn check() {
+    let token = CancellationToken::new();
+    assert_eq!(token.check(), Ok(()));
+    assert!(!token.is_cancelled());
+    let clone = token.clone();
+    clone.cancel();
+    assert!(token.is_cancelled());
+    assert_eq!(token.check(), Err(Cancelled));
+    assert_eq!(Cancelled.to_string(), "operation cancelled");
+}
+
+#[test]
+fn checkpoints() {
+    let token = CancellationToken::new();
+    assert_eq!(eval(&[1, 2, 3], &token, None).into_result(), Ok(6));
+    assert_eq!(
+        eval(&[1, -2, 3], &token, None).into_result(),
+        Err(EvalError::Negative(-2))
+    );
+    assert_eq!(
+        eval(&[1, 2, 3], &token, Some(1)).into_result(),
+        Err(EvalError::Cancelled)
+    );
+    #[cfg(feature = "testing")]
+    assert!(iex::testing::exception_is_clear());
+}
+
+#[test]
+fn with_cancellation() {
+    let token = CancellationToken::new();
+    let idle = CancellationToken::new();
+    // The outcome doesn't check `token` itself
+    assert_eq!(
+        eval(&[1, 2], &idle, None)
+            .with_cancellation(&token)
+            .into_result(),
+        Ok(3)
+    );
+    assert_eq!(
+        eval(&[-1], &idle, None)
+            .with_cancellation(&token)
+            .into_result(),
+        Err(EvalError::Negative(-1))
+    );
+    // Cancelled while driving: the output is discarded
+    assert_eq!(
+        iex::from_fn(|| {
+            token.cancel();
+            Ok::<i64, EvalError>(0)
+        })
+        .with_cancellation(&token)
+        .into_result(),
+        Err(EvalError::Cancelled)
+    );
+    // Already cancelled: the outcome is not driven
+    let driven = std::cell::Cell::new(false);
+    assert_eq!(
+        iex::from_fn(|| {
+            driven.set(true);
+            Ok::<i64, EvalError>(0)
+        })
+        .with_cancellation(&token)
+        .into_result(),
+        Err(EvalError::Cancelled)
+    );
+    assert!(!driven.get());
+    #[cfg(feature = "testing")]
+    assert!(iex::testing::exception_is_clear());
+}
+
+#[iex]
+fn spin(token: &CancellationToken, started: mpsc::Sender<()>) -> Result<u64, Cancelled> {
+    started.send(()).unwrap();
+    let mut iterations = 0u64;
+    while iterations < u64::MAX {
+        token.check()?;
+        iterations += 1;
+        std::hint::spin_loop();
+    }
+    Ok(iterations)
+}
+
+#[test]
+fn cancel_from_another_thread() {
+    let token = CancellationToken::new();
+    let (started_tx, started_rx) = mpsc::channel();
+    let worker = std::thread::spawn({
+        let token = token.clone();
+        move || {
+            let result = spin(&token, started_tx).into_result();
+            #[cfg(feature = "testing")]
+            assert!(iex::testing::exception_is_clear());
+            result
+        }
+    });
+    started_rx.recv().unwrap();
+    token.cancel();
+    assert_eq!(worker.join().unwrap(), Err(Cancelled));
+}
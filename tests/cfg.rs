@@ -0,0 +1,164 @@
+use iex::{iex, Outcome};
+
+// `cfg(test)` is always enabled and `cfg(not(test))` is always disabled in tests, so these don't
+// depend on the platform
+
+#[iex]
+fn fails(err: i32) -> Result<i32, i32> {
+    Err(err)
+}
+
+#[cfg(test)]
+#[iex]
+fn outer_cfg() -> Result<i32, i32> {
+    Ok(1)
+}
+
+#[cfg(not(test))]
+#[iex]
+fn outer_cfg() -> Result<i32, i32> {
+    Ok(2)
+}
+
+#[iex]
+#[cfg(test)]
+fn inner_cfg() -> Result<i32, i32> {
+    Ok(1)
+}
+
+#[iex]
+#[cfg(not(test))]
+fn inner_cfg() -> Result<i32, i32> {
+    Ok(2)
+}
+
+#[iex]
+#[cfg(not(test))]
+fn removed() -> Result<i32, i32> {
+    compile_error!("removed by cfg")
+}
+
+#[iex(also_result)]
+#[cfg(test)]
+fn twin() -> Result<i32, i32> {
+    fails(1)?;
+    Ok(1)
+}
+
+#[iex(also_result)]
+#[cfg(not(test))]
+fn twin() -> Result<i32, i32> {
+    Ok(2)
+}
+
+#[iex]
+#[cfg_attr(test, inline)]
+#[cfg_attr(not(test), cfg(not(test)))]
+fn with_cfg_attr() -> Result<i32, i32> {
+    Ok(1)
+}
+
+#[test]
+fn cfg_on_function() {
+    assert_eq!(outer_cfg().into_result(), Ok(1));
+    assert_eq!(inner_cfg().into_result(), Ok(1));
+    assert_eq!(twin().into_result(), Err(1));
+    assert_eq!(twin_as_result(), Err(1));
+    assert_eq!(with_cfg_attr().into_result(), Ok(1));
+}
+
+struct Platform;
+
+impl Platform {
+    #[iex]
+    #[cfg(test)]
+    fn name(&self) -> Result<&'static str, i32> {
+        Ok("enabled")
+    }
+
+    #[iex]
+    #[cfg(not(test))]
+    fn name(&self) -> Result<&'static str, i32> {
+        Ok("disabled")
+    }
+}
+
+trait Named {
+    #[iex]
+    #[cfg(test)]
+    fn name(&self) -> Result<&'static str, i32>;
+
+    #[iex]
+    #[cfg(not(test))]
+    fn name(&self) -> Result<&'static str, i32>;
+}
+
+impl Named for i32 {
+    #[iex]
+    #[cfg(test)]
+    fn name(&self) -> Result<&'static str, i32> {
+        Err(*self)
+    }
+
+    #[iex]
+    #[cfg(not(test))]
+    fn name(&self) -> Result<&'static str, i32> {
+        Ok("disabled")
+    }
+}
+
+#[test]
+fn cfg_on_method() {
+    assert_eq!(Platform.name().into_result(), Ok("enabled"));
+    assert_eq!(Named::name(&5).into_result(), Err(5));
+}
+
+#[iex]
+fn cfg_in_body(x: i32) -> Result<i32, i32> {
+    #[cfg(not(test))]
+    return fails(-1);
+
+    #[cfg(test)]
+    let y = fails(x)?;
+    #[cfg(not(test))]
+    let y = 0;
+
+    #[cfg(not(test))]
+    {
+        fails(-2)?;
+    }
+
+    if cfg!(not(test)) {
+        fails(-3)?;
+    }
+
+    Ok(y)
+}
+
+#[iex]
+fn cfg_on_tail() -> Result<i32, i32> {
+    #[cfg(test)]
+    {
+        fails(1)?;
+        Ok(1)
+    }
+    #[cfg(not(test))]
+    {
+        Ok(2)
+    }
+}
+
+#[iex]
+fn cfg_on_unit_tail() -> Result<(), i32> {
+    #[cfg(test)]
+    fails(1)?;
+    #[cfg(not(test))]
+    fails(2)?;
+}
+
+#[test]
+fn cfg_inside_body() {
+    assert_eq!(cfg_in_body(1).into_result(), Err(1));
+    assert_eq!(cfg_on_tail().into_result(), Err(1));
+    assert_eq!(cfg_on_unit_tail().into_result(), Err(1));
+}
@@ -0,0 +1,96 @@
+// A size check only: an `#[iex] Result<u32, E>` must only store the captured arguments, regardless
+// of `E`, so the outcome itself has no room for an error or a discriminant. The machine code is not
+// inspected, so this does not prove that the happy path never builds a `Result` internally, and it
+// is not exposed to downstream crates.
+
+#![allow(clippy::result_large_err)]
+
+use iex::{iex, Outcome};
+use std::any::type_name_of_val;
+use std::hint::black_box;
+use std::mem::size_of_val;
+
+#[iex]
+fn small(x: u32) -> Result<u32, u8> {
+    if x == u32::MAX {
+        Err(0)
+    } else {
+        Ok(x + 1)
+    }
+}
+
+#[iex]
+fn large(x: u32) -> Result<u32, [u8; 1024]> {
+    if x == u32::MAX {
+        Err([0; 1024])
+    } else {
+        Ok(x + 1)
+    }
+}
+
+#[iex]
+fn forwards(x: u32) -> Result<u32, [u8; 1024]> {
+    Ok(large(x)? + small(x).map_err(|err| [err; 1024])?)
+}
+
+#[iex]
+fn no_args() -> Result<u32, [u8; 1024]> {
+    Ok(black_box(1))
+}
+
+#[test]
+fn outcome_stores_only_captures() {
+    assert_eq!(size_of_val(&small(1)), size_of_val(&1u32));
+    assert_eq!(size_of_val(&large(1)), size_of_val(&1u32));
+    assert_eq!(size_of_val(&forwards(1)), size_of_val(&1u32));
+    assert_eq!(size_of_val(&no_args()), 0);
+}
+
+#[test]
+fn happy_path_values() {
+    assert_eq!(black_box(forwards(black_box(1))).into_result(), Ok(4));
+    assert_eq!(no_args().into_result(), Ok(1));
+}
+
+// Pure forwarders reuse the inner outcome instead of wrapping it in a closure of their own. The
+// outcome's type is revealed at runtime, so its name tells whether it is the inner outcome itself.
+
+#[iex]
+fn bare_forward(x: u32) -> Result<u32, [u8; 1024]> {
+    large(x)
+}
+
+#[iex]
+fn try_forward(x: u32) -> Result<u32, [u8; 1024]> {
+    Ok(large(x)?)
+}
+
+#[iex]
+fn not_a_forwarder(x: u32) -> Result<u32, [u8; 1024]> {
+    let y = x;
+    large(y)
+}
+
+#[test]
+fn forwarders_reuse_inner_outcome() {
+    assert_eq!(
+        type_name_of_val(&bare_forward(1)),
+        type_name_of_val(&large(1))
+    );
+    assert_ne!(
+        type_name_of_val(&not_a_forwarder(1)),
+        type_name_of_val(&large(1))
+    );
+    // `?` still needs a marker, so the inner outcome is wrapped, but nothing else is captured
+    assert_eq!(size_of_val(&try_forward(1)), size_of_val(&large(1)));
+}
+
+#[test]
+fn forwarder_values() {
+    for x in [1, u32::MAX] {
+        let direct = black_box(large(black_box(x))).into_result();
+        assert_eq!(bare_forward(x).into_result(), direct);
+        assert_eq!(try_forward(x).into_result(), direct);
+        assert_eq!(not_a_forwarder(x).into_result(), direct);
+    }
+}
@@ -0,0 +1,89 @@
+use iex::{collect_boxed_slice, iex, Outcome};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+// Counts allocations made by the current thread, so that tests running in parallel don't interfere
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations<R>(f: impl FnOnce() -> R) -> (R, usize) {
+    let before = ALLOCATIONS.get();
+    let result = f();
+    (result, ALLOCATIONS.get() - before)
+}
+
+#[iex]
+fn double(x: u32) -> Result<u32, u32> {
+    x.checked_mul(2).ok_or(x)
+}
+
+#[test]
+fn single_allocation() {
+    let (result, count) =
+        allocations(|| collect_boxed_slice([1, 2, 3, 4].map(double)).into_result());
+    assert_eq!(result.as_deref(), Ok(&[2, 4, 6, 8][..]));
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn unknown_size() {
+    let result = collect_boxed_slice((0..10).filter(|x| x % 3 == 0).map(double)).into_result();
+    assert_eq!(result.as_deref(), Ok(&[0, 6, 12, 18][..]));
+    assert_eq!(
+        collect_boxed_slice(std::iter::empty::<Result<u32, u32>>()).into_result(),
+        Ok(Box::default())
+    );
+}
+
+struct Tracked<'a>(&'a Cell<u32>);
+
+impl Drop for Tracked<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+#[iex]
+fn track<'a>(drops: &'a Cell<u32>, visited: &Cell<u32>, fail: bool) -> Result<Tracked<'a>, ()> {
+    visited.set(visited.get() + 1);
+    if fail {
+        Err(())
+    } else {
+        Ok(Tracked(drops))
+    }
+}
+
+#[test]
+fn drops_on_error() {
+    let drops = Cell::new(0);
+    let visited = Cell::new(0);
+    let result =
+        collect_boxed_slice([false, false, true, false].map(|fail| track(&drops, &visited, fail)))
+            .into_result();
+    assert!(result.is_err());
+    // The two collected elements are dropped, and the last item is never visited
+    assert_eq!(drops.get(), 2);
+    assert_eq!(visited.get(), 3);
+}
@@ -0,0 +1,22 @@
+#![cfg(feature = "debug")]
+
+use iex::{iex, Outcome};
+
+#[iex]
+fn parse(s: &str) -> Result<i32, String> {
+    s.parse().map_err(|_| format!("Invalid number: {s}"))
+}
+
+#[iex]
+fn sum(a: &str, b: &str) -> Result<i32, String> {
+    Ok(parse(a)? + parse(b)?)
+}
+
+#[test]
+fn debug_drive() {
+    assert_eq!(sum("1", "2").debug_drive(), "Ok(3)");
+    assert_eq!(sum("1", "x").debug_drive(), "Err(\"Invalid number: x\")");
+    assert_eq!(Ok::<_, ()>(vec![1]).debug_drive(), "Ok([1])");
+    // The error is handled, so later outcomes are unaffected
+    assert_eq!(sum("3", "4").into_result(), Ok(7));
+}
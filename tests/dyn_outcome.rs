@@ -0,0 +1,59 @@
+use iex::{iex, DynOutcome, Outcome};
+
+#[iex]
+fn check(x: i32) -> Result<i32, i32> {
+    if x < 0 {
+        Err(x)
+    } else {
+        Ok(x + 1)
+    }
+}
+
+#[iex]
+fn nested(x: i32) -> Result<i32, i32> {
+    Ok(check(x)? * 2)
+}
+
+fn pending<'a>(x: &'a i32) -> Vec<Box<dyn DynOutcome<Output = i32, Error = i32> + 'a>> {
+    vec![
+        Box::new(check(*x)),
+        Box::new(nested(*x)),
+        Box::new(Ok(*x)),
+        Box::new(check(*x).map_err(|err| err - 100)),
+    ]
+}
+
+#[test]
+fn heterogeneous_collection() {
+    let results: Vec<_> = pending(&1)
+        .into_iter()
+        .map(|outcome| outcome.into_result_boxed())
+        .collect();
+    assert_eq!(results, [Ok(2), Ok(4), Ok(1), Ok(2)]);
+
+    let results: Vec<_> = pending(&-1)
+        .into_iter()
+        .map(|outcome| outcome.into_result_boxed())
+        .collect();
+    assert_eq!(results, [Err(-1), Err(-1), Ok(-1), Err(-101)]);
+}
+
+#[iex]
+fn propagates(outcome: Box<dyn DynOutcome<Output = i32, Error = i32>>) -> Result<i32, i32> {
+    Ok(outcome.into_result_boxed()? + 10)
+}
+
+#[test]
+fn propagate_from_iex() {
+    assert_eq!(propagates(Box::new(check(1))).into_result(), Ok(12));
+    assert_eq!(propagates(Box::new(check(-1))).into_result(), Err(-1));
+}
+
+#[test]
+fn send() {
+    let outcome: Box<dyn DynOutcome<Output = i32, Error = i32> + Send> = Box::new(check(1));
+    let result = std::thread::spawn(move || outcome.into_result_boxed())
+        .join()
+        .unwrap();
+    assert_eq!(result, Ok(2));
+}
@@ -0,0 +1,85 @@
+use iex::{iex, ErrorMapper, Outcome};
+use std::cell::Cell;
+
+#[iex]
+fn check(x: i32) -> Result<i32, i32> {
+    if x < 0 {
+        Err(x)
+    } else {
+        Ok(x + 1)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Traced {
+    trail: Vec<&'static str>,
+    error: i32,
+}
+
+#[iex]
+fn traced(x: i32) -> Result<i32, Traced> {
+    ErrorMapper::new(Vec::new(), |trail, error| Traced { trail, error }).run(move |trail| {
+        trail.push("checking");
+        check(x)
+    })
+}
+
+#[test]
+fn state_reaches_handler() {
+    assert_eq!(traced(1).into_result(), Ok(2));
+    assert_eq!(
+        traced(-1).into_result(),
+        Err(Traced {
+            trail: vec!["checking"],
+            error: -1
+        })
+    );
+}
+
+#[test]
+fn lazy() {
+    let called = Cell::new(false);
+    let outcome = ErrorMapper::new((), |(), err: i32| err).run(|()| {
+        called.set(true);
+        check(1)
+    });
+    assert!(!called.get());
+    assert_eq!(outcome.into_result(), Ok(2));
+    assert!(called.get());
+}
+
+struct DropCounter<'a>(&'a Cell<u32>);
+
+impl Drop for DropCounter<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+#[test]
+fn state_dropped_once() {
+    let drops = Cell::new(0);
+    let mapper = ErrorMapper::new(DropCounter(&drops), |_, err: i32| err * 2);
+    assert_eq!(mapper.run(|_| check(1)).into_result(), Ok(2));
+    assert_eq!(drops.get(), 1);
+    let mapper = ErrorMapper::new(DropCounter(&drops), |_, err: i32| err * 2);
+    assert_eq!(mapper.run(|_| check(-1)).into_result(), Err(-2));
+    assert_eq!(drops.get(), 2);
+}
+
+#[iex]
+fn nested(x: i32) -> Result<i32, String> {
+    let handler = |depth: u32, err: i32| {
+        // The handler may use #[iex] functions itself
+        assert_eq!(check(-5).into_result(), Err(-5));
+        format!("{err} at depth {depth}")
+    };
+    ErrorMapper::new(1, handler)
+        .run(move |_| ErrorMapper::new(2, |depth, err: i32| err * depth).run(move |_| check(x)))
+}
+
+#[test]
+fn nested_mappers() {
+    assert_eq!(nested(3).into_result(), Ok(4));
+    assert_eq!(nested(-3).into_result(), Err("-6 at depth 1".to_string()));
+}
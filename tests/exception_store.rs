@@ -0,0 +1,100 @@
+#![cfg(feature = "exception_store")]
+
+use iex::exception_store::{set_exception_store, ExceptionSlot, ExceptionStore};
+use iex::{iex, Outcome};
+use std::cell::Cell;
+use std::ptr::NonNull;
+use std::sync::Once;
+
+thread_local! {
+    static CURRENT_SLOT: Cell<Option<NonNull<ExceptionSlot>>> = const { Cell::new(None) };
+    static ACCESSES: Cell<usize> = const { Cell::new(0) };
+}
+
+struct TaskStore;
+
+unsafe impl ExceptionStore for TaskStore {
+    fn slot(&self) -> NonNull<ExceptionSlot> {
+        ACCESSES.set(ACCESSES.get() + 1);
+        CURRENT_SLOT.get().expect("not running a task")
+    }
+}
+
+fn run_task<R>(slot: &ExceptionSlot, f: impl FnOnce() -> R) -> R {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| unsafe { set_exception_store(&TaskStore) });
+    let outer_slot = CURRENT_SLOT.replace(Some(NonNull::from(slot)));
+    let result = f();
+    CURRENT_SLOT.set(outer_slot);
+    result
+}
+
+#[iex]
+fn fails(err: i32) -> Result<(), i32> {
+    Err(err)
+}
+
+#[test]
+fn propagates() {
+    let slot = ExceptionSlot::new();
+    let accesses = ACCESSES.get();
+    let result = run_task(&slot, || fails(1).map_err(|err| err + 1).into_result());
+    assert_eq!(result, Err(2));
+    assert!(ACCESSES.get() > accesses);
+}
+
+struct SwitchesTask<'a>(&'a ExceptionSlot);
+
+impl Drop for SwitchesTask<'_> {
+    fn drop(&mut self) {
+        assert_eq!(run_task(self.0, || fails(2).into_result()), Err(2));
+    }
+}
+
+#[iex]
+fn fails_while_switching(other_task: &ExceptionSlot) -> Result<(), i32> {
+    let _guard = SwitchesTask(other_task);
+    fails(1)
+}
+
+#[test]
+fn separate_tasks() {
+    let slots = [ExceptionSlot::new(), ExceptionSlot::new()];
+    let result = run_task(&slots[0], || fails_while_switching(&slots[1]).into_result());
+    assert_eq!(result, Err(1));
+}
+
+#[test]
+#[should_panic = "an exception store has already been installed"]
+fn install_twice() {
+    run_task(&ExceptionSlot::new(), || {});
+    unsafe {
+        set_exception_store(&TaskStore);
+    }
+}
+
+#[iex]
+fn propagates_directly(err: i32) -> Result<(), i32> {
+    fails(err)?;
+    Ok(())
+}
+
+#[iex]
+fn propagates_via_result(err: i32) -> Result<(), i32> {
+    fails(err).into_result()?;
+    Ok(())
+}
+
+fn count_accesses(f: impl FnOnce() -> Result<(), i32>) -> usize {
+    let accesses = ACCESSES.get();
+    assert_eq!(run_task(&ExceptionSlot::new(), f), Err(1));
+    ACCESSES.get() - accesses
+}
+
+#[test]
+fn into_result_question_mark_does_not_rethrow() {
+    assert_eq!(
+        count_accesses(|| propagates_via_result(1).into_result()),
+        count_accesses(|| propagates_directly(1).into_result()),
+    );
+}
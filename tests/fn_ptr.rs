@@ -0,0 +1,88 @@
+use iex::{iex, Outcome};
+use std::collections::HashMap;
+
+#[iex(fn_ptr)]
+fn checked_divide(a: u32, b: u32) -> Result<u32, &'static str> {
+    a.checked_div(b).ok_or("Cannot divide by zero")
+}
+
+#[iex(fn_ptr)]
+fn divide_pair((a, b): (u32, u32)) -> Result<u32, &'static str> {
+    Ok(checked_divide(a, b)? + 1)
+}
+
+#[iex(fn_ptr)]
+fn parse<T: std::str::FromStr>(s: &str) -> Result<T, String> {
+    s.parse().map_err(|_| format!("Invalid input: {s}"))
+}
+
+type Parser = fn(&str) -> Result<i64, String>;
+
+#[test]
+fn coerces() {
+    let divide: fn(u32, u32) -> Result<u32, &'static str> = checked_divide_fn_ptr;
+    assert_eq!(divide(6, 2), Ok(3));
+    assert_eq!(divide(6, 0), Err("Cannot divide by zero"));
+    assert_eq!(checked_divide(6, 2).into_result(), Ok(3));
+
+    let divide_pair: fn((u32, u32)) -> Result<u32, &'static str> = divide_pair_fn_ptr;
+    assert_eq!(divide_pair((6, 2)), Ok(4));
+    assert_eq!(divide_pair((6, 0)), Err("Cannot divide by zero"));
+    assert_eq!(self::divide_pair((6, 2)).into_result(), Ok(4));
+
+    let mut registry: HashMap<&str, Parser> = HashMap::new();
+    registry.insert("int", parse_fn_ptr::<i64>);
+    assert_eq!(registry["int"]("12"), Ok(12));
+    assert_eq!(registry["int"]("x"), Err("Invalid input: x".to_string()));
+    assert_eq!(parse::<u8>("7").into_result(), Ok(7));
+}
+
+struct Counter(u32);
+
+impl Counter {
+    #[iex(fn_ptr)]
+    fn take(&mut self, n: u32) -> Result<u32, u32> {
+        self.0 = self.0.checked_sub(n).ok_or(self.0)?;
+        Ok(self.0)
+    }
+
+    #[iex(fn_ptr)]
+    fn new(start: u32) -> Result<Self, ()> {
+        Ok(Self(start))
+    }
+}
+
+#[test]
+fn methods() {
+    let take: fn(&mut Counter, u32) -> Result<u32, u32> = Counter::take_fn_ptr;
+    let new: fn(u32) -> Result<Counter, ()> = Counter::new_fn_ptr;
+    let mut counter = new(5).unwrap();
+    assert_eq!(take(&mut counter, 2), Ok(3));
+    assert_eq!(take(&mut counter, 4), Err(3));
+    assert_eq!(counter.take(3).into_result(), Ok(0));
+    assert!(Counter::new(0).into_result().is_ok());
+}
+
+trait Handler {
+    #[iex(fn_ptr)]
+    fn handle(&self, pair: (u32, u32)) -> Result<u32, String>;
+}
+
+struct Divider;
+
+impl Handler for Divider {
+    #[iex]
+    fn handle(&self, (a, b): (u32, u32)) -> Result<u32, String> {
+        Ok(checked_divide(a, b)?)
+    }
+}
+
+#[test]
+fn trait_methods() {
+    let handle: fn(&Divider, (u32, u32)) -> Result<u32, String> = Divider::handle_fn_ptr;
+    assert_eq!(handle(&Divider, (6, 3)), Ok(2));
+    assert_eq!(
+        handle(&Divider, (6, 0)),
+        Err("Cannot divide by zero".to_string())
+    );
+}
@@ -0,0 +1,59 @@
+use iex::{from_fn, iex, IexResult, Outcome};
+
+fn zero() -> IexResult<i32, String> {
+    from_fn(|| Ok(0))
+}
+
+fn parse(s: &str) -> IexResult<i32, String, impl FnOnce() -> Result<i32, String> + '_> {
+    from_fn(move || s.parse().map_err(|_| format!("Invalid number: {s}")))
+}
+
+#[iex]
+fn sum(a: &str, b: &str) -> Result<i32, String> {
+    Ok(zero()? + parse(a)? + parse(b)?)
+}
+
+struct Config {
+    // A nameable outcome can be stored without making the struct generic
+    fallback: IexResult<i32, String>,
+}
+
+#[test]
+fn nameable() {
+    assert_eq!(sum("1", "2").into_result(), Ok(3));
+    assert_eq!(
+        sum("1", "x").into_result(),
+        Err("Invalid number: x".to_string())
+    );
+    let config = Config {
+        fallback: from_fn(|| Err("No fallback".to_string())),
+    };
+    assert_eq!(
+        config.fallback.into_result(),
+        Err("No fallback".to_string())
+    );
+}
+
+#[iex]
+fn mapped(s: &str) -> Result<i32, usize> {
+    parse(s)
+        .inspect_err(|err| assert!(err.starts_with("Invalid")))
+        .map_err(|err| err.len())
+}
+
+#[test]
+fn adapters() {
+    assert_eq!(mapped("7").into_result(), Ok(7));
+    assert_eq!(mapped("ab").into_result(), Err(18));
+    assert_eq!(parse("ab").map_err(|err| err.len()).into_result(), Err(18));
+    #[cfg(feature = "anyhow")]
+    assert_eq!(
+        format!(
+            "{:#}",
+            iex::Context::context(from_fn(|| Err::<(), _>(std::fmt::Error)), "Formatting")
+                .into_result()
+                .unwrap_err()
+        ),
+        "Formatting: an error occurred when formatting an argument"
+    );
+}
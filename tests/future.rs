@@ -0,0 +1,95 @@
+use async_trait::async_trait;
+use iex::{iex, Outcome};
+use std::future::Future;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+#[iex]
+fn checked_divide(a: u32, b: u32) -> Result<u32, &'static str> {
+    a.checked_div(b).ok_or("Cannot divide by zero")
+}
+
+#[cfg(feature = "future")]
+async fn divide_async(a: u32, b: u32) -> Result<u32, &'static str> {
+    checked_divide(a, b).into_future().await
+}
+
+fn poll_once<F: Future>(future: F) -> Poll<F::Output> {
+    pin!(future).poll(&mut Context::from_waker(Waker::noop()))
+}
+
+#[cfg(feature = "future")]
+#[test]
+fn into_future() {
+    assert_eq!(poll_once(divide_async(6, 2)), Poll::Ready(Ok(3)));
+    assert_eq!(
+        poll_once(divide_async(6, 0)),
+        Poll::Ready(Err("Cannot divide by zero"))
+    );
+}
+
+type BoxFuture = std::pin::Pin<Box<dyn Future<Output = Result<u32, String>>>>;
+
+#[iex(also_result)]
+fn make_future(a: u32, b: u32, c: &'static str) -> Result<BoxFuture, &'static str> {
+    // This `?` belongs to the #[iex] function...
+    let quotient = checked_divide(a, b)?;
+    let future: BoxFuture = Box::pin(async move {
+        // ...and these belong to the future
+        let parsed: u32 = c.parse().map_err(|_| format!("Invalid number: {c}"))?;
+        Ok(checked_divide(quotient, parsed).into_result()?)
+    });
+    Ok(future)
+}
+
+#[test]
+fn question_mark_in_async_block() {
+    let future = make_future(12, 2, "3").into_result().unwrap();
+    assert_eq!(poll_once(future), Poll::Ready(Ok(2)));
+    let future = make_future(12, 2, "x").into_result().unwrap();
+    assert_eq!(
+        poll_once(future),
+        Poll::Ready(Err("Invalid number: x".to_string()))
+    );
+    let future = make_future(12, 2, "0").into_result().unwrap();
+    assert_eq!(
+        poll_once(future),
+        Poll::Ready(Err("Cannot divide by zero".to_string()))
+    );
+    assert!(make_future(12, 0, "3").into_result().is_err());
+
+    let future = make_future_as_result(12, 2, "3").unwrap();
+    assert_eq!(poll_once(future), Poll::Ready(Ok(2)));
+}
+
+// Outcomes are resolved inside the future, before the next `.await`, so the future stays `Send`
+#[async_trait]
+trait Service {
+    async fn scale(&self, input: &str) -> Result<u32, String>;
+}
+
+#[iex]
+fn parse(input: &str) -> Result<u32, String> {
+    input
+        .parse()
+        .map_err(|_| format!("Invalid number: {input}"))
+}
+
+struct Scaler(u32);
+
+#[async_trait]
+impl Service for Scaler {
+    async fn scale(&self, input: &str) -> Result<u32, String> {
+        let value = parse(input).into_result()?;
+        Ok(checked_divide(value * self.0, 2).into_result()?)
+    }
+}
+
+#[test]
+fn async_trait_method() {
+    assert_eq!(poll_once(Scaler(3).scale("4")), Poll::Ready(Ok(6)));
+    assert_eq!(
+        poll_once(Scaler(3).scale("x")),
+        Poll::Ready(Err("Invalid number: x".to_string()))
+    );
+}
@@ -0,0 +1,108 @@
+use iex::{iex, Outcome};
+
+trait Parser {
+    type Error<'a>
+    where
+        Self: 'a;
+
+    #[iex]
+    fn parse<'a>(&'a self, input: &'a str) -> Result<u32, Self::Error<'a>>;
+
+    // Provided methods can only mention a single lifetime, see the docs of #[iex]
+    #[iex]
+    fn parse_pair<'a>(&'a self, a: &'a str, b: &'a str) -> Result<(u32, u32), Self::Error<'a>>
+    where
+        Self: Sized,
+    {
+        Ok((self.parse(a)?, self.parse(b)?))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct UnexpectedInput<'a>(&'a str);
+
+struct Digits;
+
+impl Parser for Digits {
+    type Error<'a> = UnexpectedInput<'a>;
+
+    #[iex]
+    fn parse<'a>(&'a self, input: &'a str) -> Result<u32, UnexpectedInput<'a>> {
+        input.parse().map_err(|_| UnexpectedInput(input))
+    }
+}
+
+#[iex]
+fn parse_both<'a, P: Parser>(parser: &'a P, a: &'a str, b: &'a str) -> Result<u32, P::Error<'a>> {
+    Ok(parser.parse(a)? + parser.parse(b)?)
+}
+
+#[test]
+fn gat_error() {
+    assert_eq!(Digits.parse("12").into_result(), Ok(12));
+    assert_eq!(Digits.parse("x").into_result(), Err(UnexpectedInput("x")));
+    assert_eq!(parse_both(&Digits, "1", "2").into_result(), Ok(3));
+    assert_eq!(
+        parse_both(&Digits, "1", "y").into_result(),
+        Err(UnexpectedInput("y"))
+    );
+    assert_eq!(Digits.parse_pair("1", "2").into_result(), Ok((1, 2)));
+    assert_eq!(
+        Digits.parse_pair("z", "2").into_result(),
+        Err(UnexpectedInput("z"))
+    );
+}
+
+trait Lexer {
+    type Error<'a>;
+
+    #[iex]
+    fn lex<'a>(&self, input: &'a str) -> Result<usize, Self::Error<'a>>;
+}
+
+struct Words;
+
+impl Lexer for Words {
+    type Error<'a> = UnexpectedInput<'a>;
+
+    #[iex]
+    fn lex<'a>(&self, input: &'a str) -> Result<usize, Self::Error<'a>> {
+        if input.is_empty() {
+            Err(UnexpectedInput(input))
+        } else {
+            Ok(input.split(' ').count())
+        }
+    }
+}
+
+trait Container {
+    type Error<T>;
+
+    #[iex]
+    fn get<T: Clone>(&self, items: &[T], index: usize) -> Result<T, Self::Error<T>>;
+}
+
+#[derive(Debug, PartialEq)]
+struct Missing<T>(Option<T>);
+
+struct Checked;
+
+impl Container for Checked {
+    type Error<T> = Missing<T>;
+
+    #[iex]
+    fn get<T: Clone>(&self, items: &[T], index: usize) -> Result<T, Self::Error<T>> {
+        items
+            .get(index)
+            .cloned()
+            .ok_or(Missing(items.last().cloned()))
+    }
+}
+
+#[test]
+fn gat_variants() {
+    assert_eq!(Words.lex("a b").into_result(), Ok(2));
+    assert_eq!(Words.lex("").into_result(), Err(UnexpectedInput("")));
+    assert_eq!(Checked.get(&[1, 2], 0).into_result(), Ok(1));
+    assert_eq!(Checked.get(&[1, 2], 5).into_result(), Err(Missing(Some(2))));
+}
@@ -27,3 +27,105 @@ fn generics() {
     assert_eq!(drop(123).into_result(), Ok(()));
     assert_eq!(drop_apit(123).into_result(), Ok(()));
 }
+
+#[iex]
+fn fill<const N: usize>(x: u8) -> Result<[u8; N], u8> {
+    if x == 0 {
+        Err(x)
+    } else {
+        Ok([x; N])
+    }
+}
+
+#[iex]
+fn sum<const N: usize>(xs: [u8; N]) -> Result<usize, u8> {
+    let mut sum = 0;
+    for x in xs {
+        sum += fill::<N>(x)?.iter().map(|&x| x as usize).sum::<usize>();
+    }
+    Ok(sum)
+}
+
+#[iex]
+fn head<'a, const N: usize>(xs: &'a [u8; N]) -> Result<&'a u8, ()> {
+    xs.first().ok_or(())
+}
+
+#[iex]
+fn const_default<const N: usize>() -> Result<[u8; N], ()>
+where
+    [u8; N]: Default,
+{
+    Ok(Default::default())
+}
+
+#[test]
+fn const_generics() {
+    assert_eq!(fill::<3>(1).into_result(), Ok([1, 1, 1]));
+    assert_eq!(fill::<3>(0).into_result(), Err(0));
+    assert_eq!(sum([1, 2]).into_result(), Ok(6));
+    assert_eq!(sum([1, 0]).into_result(), Err(0));
+    assert_eq!(head(&[5, 6]).into_result(), Ok(&5));
+    assert_eq!(head(&[]).into_result(), Err(()));
+    assert_eq!(const_default::<4>().into_result(), Ok([0; 4]));
+}
+
+struct Buffer<const N: usize>([u8; N]);
+
+impl<const N: usize> Buffer<N> {
+    #[iex]
+    fn prefix<const M: usize>(&self) -> Result<[u8; M], ()> {
+        self.0.get(..M).ok_or(())?.try_into().map_err(|_| ())
+    }
+}
+
+trait Decode<const N: usize>: Sized {
+    #[iex]
+    fn decode(bytes: [u8; N]) -> Result<Self, u8>;
+
+    #[iex]
+    fn decode_all<const M: usize>(chunks: [[u8; N]; M]) -> Result<Vec<Self>, u8> {
+        let mut decoded = Vec::with_capacity(M);
+        for chunk in chunks {
+            decoded.push(Self::decode(chunk)?);
+        }
+        Ok(decoded)
+    }
+}
+
+impl Decode<2> for u16 {
+    #[iex]
+    fn decode(bytes: [u8; 2]) -> Result<Self, u8> {
+        if bytes[1] == 0xff {
+            Err(bytes[1])
+        } else {
+            Ok(u16::from_le_bytes(bytes))
+        }
+    }
+}
+
+impl<const N: usize> Decode<N> for [u8; N] {
+    #[iex]
+    fn decode(bytes: [u8; N]) -> Result<Self, u8> {
+        Ok(bytes)
+    }
+}
+
+#[test]
+fn const_generic_methods() {
+    let buffer = Buffer([1, 2, 3]);
+    assert_eq!(buffer.prefix::<2>().into_result(), Ok([1, 2]));
+    assert_eq!(buffer.prefix::<4>().into_result(), Err(()));
+    assert_eq!(
+        u16::decode_all([[1, 0], [2, 1]]).into_result(),
+        Ok(vec![1, 258])
+    );
+    assert_eq!(
+        u16::decode_all([[1, 0], [2, 0xff]]).into_result(),
+        Err(0xff)
+    );
+    assert_eq!(
+        <[u8; 3]>::decode_all([[1, 2, 3]]).into_result(),
+        Ok(vec![[1, 2, 3]])
+    );
+}
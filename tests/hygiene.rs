@@ -9,3 +9,72 @@ fn marker_and_no_copy(marker: i32, no_copy: i32) -> Result<i32, ()> {
 fn hygiene() {
     assert_eq!(marker_and_no_copy(5, 7).into_result(), Ok(12));
 }
+
+// Items are not affected by the mixed-site span, so these would turn the macro's bindings into
+// patterns if the names collided. They live in a module of their own so that the bindings above
+// stay bindings.
+#[allow(non_upper_case_globals, dead_code)]
+mod items {
+    use iex::{iex, try_block, Outcome};
+
+    const marker: i32 = 0;
+    const no_copy: i32 = 0;
+    const closure: i32 = 0;
+    const exception_mapper: i32 = 0;
+    const value: i32 = 0;
+
+    #[iex]
+    fn fails_with(err: i32) -> Result<i32, i32> {
+        Err(err)
+    }
+
+    #[iex]
+    fn uses_internal_names(_unsafe_iex_marker: i32, _iex_no_copy: i32) -> Result<i32, i32> {
+        let marker_ = _unsafe_iex_marker + _iex_no_copy;
+        if marker_ < 0 {
+            fails_with(marker_)?;
+        }
+        Ok(marker_)
+    }
+
+    #[iex]
+    fn shadows_own_name(x: i32) -> Result<i32, i32> {
+        let shadows_own_name = x * 2;
+        if x < 0 {
+            fails_with(x)?;
+        }
+        Ok(shadows_own_name)
+    }
+
+    #[iex]
+    fn maps_err(x: i32) -> Result<i32, i32> {
+        let mut err = 0;
+        let value_ = fails_with(x).map_err(
+            #[iex(shares = err)]
+            |e| {
+                err += 1;
+                e + err
+            },
+        )?;
+        Ok(value_)
+    }
+
+    #[test]
+    fn user_bindings() {
+        assert_eq!(uses_internal_names(1, 2).into_result(), Ok(3));
+        assert_eq!(uses_internal_names(-1, -2).into_result(), Err(-3));
+        assert_eq!(shadows_own_name(2).into_result(), Ok(4));
+        assert_eq!(shadows_own_name(-2).into_result(), Err(-2));
+        assert_eq!(maps_err(1).into_result(), Err(2));
+    }
+
+    #[test]
+    fn try_block() {
+        let _unsafe_iex_marker = 5;
+        let result = try_block! {
+            let _iex_no_copy = fails_with(_unsafe_iex_marker)?;
+            Ok::<_, i32>(_iex_no_copy)
+        };
+        assert_eq!(result.into_result(), Err(5));
+    }
+}
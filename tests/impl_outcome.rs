@@ -0,0 +1,73 @@
+use iex::{apply, iex, Outcome};
+
+#[iex]
+fn parse(s: &str) -> Result<u32, String> {
+    s.parse().map_err(|_| format!("Not a number: {s}"))
+}
+
+// A hand-written outcome combinator, turned into an #[iex] function to use `?` in its body
+#[iex(output = "u32", error = "String", also_result, fn_ptr)]
+fn parse_sum<'a>(a: &'a str, b: &'a str) -> impl Outcome<Output = u32, Error = String> + 'a {
+    let a = parse(a)?;
+    apply(parse(b), move |b| a + b)
+}
+
+#[iex(output = "Vec<T>", error = "E")]
+fn repeat<T: Clone, E>(
+    outcome: impl Outcome<Output = T, Error = E>,
+    n: usize,
+) -> impl Outcome<Output = Vec<T>, Error = E> {
+    let value = outcome?;
+    Ok::<_, E>(vec![value; n])
+}
+
+trait Parser {
+    #[iex(output = "u32", error = "String", also_result, fn_ptr)]
+    fn parse_twice<'a>(&'a self, s: &'a str) -> impl Outcome<Output = u32, Error = String> + 'a;
+}
+
+struct Doubler;
+
+impl Parser for Doubler {
+    #[iex(output = "u32", error = "String", also_result)]
+    fn parse_twice<'a>(&'a self, s: &'a str) -> impl Outcome<Output = u32, Error = String> + 'a {
+        let x = parse(s)?;
+        apply(parse(s), move |y| x + y)
+    }
+}
+
+#[test]
+fn annotated_impl_outcome() {
+    assert_eq!(parse_sum("1", "2").into_result(), Ok(3));
+    assert_eq!(
+        parse_sum("x", "2").into_result(),
+        Err("Not a number: x".to_string())
+    );
+    assert_eq!(
+        parse_sum("1", "y").into_result(),
+        Err("Not a number: y".to_string())
+    );
+    assert_eq!(
+        parse_sum_as_result("1", "y"),
+        parse_sum("1", "y").into_result()
+    );
+    let f: fn(&str, &str) -> Result<u32, String> = parse_sum_fn_ptr;
+    assert_eq!(f("4", "5"), Ok(9));
+
+    assert_eq!(repeat(parse("7"), 2).into_result(), Ok(vec![7, 7]));
+    assert_eq!(
+        repeat(parse("z"), 2).into_result(),
+        Err("Not a number: z".to_string())
+    );
+}
+
+#[test]
+fn annotated_trait_method() {
+    assert_eq!(Doubler.parse_twice("21").into_result(), Ok(42));
+    assert_eq!(
+        Doubler.parse_twice("x").into_result(),
+        Err("Not a number: x".to_string())
+    );
+    assert_eq!(Doubler.parse_twice_as_result("2"), Ok(4));
+    assert_eq!(Doubler.parse_twice_fn_ptr("3"), Ok(6));
+}
@@ -0,0 +1,84 @@
+#![deny(unreachable_code)]
+
+use iex::{iex, Outcome};
+
+#[iex]
+fn check(x: i32) -> Result<i32, i32> {
+    if x < 0 {
+        Err(x)
+    } else {
+        Ok(x)
+    }
+}
+
+#[iex]
+fn implicit(x: i32) -> Result<(), i32> {
+    check(x)?;
+}
+
+#[iex]
+fn explicit(x: i32) -> Result<(), i32> {
+    check(x)?;
+    Ok(())
+}
+
+#[iex]
+fn empty() -> Result<(), i32> {}
+
+#[iex]
+fn io(x: i32) -> std::io::Result<()> {
+    if x < 0 {
+        return Err(std::io::ErrorKind::InvalidInput.into());
+    }
+}
+
+#[iex]
+fn for_loop(xs: &[i32]) -> Result<(), i32> {
+    for &x in xs {
+        check(x)?;
+    }
+}
+
+#[iex]
+fn returns_outcome(x: i32) -> Result<(), i32> {
+    let _ = x;
+    return implicit(x);
+}
+
+#[iex]
+fn diverges() -> Result<(), i32> {
+    panic!("diverges");
+}
+
+#[iex(also_result)]
+fn with_twin(x: i32) -> Result<(), i32> {
+    check(x)?;
+}
+
+struct Checker;
+
+impl Checker {
+    #[iex]
+    fn check(&self, x: i32) -> Result<(), i32> {
+        check(x)?;
+    }
+}
+
+#[test]
+fn implicit_ok() {
+    assert_eq!(implicit(1).into_result(), Ok(()));
+    assert_eq!(implicit(-1).into_result(), Err(-1));
+    assert_eq!(explicit(1).into_result(), Ok(()));
+    assert_eq!(explicit(-1).into_result(), Err(-1));
+    assert_eq!(empty().into_result(), Ok(()));
+    assert!(io(1).into_result().is_ok());
+    assert!(io(-1).into_result().is_err());
+    assert_eq!(for_loop(&[1, 2]).into_result(), Ok(()));
+    assert_eq!(for_loop(&[1, -2]).into_result(), Err(-2));
+    assert_eq!(returns_outcome(-1).into_result(), Err(-1));
+    assert!(std::panic::catch_unwind(|| diverges().into_result()).is_err());
+    assert_eq!(with_twin(1).into_result(), Ok(()));
+    assert_eq!(with_twin_as_result(1), Ok(()));
+    assert_eq!(with_twin_as_result(-1), Err(-1));
+    assert_eq!(Checker.check(-1).into_result(), Err(-1));
+}
@@ -0,0 +1,51 @@
+use iex::{iex, Outcome};
+use std::convert::Infallible;
+use std::mem::size_of_val;
+use std::panic::catch_unwind;
+
+#[iex]
+fn infallible(x: u32) -> Result<u32, Infallible> {
+    Ok(x + 1)
+}
+
+#[iex]
+fn forwards(x: u32) -> Result<u32, Infallible> {
+    Ok(infallible(x)? * 2)
+}
+
+#[iex]
+fn converts(x: u32) -> Result<u32, String> {
+    // `String: From<Infallible>` doesn't hold, so go through `match`
+    Ok(match infallible(x).into_result() {
+        Ok(value) => value,
+    })
+}
+
+#[test]
+fn happy_path() {
+    assert_eq!(infallible(1).into_result(), Ok(2));
+    assert_eq!(forwards(1).into_result(), Ok(4));
+    assert_eq!(converts(1).into_result(), Ok(2));
+    let Ok(value) = forwards(2).into_result();
+    assert_eq!(value, 6);
+}
+
+#[test]
+fn same_size_as_output() {
+    assert_eq!(size_of_val(&Ok::<u32, Infallible>(1)), size_of_val(&1u32));
+    assert_eq!(size_of_val(&infallible(1)), size_of_val(&1u32));
+}
+
+#[iex]
+fn panics(x: u32) -> Result<u32, Infallible> {
+    if x > 0 {
+        panic!("Genuine panic");
+    }
+    Ok(x)
+}
+
+#[test]
+fn panics_are_not_swallowed() {
+    let payload = catch_unwind(|| panics(1).into_result()).unwrap_err();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"Genuine panic"));
+}
@@ -0,0 +1,49 @@
+use iex::{iex, Outcome};
+
+#[iex]
+fn run(task: i32) -> Result<i32, String> {
+    match task {
+        0 => Err("Expected failure".to_string()),
+        1 => panic!("Bug in task {task}"),
+        _ => Ok(task),
+    }
+}
+
+#[iex]
+fn runs_nested(task: i32) -> Result<i32, String> {
+    Ok(run(task)? + 1)
+}
+
+#[test]
+fn three_outcomes() {
+    assert_eq!(runs_nested(2).into_result_or_panic().ok(), Some(Ok(3)));
+    assert_eq!(
+        runs_nested(0).into_result_or_panic().ok(),
+        Some(Err("Expected failure".to_string()))
+    );
+    let payload = runs_nested(1).into_result_or_panic().unwrap_err();
+    assert_eq!(
+        payload.downcast_ref::<String>().map(String::as_str),
+        Some("Bug in task 1")
+    );
+}
+
+#[test]
+fn plain_result() {
+    assert_eq!(Ok::<i32, i32>(1).into_result_or_panic().ok(), Some(Ok(1)));
+    assert_eq!(Err::<i32, i32>(2).into_result_or_panic().ok(), Some(Err(2)));
+}
+
+#[iex]
+fn escalates(task: i32) -> Result<i32, String> {
+    Ok(run(task).panic_on_err())
+}
+
+#[test]
+fn escalated_errors_are_panics() {
+    let payload = escalates(0).into_result_or_panic().unwrap_err();
+    assert_eq!(
+        payload.downcast_ref::<String>().map(String::as_str),
+        Some("Expected failure")
+    );
+}
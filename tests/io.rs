@@ -0,0 +1,104 @@
+#![cfg(feature = "io")]
+
+use iex::{iex, io::ReadIexExt, Outcome};
+use std::io::{self, Read};
+
+#[derive(Debug, PartialEq)]
+struct Header {
+    magic: [u8; 4],
+    version: u16,
+    length: u32,
+    checksum: u64,
+}
+
+#[iex]
+fn read_header(reader: &mut impl Read) -> io::Result<Header> {
+    Ok(Header {
+        magic: reader.read_array_iex()?,
+        version: reader.read_u16_be()?,
+        length: reader.read_u32_le()?,
+        checksum: reader.read_u64_le()?,
+    })
+}
+
+#[iex]
+fn read_payload(reader: &mut impl Read) -> io::Result<Vec<u8>> {
+    let header = read_header(reader)?;
+    let mut payload = vec![0; header.length as usize];
+    reader.read_exact_iex(&mut payload)?;
+    Ok(payload)
+}
+
+const HEADER: [u8; 18] = [
+    b'I', b'E', b'X', b'!', // magic
+    0, 2, // version
+    3, 0, 0, 0, // length
+    1, 0, 0, 0, 0, 0, 0, 0, // checksum
+];
+
+#[test]
+fn integers() {
+    let mut input: &[u8] = &[1, 2, 3, 4, 5, 6, 7, 8, 9];
+    assert_eq!(input.read_u8().into_result().unwrap(), 1);
+    assert_eq!(input.read_u16_le().into_result().unwrap(), 0x0302);
+    assert_eq!(input.read_u16_be().into_result().unwrap(), 0x0405);
+    assert_eq!(input.read_u32_be().into_result().unwrap(), 0x06070809);
+
+    let bytes = 0x0102030405060708u64.to_be_bytes();
+    assert_eq!(
+        (&bytes[..]).read_u64_be().into_result().unwrap(),
+        0x0102030405060708
+    );
+    assert_eq!(
+        (&bytes[..]).read_u64_le().into_result().unwrap(),
+        0x0807060504030201
+    );
+    assert_eq!(
+        (&bytes[..]).read_u32_le().into_result().unwrap(),
+        0x04030201
+    );
+}
+
+#[test]
+fn nested_reads() {
+    let mut input = HEADER.to_vec();
+    input.extend_from_slice(b"abc");
+    let mut reader = &input[..];
+    assert_eq!(
+        read_header(&mut &input[..]).into_result().unwrap(),
+        Header {
+            magic: *b"IEX!",
+            version: 2,
+            length: 3,
+            checksum: 1,
+        }
+    );
+    assert_eq!(read_payload(&mut reader).into_result().unwrap(), b"abc");
+    assert!(reader.is_empty());
+}
+
+#[test]
+fn eof() {
+    // Every prefix of the input ends too early somewhere in the nested reads
+    let mut input = HEADER.to_vec();
+    input.extend_from_slice(b"abc");
+    for len in 0..input.len() {
+        let err = read_payload(&mut &input[..len]).into_result().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
+
+struct FailingReader;
+
+impl Read for FailingReader {
+    fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::other("disk on fire"))
+    }
+}
+
+#[test]
+fn reader_error() {
+    let err = read_header(&mut FailingReader).into_result().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Other);
+    assert_eq!(err.to_string(), "disk on fire");
+}
@@ -0,0 +1,221 @@
+use iex::{iex, IexIteratorExt, Outcome};
+use std::cell::RefCell;
+
+#[iex]
+fn visit(visited: &RefCell<Vec<i32>>, x: i32) -> Result<(), i32> {
+    visited.borrow_mut().push(x);
+    if x < 0 {
+        Err(x)
+    } else {
+        Ok(())
+    }
+}
+
+#[iex]
+fn visit_all(visited: &RefCell<Vec<i32>>, xs: &[i32]) -> Result<(), i32> {
+    xs.iter().iex_try_for_each(|&x| visit(visited, x))
+}
+
+#[test]
+fn iex_try_for_each() {
+    let visited = RefCell::new(Vec::new());
+    assert_eq!(visit_all(&visited, &[1, 2, 3]).into_result(), Ok(()));
+    assert_eq!(*visited.borrow(), [1, 2, 3]);
+
+    visited.borrow_mut().clear();
+    assert_eq!(visit_all(&visited, &[1, -2, 3, -4]).into_result(), Err(-2));
+    assert_eq!(*visited.borrow(), [1, -2]);
+}
+
+#[test]
+fn iex_try_for_each_remaining() {
+    let mut iter = 0..10;
+    assert_eq!(
+        iter.by_ref()
+            .iex_try_for_each(|x| if x == 3 { Err(x) } else { Ok(()) })
+            .into_result(),
+        Err(3)
+    );
+    assert_eq!(iter.next(), Some(4));
+}
+
+#[iex]
+fn parse(s: &str) -> Result<i32, String> {
+    s.parse().map_err(|_| format!("Invalid number: {s}"))
+}
+
+#[test]
+fn sink_err() {
+    let mut errors = Vec::new();
+    assert_eq!(parse("1").sink_err(&mut errors, 0), 1);
+    assert!(errors.is_empty());
+    assert_eq!(parse("x").sink_err(&mut errors, 0), 0);
+    assert_eq!(parse("2").sink_err(&mut errors, 0), 2);
+    assert_eq!(parse("y").sink_err(&mut errors, -1), -1);
+    assert_eq!(errors, ["Invalid number: x", "Invalid number: y"]);
+}
+
+#[test]
+fn sink_errors() {
+    let mut errors = Vec::new();
+    let values: Vec<i32> = ["1", "x", "2", "y", "3"]
+        .into_iter()
+        .map(parse)
+        .sink_errors(&mut errors)
+        .collect();
+    assert_eq!(values, [1, 2, 3]);
+    assert_eq!(errors, ["Invalid number: x", "Invalid number: y"]);
+
+    // Errors accumulate across batches
+    let values: Vec<i32> = ["z", "4"]
+        .into_iter()
+        .map(parse)
+        .sink_errors(&mut errors)
+        .collect();
+    assert_eq!(values, [4]);
+    assert_eq!(
+        errors,
+        [
+            "Invalid number: x",
+            "Invalid number: y",
+            "Invalid number: z"
+        ]
+    );
+}
+
+#[iex]
+fn parse_with_progress(
+    input: &[&str],
+    every: usize,
+    progress: &mut Vec<usize>,
+) -> Result<Vec<i32>, String> {
+    iex::try_collect_iex_with_progress(input.iter().map(|s| parse(s)), every, |n| progress.push(n))
+}
+
+#[test]
+fn try_collect_iex_with_progress() {
+    let input: Vec<String> = (1..=10).map(|x| x.to_string()).collect();
+    let input: Vec<&str> = input.iter().map(String::as_str).collect();
+
+    let mut progress = Vec::new();
+    assert_eq!(
+        parse_with_progress(&input, 3, &mut progress).into_result(),
+        Ok((1..=10).collect())
+    );
+    assert_eq!(progress, [3, 6, 9]);
+
+    progress.clear();
+    assert_eq!(
+        parse_with_progress(&input, 1, &mut progress).into_result(),
+        Ok((1..=10).collect())
+    );
+    assert_eq!(progress, (1..=10).collect::<Vec<_>>());
+
+    // The callback is not called for the failing item or after it
+    progress.clear();
+    let mut with_error = input.clone();
+    with_error[6] = "x";
+    assert_eq!(
+        parse_with_progress(&with_error, 3, &mut progress).into_result(),
+        Err("Invalid number: x".to_string())
+    );
+    assert_eq!(progress, [3, 6]);
+
+    progress.clear();
+    with_error[5] = "y";
+    assert_eq!(
+        parse_with_progress(&with_error, 3, &mut progress).into_result(),
+        Err("Invalid number: y".to_string())
+    );
+    assert_eq!(progress, [3]);
+
+    // Any FromIterator collection works
+    let set: std::collections::BTreeSet<i32> =
+        iex::try_collect_iex_with_progress(["2", "1", "2"].map(parse), 5, |_| unreachable!())
+            .into_result()
+            .unwrap();
+    assert_eq!(set, [1, 2].into());
+}
+
+#[test]
+#[should_panic = "progress interval must be non-zero"]
+fn try_collect_iex_with_progress_zero() {
+    let _ = iex::try_collect_iex_with_progress::<_, Vec<i32>, _>(["1"].map(parse), 0, |_| {});
+}
+
+#[derive(Debug, PartialEq)]
+enum RecordError {
+    Header(String),
+    Record(String),
+}
+
+impl From<String> for RecordError {
+    fn from(err: String) -> Self {
+        Self::Record(err)
+    }
+}
+
+// The header lists the number of records, and the records are parsed lazily. `#[iex]` can't return
+// `Result<impl Trait, _>`, so this is a plain function.
+fn records<'a>(
+    visited: &'a RefCell<Vec<&'a str>>,
+    input: &'a str,
+) -> Result<impl Iterator<Item = impl Outcome<Output = i32, Error = String> + 'a>, RecordError> {
+    let (header, body) = input.split_once(':').unwrap_or((input, ""));
+    let count: usize = header
+        .parse()
+        .map_err(|_| RecordError::Header(header.to_string()))?;
+    Ok(body.split(',').take(count).map(move |record| {
+        visited.borrow_mut().push(record);
+        parse(record)
+    }))
+}
+
+#[iex]
+fn parse_records<'a>(
+    visited: &'a RefCell<Vec<&'a str>>,
+    input: &'a str,
+) -> Result<Vec<i32>, RecordError> {
+    iex::flatten_collect(records(visited, input))
+}
+
+#[test]
+fn flatten_collect() {
+    let visited = RefCell::new(Vec::new());
+    assert_eq!(
+        parse_records(&visited, "2:1,2,3").into_result(),
+        Ok(vec![1, 2])
+    );
+    assert_eq!(*visited.borrow(), ["1", "2"]);
+
+    // Outer error
+    let visited = RefCell::new(Vec::new());
+    assert_eq!(
+        parse_records(&visited, "x:1,2").into_result(),
+        Err(RecordError::Header("x".to_string()))
+    );
+    assert!(visited.borrow().is_empty());
+
+    // Error at the first record
+    let visited = RefCell::new(Vec::new());
+    assert_eq!(
+        parse_records(&visited, "3:a,2,3").into_result(),
+        Err(RecordError::Record("Invalid number: a".to_string()))
+    );
+    assert_eq!(*visited.borrow(), ["a"]);
+
+    // Error at a middle record stops the iteration
+    let visited = RefCell::new(Vec::new());
+    assert_eq!(
+        parse_records(&visited, "4:1,b,3,4").into_result(),
+        Err(RecordError::Record("Invalid number: b".to_string()))
+    );
+    assert_eq!(*visited.borrow(), ["1", "b"]);
+
+    // Same error types at both levels, and any FromIterator collection
+    let set: std::collections::BTreeSet<i32> =
+        iex::flatten_collect(Ok::<_, String>(["2", "1", "2"].map(parse)))
+            .into_result()
+            .unwrap();
+    assert_eq!(set, [1, 2].into());
+}
@@ -0,0 +1,175 @@
+use iex::{iex, join, partition_iex, race, try_join_all, validate_all, Outcome};
+use std::cell::RefCell;
+
+#[iex]
+fn step(log: &RefCell<Vec<i32>>, x: i32) -> Result<i32, i32> {
+    log.borrow_mut().push(x);
+    if x < 0 {
+        Err(x)
+    } else {
+        Ok(x * 10)
+    }
+}
+
+#[iex]
+fn joins(log: &RefCell<Vec<i32>>, a: i32, b: i32, c: i32) -> Result<i32, i32> {
+    let (a, b, c) = join!(step(log, a), step(log, b), step(log, c))?;
+    Ok(a + b + c)
+}
+
+#[test]
+fn join() {
+    let log = RefCell::new(Vec::new());
+    assert_eq!(joins(&log, 1, 2, 3).into_result(), Ok(60));
+    assert_eq!(*log.borrow(), [1, 2, 3]);
+
+    log.borrow_mut().clear();
+    assert_eq!(joins(&log, 1, -2, -3).into_result(), Err(-2));
+    assert_eq!(*log.borrow(), [1, -2]);
+}
+
+#[test]
+fn join_mixed() {
+    let log = RefCell::new(Vec::new());
+    assert_eq!(
+        join!(Ok::<_, i32>("a"), step(&log, 1), Ok(())).into_result(),
+        Ok(("a", 10, ()))
+    );
+    assert_eq!(join!(Err::<(), _>(0), step(&log, 2)).into_result(), Err(0));
+    assert_eq!(*log.borrow(), [1]);
+}
+
+#[test]
+fn join_twelve() {
+    let log = RefCell::new(Vec::new());
+    let outcome = join!(
+        step(&log, 0),
+        step(&log, 1),
+        step(&log, 2),
+        step(&log, 3),
+        step(&log, 4),
+        step(&log, 5),
+        step(&log, 6),
+        step(&log, 7),
+        step(&log, 8),
+        step(&log, 9),
+        step(&log, 10),
+        step(&log, 11),
+    );
+    assert!(log.borrow().is_empty());
+    assert_eq!(
+        outcome.into_result(),
+        Ok((0, 10, 20, 30, 40, 50, 60, 70, 80, 90, 100, 110))
+    );
+    assert_eq!(*log.borrow(), (0..12).collect::<Vec<_>>());
+}
+
+#[test]
+fn try_join_all() {
+    let log = RefCell::new(Vec::new());
+    assert_eq!(
+        try_join_all!(step(&log, 1), step(&log, 2)).into_result(),
+        Ok((10, 20))
+    );
+    assert_eq!(
+        try_join_all!(step(&log, -3), step(&log, 4), step(&log, -5)).into_result(),
+        Err(vec![-3, -5])
+    );
+    assert_eq!(*log.borrow(), [1, 2, -3, 4, -5]);
+}
+
+#[iex]
+fn propagates_all(log: &RefCell<Vec<i32>>) -> Result<(i32, i32), Vec<i32>> {
+    let pair = try_join_all!(step(log, -1), step(log, -2))?;
+    log.borrow_mut().push(0);
+    Ok(pair)
+}
+
+#[test]
+fn try_join_all_propagates() {
+    let log = RefCell::new(Vec::new());
+    assert_eq!(propagates_all(&log).into_result(), Err(vec![-1, -2]));
+    assert_eq!(*log.borrow(), [-1, -2]);
+}
+
+#[iex]
+fn check(log: &RefCell<Vec<i32>>, x: i32) -> Result<(), i32> {
+    step(log, x)?;
+}
+
+#[test]
+fn validate_all_checks() {
+    let log = RefCell::new(Vec::new());
+    assert_eq!(validate_all([1, 2, 3].map(|x| check(&log, x))), Ok(()));
+    assert_eq!(*log.borrow(), [1, 2, 3]);
+
+    log.borrow_mut().clear();
+    assert_eq!(
+        validate_all([-1, 2, -3, 4].map(|x| check(&log, x))),
+        Err(vec![-1, -3])
+    );
+    // Failing checks don't stop the following ones
+    assert_eq!(*log.borrow(), [-1, 2, -3, 4]);
+
+    assert_eq!(validate_all(Vec::<Result<(), i32>>::new()), Ok(()));
+    assert_eq!(validate_all([Ok(()), Err(1), Err(2)]), Err(vec![1, 2]));
+}
+
+#[iex]
+fn double(log: &RefCell<Vec<i32>>, x: i32) -> Result<i32, i32> {
+    step(log, x)?;
+    Ok(x * 2)
+}
+
+#[test]
+fn partition_iex_keeps_order() {
+    let log = RefCell::new(Vec::new());
+    let (values, errors) = partition_iex([1, -2, 3, -4, -5, 6].map(|x| double(&log, x)));
+    assert_eq!(values, [2, 6, 12]);
+    assert_eq!(errors, [-2, -4, -5]);
+    assert_eq!(*log.borrow(), [1, -2, 3, -4, -5, 6]);
+
+    let (values, errors) = partition_iex(Vec::<Result<i32, i32>>::new());
+    assert!(values.is_empty() && errors.is_empty());
+    assert_eq!(partition_iex([Err(1), Ok(2)]), (vec![2], vec![1]));
+}
+
+#[test]
+fn race_first_success_wins() {
+    let log = RefCell::new(Vec::new());
+    assert_eq!(
+        race([-1, 2, -3, 4].map(|x| step(&log, x))).into_result(),
+        Ok(20)
+    );
+    // Outcomes after the first success are not driven
+    assert_eq!(*log.borrow(), [-1, 2]);
+}
+
+#[test]
+fn race_all_fail() {
+    let log = RefCell::new(Vec::new());
+    assert_eq!(
+        race([-1, -2, -3].map(|x| step(&log, x))).into_result(),
+        Err(vec![-1, -2, -3])
+    );
+    assert_eq!(*log.borrow(), [-1, -2, -3]);
+
+    assert_eq!(
+        race(Vec::<Result<i32, i32>>::new()).into_result(),
+        Err(vec![])
+    );
+}
+
+#[iex]
+fn race_propagates(log: &RefCell<Vec<i32>>) -> Result<i32, Vec<i32>> {
+    let value = race([-1, -2].map(|x| step(log, x)))?;
+    log.borrow_mut().push(0);
+    Ok(value)
+}
+
+#[test]
+fn race_propagates_errors() {
+    let log = RefCell::new(Vec::new());
+    assert_eq!(race_propagates(&log).into_result(), Err(vec![-1, -2]));
+    assert_eq!(*log.borrow(), [-1, -2]);
+}
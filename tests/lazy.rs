@@ -0,0 +1,56 @@
+use iex::{iex, Lazy, Outcome};
+use std::cell::Cell;
+
+#[iex]
+fn count(calls: &Cell<u32>, x: i32) -> Result<i32, i32> {
+    calls.set(calls.get() + 1);
+    if x < 0 {
+        Err(x)
+    } else {
+        Ok(x)
+    }
+}
+
+#[iex]
+fn sum(jobs: Vec<Lazy<'_, i32, i32>>) -> Result<i32, i32> {
+    let mut sum = 0;
+    for job in jobs {
+        sum += job.run()?;
+    }
+    Ok(sum)
+}
+
+#[test]
+fn run() {
+    let calls = Cell::new(0);
+    let jobs = vec![
+        Lazy::new(count(&calls, 1)),
+        Lazy::new(Ok(2)),
+        Lazy::new(count(&calls, 3)),
+    ];
+    assert_eq!(calls.get(), 0);
+    assert_eq!(sum(jobs).into_result(), Ok(6));
+    assert_eq!(calls.get(), 2);
+
+    let jobs = vec![
+        Lazy::new(count(&calls, -1)),
+        Lazy::new(Err(-2)),
+        Lazy::new(count(&calls, 3)),
+    ];
+    assert_eq!(sum(jobs).into_result(), Err(-1));
+    assert_eq!(calls.get(), 3);
+}
+
+#[test]
+fn mapped() {
+    let calls = Cell::new(0);
+    let job = Lazy::new(count(&calls, -1).map_err(|err| err * 10));
+    assert_eq!(job.run().into_result(), Err(-10));
+}
+
+#[test]
+fn drop_without_running() {
+    let calls = Cell::new(0);
+    drop(Lazy::new(count(&calls, 1)));
+    assert_eq!(calls.get(), 0);
+}
@@ -60,3 +60,96 @@ fn mutability() {
     assert_eq!(x, 2);
     assert_eq!(y, 1);
 }
+
+// Errors that borrow. Forwarding between error types that only differ in lifetimes skips the
+// conversion, which must still produce the same error.
+
+#[derive(Debug, PartialEq)]
+struct ParseError {
+    position: usize,
+}
+
+#[iex]
+fn first_digit<'a>(s: &'a str) -> Result<u32, &'a str> {
+    s.chars().next().and_then(|c| c.to_digit(10)).ok_or(s)
+}
+
+#[iex]
+fn sum_digits<'a, 'b>(a: &'a str, b: &'b str) -> Result<u32, &'b str>
+where
+    'a: 'b,
+{
+    Ok(first_digit(a)? + first_digit(b)?)
+}
+
+#[iex]
+fn check_position<'e>(s: &str, error: &'e ParseError) -> Result<usize, &'e ParseError> {
+    if s.len() > error.position {
+        Ok(s.len())
+    } else {
+        Err(error)
+    }
+}
+
+#[iex]
+fn check_both<'e>(s: &str, error: &'e ParseError) -> Result<usize, &'e ParseError> {
+    Ok(check_position(s, error)? + check_position(s, error)?)
+}
+
+#[derive(Debug, PartialEq)]
+struct OwnedError(usize);
+
+impl From<&ParseError> for OwnedError {
+    fn from(error: &ParseError) -> Self {
+        Self(error.position)
+    }
+}
+
+#[iex]
+fn check_owned(s: &str) -> Result<usize, OwnedError> {
+    let error = ParseError { position: 3 };
+    Ok(check_position(s, &error)?)
+}
+
+type Formatter<'a> = Box<dyn for<'x> Fn(&'x str) -> String + 'a>;
+
+#[iex]
+fn fails_with_formatter<'a>(prefix: &'a str) -> Result<(), Formatter<'a>> {
+    let formatter: Formatter<'a> = Box::new(move |s| format!("{prefix}{s}"));
+    Err(formatter)
+}
+
+#[iex]
+fn forwards_formatter<'a>(prefix: &'a str) -> Result<(), Formatter<'a>> {
+    fails_with_formatter(prefix)?;
+    Ok(())
+}
+
+#[test]
+fn borrowing_errors() {
+    assert_eq!(sum_digits("1", "2").into_result(), Ok(3));
+    let a = String::from("x");
+    let b = String::from("y");
+    assert!(std::ptr::eq(
+        sum_digits(&a, "1").into_result().unwrap_err(),
+        &*a
+    ));
+    assert!(std::ptr::eq(
+        sum_digits("1", &b).into_result().unwrap_err(),
+        &*b
+    ));
+
+    let error = ParseError { position: 3 };
+    assert_eq!(check_both("abcd", &error).into_result(), Ok(8));
+    assert!(std::ptr::eq(
+        check_both("ab", &error).into_result().unwrap_err(),
+        &error
+    ));
+
+    assert_eq!(check_owned("abcd").into_result(), Ok(4));
+    assert_eq!(check_owned("ab").into_result(), Err(OwnedError(3)));
+
+    let prefix = String::from("> ");
+    let formatter = forwards_formatter(&prefix).into_result().unwrap_err();
+    assert_eq!(formatter("text"), "> text");
+}
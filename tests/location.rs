@@ -0,0 +1,117 @@
+#![cfg(feature = "location")]
+
+use iex::{iex, Located, Outcome};
+use std::panic::Location;
+
+#[iex]
+fn fails(err: i32) -> Result<i32, i32> {
+    if err == 0 {
+        Ok(0)
+    } else {
+        Err(err)
+    }
+}
+
+#[iex]
+fn tail_calls(err: i32) -> Result<i32, i32> {
+    fails(err)
+}
+
+#[iex]
+fn propagates(err: i32, line: &mut u32) -> Result<i32, i64> {
+    *line = line!() + 1;
+    let value = tail_calls(err)?;
+    Ok(value)
+}
+
+#[iex]
+fn propagates_further(err: i32, line: &mut u32) -> Result<i32, i64> {
+    // The outermost `?` wins
+    *line = line!() + 1;
+    Ok(propagates(err, &mut 0)? + 1)
+}
+
+#[iex]
+fn forwards(err: i32, line: &mut u32) -> Result<i32, i64> {
+    // Tail calls don't involve `?`, so the location is the last `?` below
+    propagates_further(err, line)
+}
+
+fn line_of(location: Option<&'static Location<'static>>) -> Option<u32> {
+    location.map(|location| {
+        assert_eq!(location.file(), file!());
+        location.line()
+    })
+}
+
+#[test]
+fn outermost_question_mark() {
+    let mut line = 0;
+    let (result, location) = propagates(1, &mut line).into_result_located();
+    assert_eq!(result, Err(1));
+    assert_eq!(line_of(location), Some(line));
+
+    let mut line = 0;
+    let (result, location) = propagates_further(2, &mut line).into_result_located();
+    assert_eq!(result, Err(2));
+    assert_eq!(line_of(location), Some(line));
+}
+
+#[test]
+fn forwarding_tail_calls() {
+    let mut line = 0;
+    let (result, location) = forwards(3, &mut line).into_result_located();
+    assert_eq!(result, Err(3));
+    assert_eq!(line_of(location), Some(line));
+}
+
+#[test]
+fn wrapper_error() {
+    let mut line = 0;
+    let err = propagates_further(4, &mut line)
+        .into_result_with_location()
+        .unwrap_err();
+    assert_eq!(err.error, 4);
+    assert_eq!(line_of(err.location), Some(line));
+    assert!(err
+        .to_string()
+        .starts_with(&format!("4 at {}:{line}:", file!())));
+
+    assert_eq!(
+        tail_calls(5).into_result_with_location(),
+        Err(Located {
+            error: 5,
+            location: None
+        })
+    );
+    assert_eq!(propagates(0, &mut line).into_result_with_location(), Ok(0));
+}
+
+#[test]
+fn no_question_mark() {
+    assert_eq!(tail_calls(1).into_result_located(), (Err(1), None));
+    assert_eq!(Err::<i32, i32>(2).into_result_located(), (Err(2), None));
+}
+
+#[test]
+fn success() {
+    let mut line = 0;
+    // A stale location from an unrelated error must not leak into a success
+    let _ = propagates(1, &mut line).into_result();
+    assert_eq!(
+        propagates(0, &mut line).into_result_located(),
+        (Ok(0), None)
+    );
+}
+
+#[iex]
+fn catches_internally(err: i32) -> Result<i32, i32> {
+    let mut line = 0;
+    let _ = propagates(err, &mut line).into_result();
+    tail_calls(err + 1)
+}
+
+#[test]
+fn caught_errors_are_forgotten() {
+    assert_eq!(catches_internally(1).into_result_located(), (Err(2), None));
+}
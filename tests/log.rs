@@ -0,0 +1,45 @@
+#![cfg(feature = "log")]
+
+use iex::{iex, Outcome};
+use log::{Level, Log, Metadata, Record};
+use std::sync::Mutex;
+
+struct Logger;
+
+static MESSAGES: Mutex<Vec<(Level, String)>> = Mutex::new(Vec::new());
+
+impl Log for Logger {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        MESSAGES
+            .lock()
+            .unwrap()
+            .push((record.level(), record.args().to_string()));
+    }
+
+    fn flush(&self) {}
+}
+
+#[iex]
+fn parse(s: &str) -> Result<i32, String> {
+    s.parse().map_err(|_| format!("Invalid number: {s}"))
+}
+
+// The logger is global, so everything is checked in one test
+#[test]
+fn unwrap_or_log() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    assert_eq!(parse("1").unwrap_or_log(0), 1);
+    assert!(MESSAGES.lock().unwrap().is_empty());
+
+    assert_eq!(parse("x").unwrap_or_log(0), 0);
+    assert_eq!(
+        *MESSAGES.lock().unwrap(),
+        [(Level::Error, "Invalid number: x".to_string())]
+    );
+}
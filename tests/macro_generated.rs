@@ -0,0 +1,198 @@
+use iex::{iex, Outcome};
+
+trait Decode: Sized {
+    #[iex]
+    fn decode(input: &str) -> Result<Self, String>;
+}
+
+// A serde-style code generator, invoked once per type
+macro_rules! impl_decode {
+    ($($ty:ty),*) => {
+        $(
+            impl Decode for $ty {
+                #[iex]
+                fn decode(input: &str) -> Result<Self, String> {
+                    let trimmed = trim(input)?;
+                    trimmed
+                        .parse()
+                        .map_err(|_| format!("Invalid {}: {input}", stringify!($ty)))
+                }
+            }
+        )*
+    };
+}
+
+impl_decode!(u8, i32);
+impl_decode!(u64);
+
+#[iex]
+fn trim<'a>(input: &'a str) -> Result<&'a str, String> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return Err("Empty input".to_string());
+    }
+    Ok(trimmed)
+}
+
+// Free functions with names and bodies built from the macro's arguments, and the `?` inside the
+// macro rather than in the arguments
+macro_rules! decoders {
+    ($($name:ident: $ty:ty;)*) => {
+        $(
+            /// Decode a comma-separated list.
+            #[iex(also_result, fn_ptr)]
+            pub fn $name(input: &str) -> Result<Vec<$ty>, String> {
+                let mut output = Vec::new();
+                for item in input.split(',') {
+                    output.push(<$ty>::decode(item)?);
+                }
+                Ok(output)
+            }
+        )*
+    };
+}
+
+decoders! {
+    decode_u8s: u8;
+    decode_i32s: i32;
+}
+
+decoders! {
+    decode_u64s: u64;
+}
+
+// The `?` comes from the macro's arguments
+macro_rules! checked {
+    ($name:ident, $input:ident => $body:expr) => {
+        #[iex]
+        fn $name($input: &str) -> Result<u8, String> {
+            Ok($body)
+        }
+    };
+}
+
+checked!(plus_one, input => u8::decode(input)? + 1);
+checked!(times_two, input => u8::decode(input)? * 2);
+
+// Methods in a generated inherent impl, with the same names in every impl
+macro_rules! wrapper {
+    ($name:ident($ty:ty)) => {
+        struct $name($ty);
+
+        impl $name {
+            #[iex]
+            fn parse(input: &str) -> Result<Self, String> {
+                Ok(Self(<$ty>::decode(input)?))
+            }
+
+            #[iex]
+            fn checked_add(&self, input: &str) -> Result<$ty, String> {
+                let other = Self::parse(input)?;
+                self.0
+                    .checked_add(other.0)
+                    .ok_or_else(|| "Overflow".to_string())
+            }
+        }
+    };
+}
+
+wrapper!(Small(u8));
+wrapper!(Large(u64));
+
+// Default methods using `self`, with `self` spelled both by the macro and by its caller
+macro_rules! summed {
+    ($trait:ident, $method:ident, |$this:ident| $get:expr) => {
+        trait $trait {
+            fn items(&self) -> &[&'static str];
+
+            #[iex]
+            fn $method(&self) -> Result<u64, String> {
+                let mut sum = 0;
+                for item in self.items() {
+                    sum += u64::decode(item)?;
+                }
+                let $this = self;
+                Ok(sum + $get)
+            }
+        }
+    };
+}
+
+summed!(Sum, sum, |this| u64::from(this.items().is_empty()));
+summed!(SumWithLen, sum_with_len, |this| this.items().len() as u64);
+
+struct Items(Vec<&'static str>);
+
+impl Sum for Items {
+    fn items(&self) -> &[&'static str] {
+        &self.0
+    }
+}
+
+impl SumWithLen for Items {
+    fn items(&self) -> &[&'static str] {
+        &self.0
+    }
+}
+
+#[test]
+fn generated_trait_impls() {
+    assert_eq!(u8::decode(" 7 ").into_result(), Ok(7));
+    assert_eq!(i32::decode("-7").into_result(), Ok(-7));
+    assert_eq!(u64::decode("7").into_result(), Ok(7));
+    assert_eq!(
+        u8::decode("-7").into_result(),
+        Err("Invalid u8: -7".to_string())
+    );
+    assert_eq!(
+        u64::decode(" ").into_result(),
+        Err("Empty input".to_string())
+    );
+}
+
+#[test]
+fn generated_functions() {
+    assert_eq!(decode_u8s("1,2").into_result(), Ok(vec![1, 2]));
+    assert_eq!(decode_i32s("1,-2").into_result(), Ok(vec![1, -2]));
+    assert_eq!(decode_u64s("3").into_result(), Ok(vec![3]));
+    assert_eq!(
+        decode_u8s("1,300").into_result(),
+        Err("Invalid u8: 300".to_string())
+    );
+    assert_eq!(decode_u8s_as_result("1,"), Err("Empty input".to_string()));
+    let f: fn(&str) -> Result<Vec<i32>, String> = decode_i32s_fn_ptr;
+    assert_eq!(f("4"), Ok(vec![4]));
+
+    assert_eq!(plus_one("1").into_result(), Ok(2));
+    assert_eq!(
+        times_two("x").into_result(),
+        Err("Invalid u8: x".to_string())
+    );
+}
+
+#[test]
+fn generated_methods() {
+    assert_eq!(Small(1).checked_add("2").into_result(), Ok(3));
+    assert_eq!(
+        Small(255).checked_add("1").into_result(),
+        Err("Overflow".to_string())
+    );
+    assert_eq!(Large(255).checked_add("1").into_result(), Ok(256));
+    assert_eq!(
+        Large(1).checked_add("").into_result(),
+        Err("Empty input".to_string())
+    );
+}
+
+#[test]
+fn generated_default_methods() {
+    let items = Items(vec!["1", "2"]);
+    assert_eq!(Sum::sum(&items).into_result(), Ok(3));
+    assert_eq!(SumWithLen::sum_with_len(&items).into_result(), Ok(5));
+    assert_eq!(Sum::sum(&Items(vec![])).into_result(), Ok(1));
+    let items = Items(vec!["1", "x"]);
+    assert_eq!(
+        Sum::sum(&items).into_result(),
+        Err("Invalid u64: x".to_string())
+    );
+}
@@ -0,0 +1,33 @@
+// Runs `examples/iex_main.rs`, whose `main` is marked with `#[iex::main]`, to check how errors are
+// reported by the process.
+
+use std::process::{Command, Output};
+
+fn run(args: &[&str]) -> Output {
+    let target_dir = concat!(env!("CARGO_TARGET_TMPDIR"), "/iex-main");
+    Command::new(env!("CARGO"))
+        .args(["run", "--quiet", "--example", "iex_main", "--manifest-path"])
+        .arg(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml"))
+        .args(["--target-dir", target_dir, "--"])
+        .args(args)
+        .output()
+        .expect("failed to run cargo run")
+}
+
+#[test]
+fn success() {
+    let output = run(&["1", "2"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "3\n");
+}
+
+#[test]
+fn error() {
+    let output = run(&["1", "x", "2"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Error: ParseIntError { kind: InvalidDigit }\n"
+    );
+}
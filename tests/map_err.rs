@@ -1,4 +1,5 @@
 use iex::{iex, Outcome};
+use std::cell::RefCell;
 
 #[iex]
 fn produces_err() -> Result<(), String> {
@@ -78,3 +79,231 @@ fn owned_method() {
 fn mut_ref() {
     assert_eq!(maps_err_mut_ref(A).into_result(), Err(()));
 }
+
+#[derive(Debug)]
+struct Inner;
+
+impl std::fmt::Display for Inner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "inner")
+    }
+}
+
+impl std::error::Error for Inner {}
+
+#[iex]
+fn produces_inner(fail: bool) -> Result<i32, Inner> {
+    if fail {
+        Err(Inner)
+    } else {
+        Ok(1)
+    }
+}
+
+#[iex]
+fn chains(fail: bool) -> Result<i32, iex::ChainedError<&'static str, Inner>> {
+    produces_inner(fail).chain_err(|| "outer")
+}
+
+#[test]
+fn chain_err() {
+    use std::error::Error;
+
+    assert_eq!(chains(false).into_result().unwrap(), 1,);
+    let err = chains(true).into_result().unwrap_err();
+    assert_eq!(err.to_string(), "outer");
+    assert_eq!(format!("{err:#}"), "outer: inner");
+    assert_eq!(err.source().unwrap().to_string(), "inner");
+    assert_eq!(
+        Ok::<i32, Inner>(1)
+            .chain_err(|| -> &str { unreachable!() })
+            .into_result()
+            .unwrap(),
+        1
+    );
+}
+
+#[iex]
+fn produces_generic_err<E>(err: E) -> Result<(), E> {
+    Err(err)
+}
+
+#[iex]
+fn maps_err_generic<E>(err: E, f: impl FnOnce(E) -> E) -> Result<(), E> {
+    produces_generic_err(err).map_err(f)
+}
+
+#[test]
+fn same_type() {
+    // Mapping to the same type must still apply the mapper
+    assert_eq!(maps_err_generic(1, |err| err + 1).into_result(), Err(2));
+    assert_eq!(
+        maps_err_generic("a".to_string(), |err| err + "b").into_result(),
+        Err("ab".to_string())
+    );
+}
+
+#[iex]
+fn peeks(fail: bool, errors: &mut Vec<String>) -> Result<i32, String> {
+    let value = produces_inner(fail)
+        .map_err(|err| err.to_string())
+        .peek_err(|err| errors.push(err.clone()))?;
+    Ok(value + 1)
+}
+
+#[iex]
+fn peeks_failing() -> Result<(), String> {
+    produces_err().peek_err(|_| maps_err().into_result())
+}
+
+#[test]
+fn peek_err() {
+    let mut errors = Vec::new();
+    assert_eq!(peeks(false, &mut errors).into_result(), Ok(2));
+    assert!(errors.is_empty());
+    assert_eq!(
+        peeks(true, &mut errors).into_result(),
+        Err("inner".to_string())
+    );
+    assert_eq!(errors, ["inner"]);
+    assert_eq!(peeks_failing().into_result(), Err("Hello,".to_string()));
+}
+
+#[iex]
+fn maps_err_chained(log: &mut Vec<&'static str>) -> Result<(), String> {
+    produces_err()
+        .map_err(|e| format!("{e} world"))
+        .inspect_err(|_| log.push("inspected"))
+        .map_err(|e| e.len())
+        .map_err(|len| {
+            assert_eq!(maps_err().into_result(), Err("Hello, world!".to_string()));
+            format!("{len} bytes")
+        })
+}
+
+#[test]
+fn chained() {
+    let mut log = Vec::new();
+    assert_eq!(
+        maps_err_chained(&mut log).into_result(),
+        Err("12 bytes".to_string())
+    );
+    assert_eq!(log, ["inspected"]);
+}
+
+#[derive(Debug, PartialEq)]
+struct Low(i32);
+
+#[derive(Debug, PartialEq)]
+struct Mid(Low);
+
+impl From<Low> for Mid {
+    fn from(err: Low) -> Self {
+        Self(err)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct High(Mid);
+
+impl From<Mid> for High {
+    fn from(err: Mid) -> Self {
+        Self(err)
+    }
+}
+
+#[iex]
+fn produces_low(fail: bool) -> Result<i32, Low> {
+    if fail {
+        Err(Low(1))
+    } else {
+        Ok(1)
+    }
+}
+
+#[iex]
+fn two_hops(fail: bool) -> Result<i32, High> {
+    Ok(produces_low(fail).rethrow_via::<Mid>()? + 1)
+}
+
+#[iex]
+fn explicit_hops(fail: bool) -> Result<i32, High> {
+    produces_low(fail)
+        .rethrow_via::<Mid>()
+        .rethrow_via::<High>()
+}
+
+#[test]
+fn rethrow_via() {
+    assert_eq!(two_hops(false).into_result(), Ok(2));
+    assert_eq!(two_hops(true).into_result(), Err(High(Mid(Low(1)))));
+    assert_eq!(explicit_hops(false).into_result(), Ok(1));
+    assert_eq!(explicit_hops(true).into_result(), Err(High(Mid(Low(1)))));
+}
+
+thread_local! {
+    static EVENTS: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+fn event(name: &'static str) {
+    EVENTS.with_borrow_mut(|events| events.push(name));
+}
+
+// Large enough to be boxed when thrown
+struct LargeError {
+    code: u8,
+    _payload: [u64; 64],
+}
+
+impl Drop for LargeError {
+    fn drop(&mut self) {
+        // Destructors may use the exception slot while the mapped error is not yet thrown
+        assert_eq!(produces_low(true).into_result(), Err(Low(1)));
+        event("drop");
+    }
+}
+
+#[allow(clippy::result_large_err)]
+#[iex]
+fn produces_large(code: u8) -> Result<i32, LargeError> {
+    Err(LargeError {
+        code,
+        _payload: [0; 64],
+    })
+}
+
+#[iex]
+fn maps_large(code: u8) -> Result<i32, u8> {
+    Ok(produces_large(code).map_err_ref(|err| {
+        assert_not_on_stack(err);
+        event("map");
+        err.code
+    })? + 1)
+}
+
+// Large errors are boxed while they propagate, and `map_err_ref` borrows them in their box instead
+// of moving them to the stack of the handler. Stack and heap are far apart.
+fn assert_not_on_stack(err: &LargeError) {
+    let local = 0u8;
+    let distance = (err as *const LargeError as usize).abs_diff(&local as *const u8 as usize);
+    assert!(distance > 1 << 16, "the error was moved to the stack");
+}
+
+#[test]
+fn map_err_ref() {
+    assert_eq!(maps_large(3).into_result(), Err(3));
+    assert_eq!(EVENTS.take(), ["map", "drop"]);
+    assert_eq!(
+        Err::<(), _>(LargeError {
+            code: 4,
+            _payload: [0; 64],
+        })
+        .map_err_ref(|err| {
+            assert_not_on_stack(err);
+            err.code
+        })
+        .into_result(),
+        Err(4)
+    );
+    assert_eq!(EVENTS.take(), ["drop"]);
+}
@@ -32,3 +32,117 @@ fn methods() {
     assert_eq!(A.ref_method().into_result(), Ok(&A));
     assert_eq!(A.mut_method().into_result(), Ok(&mut A));
 }
+
+trait Validate: Sized {
+    type Error;
+
+    #[iex]
+    fn validate(value: i32) -> Result<Self, Self::Error>;
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Positive(i32);
+
+impl Validate for Positive {
+    type Error = String;
+
+    #[iex]
+    fn validate(value: i32) -> Result<Self, String> {
+        if value > 0 {
+            Ok(Self(value))
+        } else {
+            Err(format!("{value} is not positive"))
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Pair<T> {
+    first: T,
+    second: T,
+}
+
+impl<T: Validate> Pair<T> {
+    #[iex]
+    fn new(first: i32, second: i32) -> Result<Self, T::Error> {
+        Ok(Self {
+            first: T::validate(first)?,
+            second: T::validate(second)?,
+        })
+    }
+
+    #[iex]
+    fn new_with_where<E>(first: i32, second: i32) -> Result<Pair<T>, E>
+    where
+        T: Validate<Error = E>,
+    {
+        Self::new(first, second)
+    }
+
+    #[iex]
+    fn from_first(first: i32) -> Result<Self, T::Error>
+    where
+        T: Clone,
+    {
+        let first = T::validate(first)?;
+        Ok(Self {
+            second: first.clone(),
+            first,
+        })
+    }
+}
+
+impl<T: Validate> Validate for Pair<T> {
+    type Error = <T as Validate>::Error;
+
+    #[iex]
+    fn validate(value: i32) -> Result<Self, Self::Error> {
+        Self::new(value, value)
+    }
+}
+
+impl<'a, T: Validate + 'a> Pair<&'a T> {
+    #[iex]
+    fn borrowed(first: &'a T, second: &'a T) -> Result<Self, T::Error> {
+        Ok(Self { first, second })
+    }
+}
+
+#[test]
+fn generic_constructors() {
+    assert_eq!(
+        Pair::<Positive>::new(1, 2).into_result(),
+        Ok(Pair {
+            first: Positive(1),
+            second: Positive(2)
+        })
+    );
+    assert_eq!(
+        Pair::<Positive>::new(1, -2).into_result(),
+        Err("-2 is not positive".to_string())
+    );
+    assert_eq!(
+        Pair::<Positive>::new_with_where(-1, 2).into_result(),
+        Err("-1 is not positive".to_string())
+    );
+    assert_eq!(
+        Pair::<Positive>::from_first(3).into_result(),
+        Ok(Pair {
+            first: Positive(3),
+            second: Positive(3)
+        })
+    );
+    assert!(Pair::<Positive>::from_first(0).into_result().is_err());
+    assert_eq!(
+        <Pair<Pair<Positive>>>::validate(-5).into_result(),
+        Err("-5 is not positive".to_string())
+    );
+    let (a, b) = (Positive(1), Positive(2));
+    assert_eq!(
+        Pair::borrowed(&a, &b).into_result(),
+        Ok(Pair {
+            first: &a,
+            second: &b
+        })
+    );
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "metrics")]
+
+use iex::{iex, Outcome};
+use std::thread::sleep;
+use std::time::Duration;
+
+#[iex]
+fn slow(delay: Duration, fail: bool) -> Result<u32, String> {
+    sleep(delay);
+    if fail {
+        return Err("failed".to_string());
+    }
+    Ok(1)
+}
+
+#[iex]
+fn timed(delay: Duration, fail: bool, timings: &mut Vec<(Duration, bool)>) -> Result<u32, String> {
+    Ok(slow(delay, fail)
+        .inspect_timing(|duration, succeeded| timings.push((duration, succeeded)))?
+        + 1)
+}
+
+#[test]
+fn succeeding() {
+    let mut timings = Vec::new();
+    assert_eq!(
+        timed(Duration::from_millis(10), false, &mut timings).into_result(),
+        Ok(2)
+    );
+    assert_eq!(timings.len(), 1);
+    assert!(timings[0].1);
+    assert!(timings[0].0 >= Duration::from_millis(10));
+}
+
+#[test]
+fn throwing() {
+    let mut timings = Vec::new();
+    assert_eq!(
+        timed(Duration::from_millis(10), true, &mut timings).into_result(),
+        Err("failed".to_string())
+    );
+    assert_eq!(timings.len(), 1);
+    assert!(!timings[0].1);
+    assert!(timings[0].0 >= Duration::from_millis(10));
+    #[cfg(feature = "testing")]
+    assert!(iex::testing::exception_is_clear());
+}
+
+#[iex]
+fn fails_in_callback(inner: Result<u32, String>) -> Result<u32, String> {
+    inner.inspect_timing(|_, _| {
+        // Errors thrown and caught by the callback don't clobber the propagating error
+        assert_eq!(
+            slow(Duration::ZERO, true).into_result(),
+            Err("failed".to_string())
+        );
+    })
+}
+
+#[test]
+fn nested_errors() {
+    assert_eq!(fails_in_callback(Ok(1)).into_result(), Ok(1));
+    assert_eq!(
+        fails_in_callback(Err("outer".to_string())).into_result(),
+        Err("outer".to_string())
+    );
+}
@@ -0,0 +1,65 @@
+use iex::{iex, Outcome};
+
+fn plain_outer(x: i32) -> Result<i32, i32> {
+    #[iex]
+    fn helper(x: i32) -> Result<i32, i32> {
+        if x < 0 {
+            Err(x)
+        } else {
+            Ok(x * 2)
+        }
+    }
+
+    /// Documented helpers get a `#[cfg(doc)]` twin, which must not collide in a local scope
+    #[iex(also_result)]
+    fn documented_helper(x: i32) -> Result<i32, i32> {
+        Ok(helper(x)? + 1)
+    }
+
+    assert_eq!(
+        documented_helper_as_result(x),
+        documented_helper(x).into_result()
+    );
+    documented_helper(x).into_result()
+}
+
+#[iex]
+fn iex_outer(x: i32) -> Result<i32, String> {
+    #[iex]
+    fn helper(x: i32) -> Result<i32, i32> {
+        if x < 0 {
+            Err(x)
+        } else {
+            Ok(x * 2)
+        }
+    }
+
+    // The outer `?` must not affect the nested function and vice versa
+    Ok(helper(x).map_err(|err| format!("{err} is negative"))? + 1)
+}
+
+struct S;
+
+impl S {
+    #[iex]
+    fn method(&self, x: i32) -> Result<i32, i32> {
+        #[iex]
+        fn helper(x: i32) -> Result<i32, i32> {
+            x.checked_add(1).ok_or(x)
+        }
+        helper(x)
+    }
+}
+
+#[test]
+fn nested_fn() {
+    assert_eq!(plain_outer(1), Ok(3));
+    assert_eq!(plain_outer(-1), Err(-1));
+    assert_eq!(iex_outer(1).into_result(), Ok(3));
+    assert_eq!(
+        iex_outer(-1).into_result(),
+        Err("-1 is negative".to_string())
+    );
+    assert_eq!(S.method(1).into_result(), Ok(2));
+    assert_eq!(S.method(i32::MAX).into_result(), Err(i32::MAX));
+}
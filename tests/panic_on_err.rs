@@ -0,0 +1,52 @@
+use iex::{iex, Outcome};
+use std::panic::catch_unwind;
+
+#[iex]
+fn check(x: i32) -> Result<i32, String> {
+    if x < 0 {
+        Err(format!("{x} is negative"))
+    } else {
+        Ok(x + 1)
+    }
+}
+
+#[iex]
+fn nested(x: i32) -> Result<i32, String> {
+    Ok(check(x)? * 2)
+}
+
+#[test]
+fn succeeds() {
+    assert_eq!(nested(1).panic_on_err(), 4);
+    assert_eq!(Ok::<i32, &str>(3).panic_on_err(), 3);
+}
+
+#[test]
+fn payload_is_string() {
+    let payload = catch_unwind(|| nested(-1).panic_on_err()).unwrap_err();
+    assert_eq!(
+        payload.downcast_ref::<String>().map(String::as_str),
+        Some("-1 is negative")
+    );
+
+    let payload = catch_unwind(|| Err::<i32, _>("plain result").panic_on_err()).unwrap_err();
+    assert_eq!(
+        payload.downcast_ref::<String>().map(String::as_str),
+        Some("plain result")
+    );
+}
+
+#[iex]
+fn panics_inside_iex(x: i32) -> Result<i32, String> {
+    // The panic must not be mistaken for an error of the outer function
+    Ok(check(x).panic_on_err())
+}
+
+#[test]
+fn escapes_into_result() {
+    let payload = catch_unwind(|| panics_inside_iex(-2).into_result()).unwrap_err();
+    assert_eq!(
+        payload.downcast_ref::<String>().map(String::as_str),
+        Some("-2 is negative")
+    );
+}
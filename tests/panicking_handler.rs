@@ -0,0 +1,135 @@
+use iex::{iex, ErrorMapper, Outcome};
+use std::cell::Cell;
+use std::convert::Infallible;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+#[iex]
+fn fails(err: i32) -> Result<i32, i32> {
+    Err(err)
+}
+
+#[iex]
+fn panics_in_map_err(err: i32, outer_called: &Cell<bool>) -> Result<i32, String> {
+    fails(err)
+        .map_err(|err| -> i32 { panic!("Handler panicked on {err}") })
+        .map_err(|err| {
+            outer_called.set(true);
+            err.to_string()
+        })
+}
+
+struct DropCounter<'a>(&'a Cell<u32>);
+
+impl Drop for DropCounter<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+#[iex]
+fn with_guard(err: i32, outer_called: &Cell<bool>, drops: &Cell<u32>) -> Result<i32, String> {
+    let _guard = DropCounter(drops);
+    Ok(panics_in_map_err(err, outer_called)? + 1)
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    payload.downcast::<String>().map(|s| *s).unwrap()
+}
+
+#[test]
+fn map_err_panic_is_a_regular_panic() {
+    let outer_called = Cell::new(false);
+    let drops = Cell::new(0);
+    let payload = catch_unwind(AssertUnwindSafe(|| {
+        with_guard(1, &outer_called, &drops).into_result()
+    }))
+    .unwrap_err();
+    assert_eq!(panic_message(payload), "Handler panicked on 1");
+    // Handlers further up are skipped, but destructors still run
+    assert!(!outer_called.get());
+    assert_eq!(drops.get(), 1);
+    #[cfg(feature = "testing")]
+    assert!(iex::testing::exception_is_clear());
+
+    // Later errors are not affected
+    assert_eq!(fails(2).into_result(), Err(2));
+    assert_eq!(fails(3).map_err(|err| err * 10).into_result(), Err(30));
+}
+
+struct ResolvesOnDrop;
+
+impl Drop for ResolvesOnDrop {
+    fn drop(&mut self) {
+        assert_eq!(fails(5).into_result(), Err(5));
+    }
+}
+
+#[iex]
+fn with_resolving_guard(err: i32, outer_called: &Cell<bool>) -> Result<i32, String> {
+    let _guard = ResolvesOnDrop;
+    Ok(panics_in_map_err(err, outer_called)? + 1)
+}
+
+#[test]
+fn failing_outcome_in_destructor_after_handler_panic() {
+    let outer_called = Cell::new(false);
+    let payload = catch_unwind(AssertUnwindSafe(|| {
+        with_resolving_guard(4, &outer_called).into_result()
+    }))
+    .unwrap_err();
+    // The destructor's error is handled locally, and the handler's panic reaches the outer catch
+    assert_eq!(panic_message(payload), "Handler panicked on 4");
+    assert!(!outer_called.get());
+    #[cfg(feature = "testing")]
+    assert!(iex::testing::exception_is_clear());
+}
+
+#[iex]
+fn panics_in_inspect_err(err: i32) -> Result<i32, i32> {
+    fails(err).inspect_err(|_| panic!("Inspector panicked"))
+}
+
+#[iex]
+fn panics_in_error_mapper(err: i32) -> Result<i32, i32> {
+    ErrorMapper::new(err, |state: i32, _err: i32| -> i32 {
+        panic!("Mapper panicked with state {state}")
+    })
+    .run(|state| fails(*state))
+}
+
+#[test]
+fn other_handlers() {
+    let payload = catch_unwind(|| panics_in_inspect_err(1).into_result()).unwrap_err();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"Inspector panicked"));
+    let payload = catch_unwind(|| panics_in_error_mapper(2).into_result()).unwrap_err();
+    assert_eq!(panic_message(payload), "Mapper panicked with state 2");
+    #[cfg(feature = "testing")]
+    assert!(iex::testing::exception_is_clear());
+}
+
+#[test]
+fn handler_panic_mapping_to_infallible() {
+    let payload = catch_unwind(|| {
+        fails(6)
+            .map_err(|err| -> Infallible { panic!("Handler panicked on {err}") })
+            .into_result()
+    })
+    .unwrap_err();
+    assert_eq!(panic_message(payload), "Handler panicked on 6");
+    // The panic must not stay around for the next catch
+    assert_eq!(fails(7).into_result(), Err(7));
+}
+
+#[iex]
+fn panics_in_map_err_kind(err: i32) -> Result<i32, String> {
+    fails(err).map_err_kind(|err| -> Result<i32, String> { panic!("Handler panicked on {err}") })
+}
+
+#[test]
+fn map_err_kind_panic_is_a_regular_panic() {
+    let payload = catch_unwind(|| panics_in_map_err_kind(4).into_result()).unwrap_err();
+    assert_eq!(panic_message(payload), "Handler panicked on 4");
+    #[cfg(feature = "testing")]
+    assert!(iex::testing::exception_is_clear());
+    assert_eq!(fails(5).into_result(), Err(5));
+}
@@ -0,0 +1,29 @@
+use iex::{iex, Outcome, PollableOutcome};
+
+#[iex]
+fn checked_divide(a: u32, b: u32) -> Result<u32, &'static str> {
+    a.checked_div(b).ok_or("Cannot divide by zero")
+}
+
+#[test]
+fn resolves_once() {
+    let mut ok = checked_divide(6, 2).into_pollable();
+    assert!(!ok.is_resolved());
+    assert_eq!(ok.poll_once(), Some(Ok(3)));
+    assert!(ok.is_resolved());
+    assert_eq!(ok.poll_once(), None);
+
+    let mut err = PollableOutcome::new(checked_divide(6, 0));
+    assert_eq!(err.poll_once(), Some(Err("Cannot divide by zero")));
+    assert_eq!(err.poll_once(), None);
+}
+
+#[test]
+fn stored_in_collection() {
+    let mut outcomes: Vec<_> = (0..3)
+        .map(|b| checked_divide(6, b).into_pollable())
+        .collect();
+    let results: Vec<_> = outcomes.iter_mut().filter_map(|o| o.poll_once()).collect();
+    assert_eq!(results, [Err("Cannot divide by zero"), Ok(6), Ok(3)]);
+    assert!(outcomes.iter_mut().all(|o| o.poll_once().is_none()));
+}
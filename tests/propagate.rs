@@ -32,3 +32,64 @@ fn simple_propagation() {
         "Cannot divide by zero",
     );
 }
+
+#[iex]
+fn propagates_into_result(a: u32, b: u32) -> Result<u32, String> {
+    let x = checked_divide(a, b).into_result()?;
+    let y = result_divide(x, 1).into_result()?;
+    let z = checked_divide(y, 1)
+        .map_err(|err| format!("Mapped: {err}"))
+        .into_result()?;
+    Ok(z)
+}
+
+#[test]
+fn into_result_question_mark() {
+    assert_eq!(propagates_into_result(6, 2).into_result(), Ok(3));
+    assert_eq!(
+        propagates_into_result(6, 0).into_result(),
+        Err("Cannot divide by zero".to_string())
+    );
+}
+
+// Not an outcome, but has a method with the same name
+struct Reply(u32);
+
+impl Reply {
+    fn into_result(self) -> Result<u32, String> {
+        if self.0 == 0 {
+            Err("Empty reply".to_string())
+        } else {
+            Ok(self.0)
+        }
+    }
+}
+
+#[iex]
+fn reply_into_result(x: u32) -> Result<u32, String> {
+    Ok(Reply(x).into_result()? + 1)
+}
+
+#[iex]
+fn generic_into_result<O: Outcome<Output = u32, Error = &'static str>>(
+    outcome: O,
+) -> Result<u32, String> {
+    Ok(outcome.into_result()? + 1)
+}
+
+#[test]
+fn into_result_on_other_types() {
+    assert_eq!(reply_into_result(1).into_result(), Ok(2));
+    assert_eq!(
+        reply_into_result(0).into_result(),
+        Err("Empty reply".to_string())
+    );
+    assert_eq!(
+        generic_into_result(checked_divide(6, 2)).into_result(),
+        Ok(4)
+    );
+    assert_eq!(
+        generic_into_result(checked_divide(6, 0)).into_result(),
+        Err("Cannot divide by zero".to_string())
+    );
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "rayon")]
+
+use iex::{iex, IexParallelIteratorExt, Outcome};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use std::collections::HashSet;
+
+#[iex]
+fn check(x: i32) -> Result<i32, i32> {
+    if x < 0 {
+        Err(x)
+    } else {
+        Ok(x + 1)
+    }
+}
+
+#[iex]
+fn check_all(xs: Vec<i32>) -> Result<Vec<i32>, i32> {
+    xs.into_par_iter().map(check).try_collect_iex()
+}
+
+#[test]
+fn collects() {
+    assert_eq!(
+        check_all((0..1000).collect()).into_result(),
+        Ok((1..1001).collect())
+    );
+    assert_eq!(
+        (0..10)
+            .into_par_iter()
+            .map(check)
+            .try_collect_iex::<HashSet<_>>()
+            .into_result(),
+        Ok((1..11).collect())
+    );
+}
+
+#[test]
+fn rethrows() {
+    assert_eq!(check_all(vec![1, -2, 3]).into_result(), Err(-2));
+    let err = check_all((-500..500).collect()).into_result().unwrap_err();
+    assert!((-500..0).contains(&err));
+}
+
+#[test]
+fn propagates_through_iex() {
+    #[iex]
+    fn outer(xs: Vec<i32>) -> Result<usize, i32> {
+        Ok(check_all(xs)?.len())
+    }
+    assert_eq!(outer(vec![1, 2]).into_result(), Ok(2));
+    assert_eq!(outer(vec![1, -2]).into_result(), Err(-2));
+}
+
+#[test]
+fn resolves_on_pool() {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(2)
+        .build()
+        .unwrap();
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    for x in [1, -2, 3] {
+        let sender = sender.clone();
+        pool.spawn(move || sender.send((x, check(x).resolve())).unwrap());
+    }
+    drop(sender);
+    let mut results: Vec<_> = receiver.iter().collect();
+    results.sort();
+    assert_eq!(results, [(-2, Err(-2)), (1, Ok(2)), (3, Ok(4))]);
+
+    assert_eq!(pool.install(|| check(-5).resolve()), Err(-5));
+}
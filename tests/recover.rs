@@ -0,0 +1,100 @@
+use iex::{iex, Outcome};
+
+#[derive(Debug, PartialEq)]
+struct Wrapped(i32);
+
+impl From<i32> for Wrapped {
+    fn from(err: i32) -> Self {
+        Self(err)
+    }
+}
+
+#[iex]
+fn fails_with(err: i32) -> Result<i32, i32> {
+    Err(err)
+}
+
+#[iex]
+fn recovers_even(err: i32) -> Result<i32, Wrapped> {
+    fails_with(err).recover_or_rethrow(|err| (err % 2 == 0).then_some(-err))
+}
+
+#[test]
+fn recover_or_rethrow() {
+    assert_eq!(recovers_even(2).into_result(), Ok(-2));
+    assert_eq!(recovers_even(3).into_result(), Err(Wrapped(3)));
+    assert_eq!(
+        Ok::<i32, i32>(1)
+            .recover_or_rethrow::<Wrapped, _>(|_| unreachable!())
+            .into_result(),
+        Ok(1)
+    );
+}
+
+#[iex]
+fn succeeds_with(value: i32) -> Result<i32, i32> {
+    Ok(value)
+}
+
+#[test]
+fn err_or_else() {
+    assert_eq!(fails_with(1).err_or_else(|_| unreachable!()), 1);
+    assert_eq!(succeeds_with(2).err_or_else(|value| -value), -2);
+    assert_eq!(Err::<i32, i32>(3).err_or_else(|_| unreachable!()), 3);
+}
+
+#[iex]
+fn maps_by_kind(err: i32) -> Result<i32, Wrapped> {
+    fails_with(err).map_err_kind(|err| {
+        if err % 2 == 0 {
+            Ok(-err)
+        } else {
+            Err(Wrapped(err * 10))
+        }
+    })
+}
+
+#[iex]
+fn maps_by_kind_via_iex(err: i32) -> Result<i32, Wrapped> {
+    // `f` runs while the original error unwinds, but may still throw and recover on its own
+    fails_with(err).map_err_kind(|err| fails_with(err + 1).into_result().map_err(Wrapped))
+}
+
+#[test]
+fn map_err_kind() {
+    assert_eq!(maps_by_kind(2).into_result(), Ok(-2));
+    assert_eq!(maps_by_kind(3).into_result(), Err(Wrapped(30)));
+    assert_eq!(maps_by_kind_via_iex(1).into_result(), Err(Wrapped(2)));
+    assert_eq!(
+        succeeds_with(4)
+            .map_err_kind::<Wrapped, _>(|_| unreachable!())
+            .into_result(),
+        Ok(4)
+    );
+}
+
+#[iex]
+fn fallback(err: i32, delta: i32) -> Result<i32, Wrapped> {
+    if delta < 0 {
+        Err(Wrapped(err * delta))
+    } else {
+        Ok(err + delta)
+    }
+}
+
+#[iex]
+fn falls_back(err: i32, delta: i32) -> Result<i32, Wrapped> {
+    fails_with(err).or_else_value(move |err| fallback(err, delta))
+}
+
+#[test]
+fn or_else_value() {
+    assert_eq!(falls_back(1, 2).into_result(), Ok(3));
+    assert_eq!(falls_back(3, -1).into_result(), Err(Wrapped(-3)));
+    assert_eq!(
+        succeeds_with(4)
+            .or_else_value(|_| -> Result<i32, Wrapped> { unreachable!() })
+            .into_result(),
+        Ok(4)
+    );
+}
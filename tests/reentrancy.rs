@@ -0,0 +1,71 @@
+use iex::{iex, Outcome};
+
+#[iex]
+fn fails(err: String) -> Result<(), String> {
+    Err(err)
+}
+
+struct CallsIexOnDrop;
+
+impl Drop for CallsIexOnDrop {
+    fn drop(&mut self) {
+        assert_eq!(
+            fails("From drop".to_string()).into_result(),
+            Err("From drop".to_string())
+        );
+    }
+}
+
+#[iex]
+fn fails_with_guard() -> Result<(), String> {
+    let _guard = CallsIexOnDrop;
+    fails("Original".to_string())?;
+    Ok(())
+}
+
+#[iex]
+fn maps_with_guard() -> Result<(), String> {
+    fails_with_guard().map_err(|err| format!("{err}, mapped"))
+}
+
+#[test]
+fn drop_during_unwinding() {
+    assert_eq!(
+        fails_with_guard().into_result(),
+        Err("Original".to_string())
+    );
+    assert_eq!(
+        maps_with_guard().into_result(),
+        Err("Original, mapped".to_string())
+    );
+}
+
+struct RecoversOnDrop;
+
+impl Drop for RecoversOnDrop {
+    fn drop(&mut self) {
+        let recovered = fails("From drop".to_string())
+            .recover_or_rethrow::<String, _>(|_| Some(()))
+            .into_result();
+        assert_eq!(recovered, Ok(()));
+        let rethrown = fails("From drop".to_string())
+            .recover_or_rethrow::<String, _>(|_| None)
+            .into_result();
+        assert_eq!(rethrown, Err("From drop".to_string()));
+    }
+}
+
+#[iex]
+fn fails_with_recovering_guard() -> Result<(), String> {
+    let _guard = RecoversOnDrop;
+    fails("Original".to_string())?;
+    Ok(())
+}
+
+#[test]
+fn recover_during_unwinding() {
+    assert_eq!(
+        fails_with_recovering_guard().into_result(),
+        Err("Original".to_string())
+    );
+}
@@ -0,0 +1,32 @@
+use iex::{iex, Outcome};
+
+#[iex]
+fn parse(s: &str) -> Result<i32, String> {
+    s.parse().map_err(|_| format!("Invalid number: {s}"))
+}
+
+#[iex]
+fn sum(a: &str, b: &str) -> Result<i32, String> {
+    Ok(parse(a)? + parse(b)?)
+}
+
+#[test]
+fn resolve_on_worker() {
+    let handles: Vec<_> = [("1", "2"), ("1", "x")]
+        .into_iter()
+        .map(|(a, b)| std::thread::spawn(move || sum(a, b).resolve()))
+        .collect();
+    let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    assert_eq!(results, [Ok(3), Err("Invalid number: x".to_string())]);
+}
+
+#[test]
+fn resolve_after_move() {
+    // Outcomes are lazy, so this runs and fails on the spawned thread only
+    let outcome = sum("y", "2");
+    let result = std::thread::spawn(move || outcome.resolve())
+        .join()
+        .unwrap();
+    assert_eq!(result, Err("Invalid number: y".to_string()));
+    assert_eq!(sum("3", "4").resolve(), Ok(7));
+}
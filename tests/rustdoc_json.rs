@@ -0,0 +1,86 @@
+// `#[iex]` emits a wrapper function for compilation and a separate function with the original
+// signature for rustdoc, gated by `cfg(not(doc))` and `cfg(doc)` respectively. Tools must only ever
+// see one of them. rust-analyzer doesn't set `cfg(doc)`, so it sees the wrapper; this checks that
+// rustdoc sees only the documentation function. Requires a nightly rustdoc for the JSON output.
+
+use serde_json::Value;
+use std::collections::HashMap;
+use std::process::Command;
+
+fn document() -> Value {
+    let target_dir = concat!(env!("CARGO_TARGET_TMPDIR"), "/rustdoc-json");
+    let status = Command::new(env!("CARGO"))
+        .args(["rustdoc", "--lib", "--manifest-path"])
+        .arg(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml"))
+        .args(["--target-dir", target_dir])
+        .args(["--", "-Z", "unstable-options", "--output-format", "json"])
+        .status()
+        .expect("failed to run cargo rustdoc");
+    assert!(status.success());
+    let json = std::fs::read_to_string(format!("{target_dir}/doc/iex.json")).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+// Counts functions by name among the direct children of the given items.
+fn functions(index: &Value, ids: &[Value]) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for id in ids {
+        let item = &index[id.to_string()];
+        if item["inner"].get("function").is_some() {
+            *counts
+                .entry(item["name"].as_str().unwrap().to_string())
+                .or_default() += 1;
+        }
+    }
+    counts
+}
+
+fn find<'a>(index: &'a Value, name: &str, kind: &str) -> &'a Value {
+    index
+        .as_object()
+        .unwrap()
+        .values()
+        .find(|item| {
+            item["crate_id"] == 0 && item["name"] == name && item["inner"].get(kind).is_some()
+        })
+        .unwrap_or_else(|| panic!("{kind} {name} not found"))
+}
+
+#[test]
+fn one_item_per_function() {
+    let krate = document();
+    let index = &krate["index"];
+
+    let module = find(index, "example", "module");
+    let counts = functions(
+        index,
+        module["inner"]["module"]["items"].as_array().unwrap(),
+    );
+    assert_eq!(counts["add"], 1);
+    assert_eq!(counts["add_outcomes"], 1);
+
+    let add = find(index, "add", "function");
+    assert!(add["docs"]
+        .as_str()
+        .unwrap()
+        .starts_with("Add numbers and check for overflow."));
+
+    let trait_ = find(index, "SayHello", "trait");
+    let counts = functions(index, trait_["inner"]["trait"]["items"].as_array().unwrap());
+    assert_eq!(counts["provided_method"], 1);
+    assert_eq!(counts["required_method"], 1);
+
+    let struct_ = find(index, "HasIexMethod", "struct");
+    let methods: Vec<Value> = struct_["inner"]["struct"]["impls"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|id| {
+            index[id.to_string()]["inner"]["impl"]["items"]
+                .as_array()
+                .unwrap()
+        })
+        .cloned()
+        .collect();
+    assert_eq!(functions(index, &methods)["iex_method"], 1);
+}
@@ -0,0 +1,90 @@
+#![cfg(feature = "testing")]
+
+use iex::{iex, testing, Outcome};
+
+#[iex]
+fn fails(err: i32) -> Result<i32, i32> {
+    Err(err)
+}
+
+#[iex]
+fn maps(err: i32) -> Result<i32, String> {
+    Ok(fails(err).map_err(|err| err.to_string())?)
+}
+
+#[iex]
+fn recovers(err: i32) -> Result<i32, i32> {
+    fails(err).recover_or_rethrow(|_| Some(0))
+}
+
+#[test]
+fn resolved_outcomes_leave_no_exception() {
+    assert_eq!(fails(1).into_result(), Err(1));
+    assert!(testing::exception_is_clear());
+    assert_eq!(maps(2).into_result(), Err("2".to_string()));
+    assert!(testing::exception_is_clear());
+    assert_eq!(recovers(3).into_result(), Ok(0));
+    assert!(testing::exception_is_clear());
+    assert_eq!(fails(4).err_or_else(|_| unreachable!()), 4);
+    assert!(testing::exception_is_clear());
+    assert_eq!(unsafe { fails(5).assume_err() }, 5);
+    assert!(testing::exception_is_clear());
+}
+
+#[test]
+fn random_trees() {
+    for tree in testing::random_tree_strategy().take(2000) {
+        tree.check();
+    }
+}
+
+#[test]
+fn random_trees_are_deterministic() {
+    let tree = testing::CallTree::random(42);
+    assert_eq!(
+        format!("{tree:?}"),
+        format!("{:?}", testing::CallTree::random(42))
+    );
+    // Both successes and failures are generated
+    let results: Vec<_> = testing::random_tree_strategy()
+        .take(100)
+        .map(|tree| tree.expected())
+        .collect();
+    assert!(results.iter().any(Result::is_ok));
+    assert!(results.iter().any(Result::is_err));
+}
+
+struct ObserveSlot<'a>(&'a std::cell::Cell<Option<bool>>);
+
+impl Drop for ObserveSlot<'_> {
+    fn drop(&mut self) {
+        self.0.set(Some(testing::exception_is_clear()));
+    }
+}
+
+#[iex]
+fn propagates(
+    result: Result<i32, i32>,
+    observed: &std::cell::Cell<Option<bool>>,
+) -> Result<i32, i32> {
+    let _guard = ObserveSlot(observed);
+    Ok(result? + 1)
+}
+
+#[test]
+fn result_state_transitions() {
+    let observed = std::cell::Cell::new(None);
+
+    // Ok: the slot is never written
+    assert_eq!(propagates(Ok(1), &observed).into_result(), Ok(2));
+    assert_eq!(observed.get(), Some(true));
+
+    // Err: the error is in the slot while unwinding, and removed by into_result
+    assert_eq!(propagates(Err(1), &observed).into_result(), Err(1));
+    assert_eq!(observed.get(), Some(false));
+    assert!(testing::exception_is_clear());
+
+    // into_result on a Result doesn't touch the slot
+    assert_eq!(Err::<i32, i32>(3).into_result(), Err(3));
+    assert!(testing::exception_is_clear());
+}
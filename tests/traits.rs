@@ -59,3 +59,125 @@ fn refined() {
     );
     assert_eq!("test".say_hello().into_result().unwrap(), "test");
 }
+
+trait Parse {
+    type Output;
+
+    #[iex]
+    fn parse<R: std::io::Read>(&self, reader: R) -> Result<Self::Output, std::io::Error>;
+
+    #[iex]
+    fn parse_str<'a>(&self, s: &'a str) -> Result<&'a str, String>;
+
+    #[iex]
+    fn parse_array<const N: usize>(&self, s: &str) -> Result<[u8; N], String>;
+
+    #[iex]
+    fn parse_with<'a, T, F, const N: usize>(&self, s: &'a str, f: F) -> Result<[T; N], String>
+    where
+        F: FnMut(&'a str) -> T;
+}
+
+struct Bytes;
+
+impl Parse for Bytes {
+    type Output = Vec<u8>;
+
+    #[iex]
+    fn parse<R: std::io::Read>(&self, mut reader: R) -> Result<Vec<u8>, std::io::Error> {
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        Ok(buf)
+    }
+
+    #[iex]
+    fn parse_str<'a>(&self, s: &'a str) -> Result<&'a str, String> {
+        s.strip_prefix('+').ok_or(format!("Missing + in {s:?}"))
+    }
+
+    #[iex]
+    fn parse_array<const N: usize>(&self, s: &str) -> Result<[u8; N], String> {
+        s.as_bytes()
+            .try_into()
+            .map_err(|_| format!("Expected {N} bytes"))
+    }
+
+    #[iex]
+    fn parse_with<'a, T, F, const N: usize>(&self, s: &'a str, f: F) -> Result<[T; N], String>
+    where
+        F: FnMut(&'a str) -> T,
+    {
+        let items: Vec<T> = s.split(',').map(f).collect();
+        items
+            .try_into()
+            .map_err(|items: Vec<T>| format!("Expected {N} items, got {}", items.len()))
+    }
+}
+
+#[iex]
+fn parse_generic<P: Parse>(parser: &P, input: &[u8]) -> Result<P::Output, std::io::Error> {
+    parser.parse(input)
+}
+
+#[test]
+fn method_generics() {
+    assert_eq!(Bytes.parse(&b"abc"[..]).into_result().unwrap(), b"abc");
+    assert_eq!(parse_generic(&Bytes, b"de").into_result().unwrap(), b"de");
+    assert_eq!(Bytes.parse_str("+1").into_result(), Ok("1"));
+    assert_eq!(
+        Bytes.parse_str("1").into_result(),
+        Err("Missing + in \"1\"".to_string())
+    );
+    assert_eq!(Bytes.parse_array::<2>("ab").into_result(), Ok(*b"ab"));
+    assert_eq!(
+        Bytes.parse_array::<3>("ab").into_result(),
+        Err("Expected 3 bytes".to_string())
+    );
+    assert_eq!(Bytes.parse_with("a,bc", str::len).into_result(), Ok([1, 2]));
+    assert_eq!(
+        Bytes.parse_with::<_, _, 3>("a,bc", str::len).into_result(),
+        Err("Expected 3 items, got 2".to_string())
+    );
+}
+
+trait Visitor {
+    type Value;
+
+    #[iex]
+    fn visit_i32(self, value: i32) -> Result<Self::Value, String>;
+}
+
+trait Deserializer {
+    #[iex]
+    fn deserialize<V: Visitor>(self, visitor: V) -> Result<V::Value, String>;
+}
+
+struct Doubler;
+
+impl Visitor for Doubler {
+    type Value = i64;
+
+    #[iex]
+    fn visit_i32(self, value: i32) -> Result<i64, String> {
+        Ok(i64::from(value) * 2)
+    }
+}
+
+impl Deserializer for &str {
+    #[iex]
+    fn deserialize<V: Visitor>(self, visitor: V) -> Result<V::Value, String> {
+        let value = self
+            .parse()
+            .map_err(|_| format!("Invalid number: {self}"))?;
+        visitor.visit_i32(value)
+    }
+}
+
+#[test]
+fn generic_return_type() {
+    assert_eq!("21".deserialize(Doubler).into_result(), Ok(42));
+    assert_eq!(
+        "x".deserialize(Doubler).into_result(),
+        Err("Invalid number: x".to_string())
+    );
+}
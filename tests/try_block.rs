@@ -0,0 +1,50 @@
+use iex::{iex, try_block, Outcome};
+
+#[iex]
+fn check(x: i32) -> Result<i32, String> {
+    if x < 0 {
+        Err(format!("{x} is negative"))
+    } else {
+        Ok(x)
+    }
+}
+
+fn first_large(xs: &[i32]) -> Result<i32, String> {
+    try_block!('blk: {
+        for &x in xs {
+            if check(x)? > 10 {
+                break 'blk x;
+            }
+        }
+        0
+    })
+    .into_result()
+}
+
+#[test]
+fn early_success() {
+    assert_eq!(first_large(&[1, 20, -1]), Ok(20));
+}
+
+#[test]
+fn early_error() {
+    assert_eq!(first_large(&[1, -1, 20]), Err("-1 is negative".to_string()));
+}
+
+#[test]
+fn fall_through() {
+    assert_eq!(first_large(&[1, 2, 3]), Ok(0));
+}
+
+#[test]
+fn braces() {
+    let result = try_block! {
+        'blk: {
+            if check(5)? == 5 {
+                break 'blk "five";
+            }
+            "other"
+        }
+    };
+    assert_eq!(result.into_result(), Ok("five"));
+}
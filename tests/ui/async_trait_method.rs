@@ -0,0 +1,19 @@
+use async_trait::async_trait;
+use iex::iex;
+
+#[async_trait]
+trait Service {
+    async fn scale(&self, input: &str) -> Result<u32, String>;
+}
+
+struct Scaler(u32);
+
+#[async_trait]
+impl Service for Scaler {
+    #[iex]
+    async fn scale(&self, input: &str) -> Result<u32, String> {
+        Ok(input.len() as u32 * self.0)
+    }
+}
+
+fn main() {}
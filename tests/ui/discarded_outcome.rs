@@ -0,0 +1,18 @@
+#![deny(unused_must_use)]
+
+use iex::iex;
+
+#[iex]
+fn process(item: i32) -> Result<(), i32> {
+    if item < 0 {
+        Err(item)
+    } else {
+        Ok(())
+    }
+}
+
+fn main() {
+    for item in [1, 2, 3] {
+        process(item);
+    }
+}
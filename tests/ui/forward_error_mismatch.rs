@@ -0,0 +1,31 @@
+use iex::iex;
+
+struct LowLevelError;
+struct HighLevelError;
+
+#[iex]
+fn low_level() -> Result<(), LowLevelError> {
+    Err(LowLevelError)
+}
+
+#[iex]
+fn propagates() -> Result<(), HighLevelError> {
+    low_level()?;
+    Ok(())
+}
+
+#[iex]
+fn tail_forwards(verbose: bool) -> Result<(), HighLevelError> {
+    if verbose {
+        println!("forwarding");
+    }
+    low_level()
+}
+
+// Pure forwarders return the callee's outcome itself, so the mismatch is reported twice
+#[iex]
+fn delegates() -> Result<(), HighLevelError> {
+    low_level()
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use iex::iex;
+
+#[iex]
+fn unannotated() -> impl iex::Outcome<Output = u32, Error = String> {
+    Ok::<_, String>(1)
+}
+
+#[iex(output = "u32")]
+fn half_annotated() -> impl iex::Outcome<Output = u32, Error = String> {
+    Ok::<_, String>(1)
+}
+
+fn main() {}
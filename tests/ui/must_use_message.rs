@@ -0,0 +1,20 @@
+#![deny(unused_must_use)]
+
+use iex::iex;
+
+#[must_use = "the file might not have been removed"]
+#[iex]
+fn remove_file() -> Result<(), ()> {
+    Ok(())
+}
+
+#[must_use]
+#[iex]
+fn remove_dir() -> Result<(), ()> {
+    Ok(())
+}
+
+fn main() {
+    remove_file();
+    remove_dir();
+}
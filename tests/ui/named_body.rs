@@ -0,0 +1,17 @@
+use iex::iex;
+
+struct Parser;
+
+impl Parser {
+    #[iex(named_body)]
+    fn parse(&self, input: &str) -> Result<u32, String> {
+        input.parse().map_err(|_| input.to_string())
+    }
+}
+
+#[iex(named_body)]
+fn first_word(input: &str) -> Result<&str, &str> {
+    input.split(' ').next().ok_or(input)
+}
+
+fn main() {}
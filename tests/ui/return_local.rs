@@ -0,0 +1,21 @@
+use iex::iex;
+
+#[iex]
+fn borrows_local(x: &str) -> Result<&str, ()> {
+    let s = x.to_uppercase();
+    Ok(s.as_str())
+}
+
+#[iex]
+fn wrong_lifetime<'a>(x: &'a str, y: &str) -> Result<&'a str, ()> {
+    let _ = x;
+    Ok(y)
+}
+
+#[iex]
+fn no_deref_coercion(x: &str) -> Result<&str, ()> {
+    let s = x.to_uppercase();
+    Ok(&s)
+}
+
+fn main() {}
@@ -0,0 +1,54 @@
+use iex::{iex, Outcome};
+
+// The body of an unsafe function is an unsafe context, including the generated closure
+#[iex]
+unsafe fn read_implicit(ptr: *const i32) -> Result<i32, ()> {
+    if ptr.is_null() {
+        Err(())
+    } else {
+        Ok(*ptr)
+    }
+}
+
+#[deny(unsafe_op_in_unsafe_fn)]
+#[iex]
+unsafe fn read_explicit(ptr: *const i32) -> Result<i32, ()> {
+    if ptr.is_null() {
+        Err(())
+    } else {
+        // SAFETY: Forwarded to the caller
+        Ok(unsafe { *ptr })
+    }
+}
+
+#[iex]
+fn calls_unsafe(ptr: *const i32) -> Result<i32, ()> {
+    // SAFETY: The pointer is either null or valid
+    Ok(unsafe { read_implicit(ptr)? + read_explicit(ptr)? })
+}
+
+struct Reader;
+
+impl Reader {
+    #[iex]
+    unsafe fn read(&self, ptr: *const i32) -> Result<i32, ()> {
+        if ptr.is_null() {
+            Err(())
+        } else {
+            Ok(*ptr)
+        }
+    }
+}
+
+#[test]
+fn unsafe_fn() {
+    let x = 5;
+    unsafe {
+        assert_eq!(read_implicit(&x).into_result(), Ok(5));
+        assert_eq!(read_explicit(&x).into_result(), Ok(5));
+        assert_eq!(read_implicit(std::ptr::null()).into_result(), Err(()));
+        assert_eq!(Reader.read(&x).into_result(), Ok(5));
+    }
+    assert_eq!(calls_unsafe(&x).into_result(), Ok(10));
+    assert_eq!(calls_unsafe(std::ptr::null()).into_result(), Err(()));
+}
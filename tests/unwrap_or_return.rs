@@ -0,0 +1,47 @@
+use iex::{iex, unwrap_or_return};
+
+#[derive(Debug, PartialEq)]
+struct Wrapped(i32);
+
+impl From<i32> for Wrapped {
+    fn from(err: i32) -> Self {
+        Self(err)
+    }
+}
+
+#[iex]
+fn check(x: i32) -> Result<i32, i32> {
+    if x < 0 {
+        Err(x)
+    } else {
+        Ok(x + 1)
+    }
+}
+
+fn converts(x: i32) -> Result<i32, Wrapped> {
+    let value = unwrap_or_return!(check(x));
+    Ok(value * 2)
+}
+
+fn maps(x: i32) -> Result<i32, String> {
+    let value = unwrap_or_return!(check(x), |err| format!("{err} is negative"));
+    Ok(value * 2)
+}
+
+fn plain_result(x: Result<i32, i32>) -> Result<i32, Wrapped> {
+    Ok(unwrap_or_return!(x) + 1)
+}
+
+#[test]
+fn from_conversion() {
+    assert_eq!(converts(1), Ok(4));
+    assert_eq!(converts(-1), Err(Wrapped(-1)));
+    assert_eq!(plain_result(Ok(1)), Ok(2));
+    assert_eq!(plain_result(Err(3)), Err(Wrapped(3)));
+}
+
+#[test]
+fn mapping_closure() {
+    assert_eq!(maps(1), Ok(4));
+    assert_eq!(maps(-1), Err("-1 is negative".to_string()));
+}
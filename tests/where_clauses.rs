@@ -0,0 +1,170 @@
+use iex::{iex, Outcome};
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+
+// A serde-style deserialization trait, borrowing from the input
+trait Deserialize<'de>: Sized {
+    #[iex]
+    fn deserialize(input: &'de str) -> Result<Self, String>;
+}
+
+impl<'de> Deserialize<'de> for &'de str {
+    #[iex]
+    fn deserialize(input: &'de str) -> Result<Self, String> {
+        input
+            .strip_prefix('"')
+            .and_then(|s| s.strip_suffix('"'))
+            .ok_or_else(|| format!("Not a string: {input}"))
+    }
+}
+
+impl Deserialize<'_> for u32 {
+    #[iex]
+    fn deserialize(input: &str) -> Result<Self, String> {
+        input.parse().map_err(|_| format!("Not a number: {input}"))
+    }
+}
+
+#[iex]
+fn from_str<'de, T>(input: &'de str) -> Result<T, String>
+where
+    T: Deserialize<'de>,
+{
+    T::deserialize(input)
+}
+
+#[iex]
+fn from_owned<T, E>(input: String) -> Result<T, E>
+where
+    T: for<'de> Deserialize<'de> + Debug,
+    E: From<String>,
+{
+    Ok(T::deserialize(&input)?)
+}
+
+#[iex]
+fn parse_all<I, T, E>(items: I) -> Result<Vec<T>, E>
+where
+    I: IntoIterator<Item: AsRef<str>>,
+    T: FromStr<Err: Display>,
+    E: From<String>,
+{
+    let mut output = Vec::new();
+    for item in items {
+        let item = item.as_ref();
+        output.push(item.parse::<T>().map_err(|err| format!("{item}: {err}"))?);
+    }
+    Ok(output)
+}
+
+#[iex]
+fn apply_each<F, E>(inputs: &[&str], f: F) -> Result<usize, E>
+where
+    F: for<'a> Fn(&'a str) -> Result<&'a str, E>,
+    for<'a> &'a str: Into<String>,
+{
+    let mut total = 0;
+    for input in inputs {
+        total += f(input)?.len();
+    }
+    Ok(total)
+}
+
+// The where clause mentions the declared return type, and the error type is a projection
+#[iex]
+fn parse_debug<T>(s: &str) -> Result<T, T::Err>
+where
+    T: FromStr,
+    Result<T, T::Err>: Debug,
+{
+    s.parse()
+}
+
+trait Source {
+    type Error: Debug;
+
+    #[iex]
+    fn read<'a, T>(&'a self) -> Result<T, Self::Error>
+    where
+        T: Deserialize<'a>,
+        Self::Error: From<String>;
+}
+
+struct Text(String);
+
+impl Source for Text {
+    type Error = String;
+
+    #[iex]
+    fn read<'a, T>(&'a self) -> Result<T, Self::Error>
+    where
+        T: Deserialize<'a>,
+        Self::Error: From<String>,
+    {
+        from_str(&self.0)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Error(String);
+
+impl From<String> for Error {
+    fn from(s: String) -> Self {
+        Self(s)
+    }
+}
+
+fn trim(s: &str) -> Result<&str, Error> {
+    let trimmed = s.trim();
+    if trimmed.is_empty() {
+        Err(Error("Empty".to_string()))
+    } else {
+        Ok(trimmed)
+    }
+}
+
+#[test]
+fn where_clauses() {
+    assert_eq!(from_str::<&str>("\"abc\"").into_result(), Ok("abc"));
+    assert_eq!(from_str::<u32>("12").into_result(), Ok(12));
+    assert_eq!(
+        from_str::<&str>("abc").into_result(),
+        Err("Not a string: abc".to_string())
+    );
+
+    assert_eq!(
+        from_owned::<u32, Error>("7".to_string()).into_result(),
+        Ok(7)
+    );
+    assert_eq!(
+        from_owned::<u32, Error>("x".to_string()).into_result(),
+        Err(Error("Not a number: x".to_string()))
+    );
+
+    assert_eq!(
+        parse_all::<_, u8, Error>(["1", "2"]).into_result(),
+        Ok(vec![1, 2])
+    );
+    assert_eq!(
+        parse_all::<_, u8, Error>(vec!["1".to_string(), "300".to_string()]).into_result(),
+        Err(Error(
+            "300: number too large to fit in target type".to_string()
+        ))
+    );
+
+    assert_eq!(apply_each(&[" a ", "bc "], trim).into_result(), Ok(3));
+    assert_eq!(
+        apply_each(&["a", " "], trim).into_result(),
+        Err(Error("Empty".to_string()))
+    );
+
+    assert_eq!(parse_debug::<i8>("-1").into_result(), Ok(-1));
+    assert!(parse_debug::<i8>("x").into_result().is_err());
+
+    let text = Text("\"hello\"".to_string());
+    assert_eq!(text.read::<&str>().into_result(), Ok("hello"));
+    assert_eq!(
+        text.read::<u32>().into_result(),
+        Err("Not a number: \"hello\"".to_string())
+    );
+}
@@ -0,0 +1,60 @@
+use iex::{iex, Outcome, SetLocation};
+use std::panic::Location;
+
+#[derive(Debug, PartialEq)]
+struct Error {
+    locations: Vec<(&'static str, u32)>,
+}
+
+impl SetLocation for Error {
+    fn set_location(&mut self, location: &'static Location<'static>) {
+        self.locations.push((location.file(), location.line()));
+    }
+}
+
+#[iex]
+fn fails(fail: bool) -> Result<i32, Error> {
+    if fail {
+        Err(Error {
+            locations: Vec::new(),
+        })
+    } else {
+        Ok(1)
+    }
+}
+
+#[iex]
+fn inner(fail: bool) -> Result<i32, Error> {
+    Ok(fails(fail).with_location()? + 1)
+}
+
+const INNER_LINE: u32 = line!() - 3;
+
+#[iex]
+fn outer(fail: bool) -> Result<i32, Error> {
+    let value = inner(fail).with_location()?;
+    Ok(value + 1)
+}
+
+const OUTER_LINE: u32 = line!() - 4;
+
+#[test]
+fn stamps_call_site() {
+    assert_eq!(outer(false).into_result(), Ok(3));
+    assert_eq!(
+        outer(true).into_result(),
+        Err(Error {
+            locations: vec![(file!(), INNER_LINE), (file!(), OUTER_LINE)],
+        })
+    );
+}
+
+#[test]
+fn plain_result() {
+    let result: Result<i32, Error> = Err(Error {
+        locations: Vec::new(),
+    });
+    let line = line!() + 1;
+    let err = result.with_location().into_result().unwrap_err();
+    assert_eq!(err.locations, [(file!(), line)]);
+}